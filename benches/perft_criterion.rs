@@ -3,7 +3,10 @@ use std::time::Duration;
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 
 use plum_chess::game_state::game_state::GameState;
-use plum_chess::move_generation::perft::perft_legal;
+use plum_chess::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+use plum_chess::move_generation::legal_move_pin_mask::generate_legal_move_descriptions_pin_mask;
+use plum_chess::move_generation::move_list::MoveList;
+use plum_chess::move_generation::perft::{perft_legal, perft_nodes};
 
 #[derive(Clone, Copy)]
 struct BenchCase {
@@ -119,5 +122,54 @@ fn bench_perft(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(perft_benches, bench_perft);
+/// Heap `Vec<u64>` per call versus the stack-allocated `MoveList`.
+fn bench_move_list(c: &mut Criterion) {
+    let mut group = c.benchmark_group("movegen_vec_vs_move_list");
+    group.warm_up_time(Duration::from_secs(1));
+    group.measurement_time(Duration::from_secs(3));
+
+    for case in CASES_STANDARD {
+        let game = GameState::from_fen(case.fen).expect("benchmark FEN should parse");
+        group.throughput(Throughput::Elements(case.expected_nodes[0]));
+
+        group.bench_function(BenchmarkId::new("vec", case.name), |b| {
+            let mut probe = game.clone();
+            b.iter(|| {
+                let moves = generate_legal_move_descriptions_in_place(black_box(&mut probe))
+                    .expect("generation should succeed");
+                black_box(moves.len())
+            });
+        });
+        group.bench_function(BenchmarkId::new("move_list", case.name), |b| {
+            b.iter(|| {
+                let mut list = MoveList::new();
+                generate_legal_move_descriptions_pin_mask(black_box(&game), &mut list);
+                black_box(list.len())
+            });
+        });
+    }
+
+    group.finish();
+
+    let mut group = c.benchmark_group("perft_make_unmake_move_list");
+    group.warm_up_time(Duration::from_secs(1));
+    group.measurement_time(Duration::from_secs(4));
+    group.sample_size(20);
+    for case in selected_cases() {
+        let depth = case.expected_nodes.len() as u8;
+        let expected = *case.expected_nodes.last().expect("cases list depths");
+        let mut game = GameState::from_fen(case.fen).expect("benchmark FEN should parse");
+        group.throughput(Throughput::Elements(expected));
+        group.bench_function(BenchmarkId::from_parameter(case.name), |b| {
+            b.iter(|| {
+                let nodes = perft_nodes(black_box(&mut game), depth).expect("perft should run");
+                assert_eq!(nodes as u64, expected);
+                black_box(nodes)
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(perft_benches, bench_perft, bench_move_list);
 criterion_main!(perft_benches);
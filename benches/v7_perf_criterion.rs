@@ -4,9 +4,7 @@ use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criteri
 
 use plum_chess::move_generation::legal_move_generator::FastLegalMoveGenerator;
 use plum_chess::search::board_scoring::EndgameTaperedScorerV14;
-use plum_chess::search::iterative_deepening_v15::{
-    iterative_deepening_search_with_tt, SearchConfig,
-};
+use plum_chess::search::iterative_deepening::{iterative_deepening_search_with_tt, SearchConfig};
 use plum_chess::search::transposition_table_v11::TranspositionTable;
 use plum_chess::utils::fen_parser::parse_fen;
 
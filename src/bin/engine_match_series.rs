@@ -3,37 +3,106 @@
 //! Run with:
 //! `cargo run --release --bin engine_match_series`
 //! `cargo run --release --bin engine_match_series -- --verbose`
-
-use plum_chess::engines::engine_trait::Engine;
+//!
+//! Flags:
+//! - `--games N`: number of games (default 10).
+//! - `--depth N` / `--nodes N`: fixed search depth or node budget per move.
+//! - `--player1 KIND` / `--player2 KIND`: `alphazero` or `standard` scorer.
+//! - `--p1-option NAME=VALUE` / `--p2-option NAME=VALUE`: `setoption` for one
+//!   player, repeatable (e.g. `--p1-option SingularExtensions=true`).
+
+use plum_chess::engines::engine_iterative_v16::{IterativeEngine, IterativeScorerKind};
+use plum_chess::engines::engine_trait::{Engine, GoParams};
 use plum_chess::utils::engine_match_harness::{
     play_engine_match_series, MatchConfig, MatchSeriesConfig,
 };
 
+struct PlayerSpec {
+    scorer: IterativeScorerKind,
+    options: Vec<(String, String)>,
+}
+
+impl PlayerSpec {
+    fn build(&self) -> Result<Box<dyn Engine>, String> {
+        let mut engine = IterativeEngine::new_with_scorer(6, self.scorer);
+        for (name, value) in &self.options {
+            engine.set_option(name, value)?;
+        }
+        Ok(Box::new(engine))
+    }
+}
+
+fn parse_scorer(value: &str) -> Result<IterativeScorerKind, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "alphazero" | "alpha_zero" => Ok(IterativeScorerKind::AlphaZero),
+        "standard" => Ok(IterativeScorerKind::Standard),
+        _ => Err(format!("unknown player kind '{value}'")),
+    }
+}
+
+fn parse_option(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{value}'"))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {flag} value '{value}'"))
+}
+
 // Use 'cargo run --release --bin engine_match_series -- --verbose' to run this
 fn main() -> Result<(), String> {
-    let verbose = std::env::args().any(|a| a == "--verbose" || a == "-v");
-
-    // Customize these two lines to experiment with different engines/scorers/depths.
-    let player1 = || {
-        Box::new(plum_chess::engines::engine_iterative_v16::IterativeEngine::new_alpha_zero(6))
-            as Box<dyn Engine>
+    let mut verbose = false;
+    let mut games = 10u16;
+    let mut go_params = GoParams::default();
+    let mut player1 = PlayerSpec {
+        scorer: IterativeScorerKind::AlphaZero,
+        options: Vec::new(),
     };
-    let player2 = || {
-        Box::new(plum_chess::engines::engine_iterative_v16::IterativeEngine::new_standard(6))
-            as Box<dyn Engine>
+    let mut player2 = PlayerSpec {
+        scorer: IterativeScorerKind::Standard,
+        options: Vec::new(),
     };
 
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--verbose" || arg == "-v" {
+            verbose = true;
+            continue;
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {arg}"))?;
+        match arg.as_str() {
+            "--games" => games = parse_number(&arg, &value)?,
+            "--depth" => go_params.depth = Some(parse_number(&arg, &value)?),
+            "--nodes" => go_params.nodes = Some(parse_number(&arg, &value)?),
+            "--player1" => player1.scorer = parse_scorer(&value)?,
+            "--player2" => player2.scorer = parse_scorer(&value)?,
+            "--p1-option" => player1.options.push(parse_option(&value)?),
+            "--p2-option" => player2.options.push(parse_option(&value)?),
+            _ => return Err(format!("unknown argument '{arg}'")),
+        }
+    }
+
+    // Surface bad option values before any game starts.
+    player1.build()?;
+    player2.build()?;
+
     let stats = play_engine_match_series(
-        player1,
-        player2,
+        || player1.build().expect("player 1 options were validated"),
+        || player2.build().expect("player 2 options were validated"),
         MatchSeriesConfig {
-            games: 10,
+            games,
             base_seed: 1234,
             per_game: MatchConfig {
                 max_plies: 200,
                 opening_min_plies: 2,
                 opening_max_plies: 6,
-                ..MatchConfig::default()
+                go_params,
             },
             verbose,
         },
@@ -155,4 +224,33 @@ vs
 plum_chess::engines::engine_iterative_v14::IterativeEngine::new_alpha_zero(6) = 4 wins @ 76.535 ms per move
 Conclusion:  Adding end-game logic leads to less draws and longer games
 
+---
+
+Singular extensions (standard scorer both sides, only player 1 toggled):
+--depth 8, 10 games:
+SingularExtensions=true  -> 6 wins / 2 losses / 2 draws @ 94.0 vs 71.4 ms per move
+SingularExtensions=false -> 6 wins / 3 losses / 1 draw  @ 81.7 vs 74.4 ms per move (control)
+--nodes 30000, 20 games:
+SingularExtensions=true  -> 11 wins / 2 losses / 7 draws, avg depth 5.72 vs 5.82
+SingularExtensions=false -> 12 wins / 3 losses / 5 draws, avg depth 5.93 vs 5.95 (control)
+Conclusion:  Within noise of the identical-engine control (player 1 is favoured by
+the seeded openings either way) and 15-30% slower per move, so the option stays off
+by default.
+
+---
+
+ProbCut and IIR (standard scorer both sides, only player 1 toggled):
+--nodes 30000, 20 games:
+ProbCut=true           -> 10 wins / 6 losses / 4 draws, avg depth 5.92 vs 5.94
+IIR=true               -> 8 wins / 6 losses / 6 draws, avg depth 5.90 vs 5.89
+ProbCut=true IIR=true  -> 9 wins / 6 losses / 5 draws, avg depth 5.89 vs 5.89
+control (3 runs)       -> 9/5/6, 9/7/4, 5/9/6
+--depth 8, 20 games:
+ProbCut=true IIR=true  -> 6 wins / 3 losses / 11 draws @ 71.8 vs 75.3 ms per move
+control                -> 3 wins / 6 losses / 11 draws @ 69.8 vs 77.8 ms per move
+tactical_suite (20 WAC positions, depth 8): v15 16 solved / 1.26M nodes,
+ProbCut 16 / 1.24M, IIR 16 / 1.02M, both 16 / 1.02M.
+Conclusion:  No tactics lost and IIR saves ~20% of nodes at fixed depth, but the
+match results stay inside the control's spread, so both options stay off by default.
+
 */
@@ -0,0 +1,288 @@
+use plum_chess::game_state::game_state::GameState;
+use plum_chess::move_generation::legal_move_pin_mask::FastPinMaskMoveGenerator;
+use plum_chess::move_generation::perft::{
+    format_perft_divide, perft_divide, perft_legal, perft_multi_threaded_with, perft_nodes,
+    perft_nodes_hashed, PerftMode,
+};
+use plum_chess::move_generation::perft_suite::load_perft_suite;
+use plum_chess::move_generation::perft_table::PerftTable;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Instant;
+
+const USAGE: &str = "\
+usage: perft [--max-depth N] [--threads] [--hash MB] SUITE.epd [SUITE.epd ...]
+       perft --fen \"<FEN>\" --depth N [--divide] [--threads] [--hash MB]
+       perft --bench-csv OUT.csv [--max-depth N]";
+
+struct Options {
+    suites: Vec<String>,
+    fen: Option<String>,
+    depth: u8,
+    max_depth: u8,
+    divide: bool,
+    threads: bool,
+    /// Perft table size; node counting is hashed when set.
+    hash_mb: Option<usize>,
+    bench_csv: Option<String>,
+}
+
+fn parse_options(args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        suites: Vec::new(),
+        fen: None,
+        depth: 1,
+        max_depth: u8::MAX,
+        divide: false,
+        threads: false,
+        hash_mb: None,
+        bench_csv: None,
+    };
+    let mut i = 0usize;
+    while i < args.len() {
+        let value = |i: usize| {
+            args.get(i + 1)
+                .cloned()
+                .ok_or_else(|| format!("missing value after {}", args[i]))
+        };
+        match args[i].as_str() {
+            "--fen" => {
+                options.fen = Some(value(i)?);
+                i += 1;
+            }
+            "--depth" => {
+                options.depth = value(i)?.parse().map_err(|_| "invalid --depth")?;
+                i += 1;
+            }
+            "--max-depth" => {
+                options.max_depth = value(i)?.parse().map_err(|_| "invalid --max-depth")?;
+                i += 1;
+            }
+            "--hash" => {
+                options.hash_mb = Some(value(i)?.parse().map_err(|_| "invalid --hash")?);
+                i += 1;
+            }
+            "--bench-csv" => {
+                options.bench_csv = Some(value(i)?);
+                i += 1;
+            }
+            "--divide" => options.divide = true,
+            "--threads" => options.threads = true,
+            "-h" | "--help" => return Err(USAGE.to_owned()),
+            flag if flag.starts_with("--") => return Err(format!("unknown flag {flag}")),
+            path => options.suites.push(path.to_owned()),
+        }
+        i += 1;
+    }
+    if options.fen.is_none() && options.suites.is_empty() && options.bench_csv.is_none() {
+        return Err(USAGE.to_owned());
+    }
+    Ok(options)
+}
+
+/// Leaf count at `depth` with bulk counting, split across one thread per root
+/// move when `threads` and cached in `table` when given.
+fn count_nodes(
+    game_state: &GameState,
+    depth: u8,
+    threads: bool,
+    table: Option<&Arc<PerftTable>>,
+) -> Result<usize, String> {
+    let nodes = if threads {
+        let mode = PerftMode::Bulk {
+            table: table.cloned(),
+        };
+        perft_multi_threaded_with(Arc::new(FastPinMaskMoveGenerator), game_state, depth, &mode)
+            .map(|counts| counts.nodes)
+    } else if let Some(table) = table {
+        perft_nodes_hashed(&mut game_state.clone(), depth, table)
+    } else {
+        perft_nodes(&mut game_state.clone(), depth)
+    };
+    nodes.map_err(|e| e.to_string())
+}
+
+fn nodes_per_second(nodes: usize, seconds: f64) -> u64 {
+    if seconds > 0.0 {
+        (nodes as f64 / seconds) as u64
+    } else {
+        0
+    }
+}
+
+fn run_single(fen: &str, options: &Options) -> Result<(), String> {
+    let game_state = GameState::from_fen(fen)?;
+    let start = Instant::now();
+    let nodes = if options.divide {
+        let entries = perft_divide(&game_state, options.depth).map_err(|e| e.to_string())?;
+        print!("{}", format_perft_divide(&entries));
+        entries.iter().map(|e| e.nodes).sum()
+    } else {
+        let table = options
+            .hash_mb
+            .map(|mb| Arc::new(PerftTable::new_with_mb(mb)));
+        let nodes = count_nodes(&game_state, options.depth, options.threads, table.as_ref())?;
+        println!("Nodes searched: {nodes}");
+        nodes
+    };
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "time {:.3}s nps {}",
+        seconds,
+        nodes_per_second(nodes, seconds)
+    );
+    Ok(())
+}
+
+fn run_suites(options: &Options) -> Result<bool, String> {
+    let mut checked = 0usize;
+    let mut failures = 0usize;
+    let mut total_nodes = 0usize;
+    let start = Instant::now();
+    let table = options
+        .hash_mb
+        .map(|mb| Arc::new(PerftTable::new_with_mb(mb)));
+
+    for path in &options.suites {
+        let entries = load_perft_suite(path)?;
+        println!("suite {path}: {} positions", entries.len());
+        for (idx, entry) in entries.iter().enumerate() {
+            let game_state = GameState::from_fen(&entry.fen)?;
+            for &(depth, expected) in entry
+                .expected
+                .iter()
+                .filter(|(d, _)| *d <= options.max_depth)
+            {
+                let depth_start = Instant::now();
+                let actual = count_nodes(&game_state, depth, options.threads, table.as_ref())?;
+                let seconds = depth_start.elapsed().as_secs_f64();
+                checked += 1;
+                total_nodes += actual;
+                let status = if actual == expected {
+                    "ok"
+                } else {
+                    failures += 1;
+                    "MISMATCH"
+                };
+                println!(
+                    "{status:>8} #{} D{depth} expected {expected} got {actual} ({:.3}s, {} nps)",
+                    idx + 1,
+                    seconds,
+                    nodes_per_second(actual, seconds)
+                );
+                if actual != expected {
+                    println!("         fen {}", entry.fen);
+                }
+            }
+        }
+    }
+
+    let seconds = start.elapsed().as_secs_f64();
+    println!(
+        "checked {checked} counts, {failures} mismatches, {total_nodes} nodes in {:.3}s ({} nps)",
+        seconds,
+        nodes_per_second(total_nodes, seconds)
+    );
+    Ok(failures == 0)
+}
+
+/// Benchmark positions: start position, "Kiwipete" and the rook endgame.
+const BENCH_CASES: [(&str, &str, &[usize]); 3] = [
+    (
+        "position_1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        &[
+            20,
+            400,
+            8902,
+            197_281,
+            4_865_609,
+            119_060_324,
+            3_195_901_860,
+        ],
+    ),
+    (
+        "position_2",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        &[48, 2039, 97_862, 4_085_603, 193_690_690],
+    ),
+    (
+        "position_3",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        &[14, 191, 2812, 43_238, 674_624, 11_030_083],
+    ),
+];
+
+/// Time the detailed-count mode (`perft_legal`, capped at depth 4) and the
+/// fast bulk + hashed mode over `BENCH_CASES`, writing one CSV row per run.
+fn run_bench_csv(path: &str, options: &Options) -> Result<bool, String> {
+    const DETAILED_MAX_DEPTH: u8 = 4;
+    let mut csv =
+        String::from("case,mode,depth,expected_nodes,actual_nodes,iteration,elapsed_ms,nps\n");
+    let mut all_match = true;
+    // Build attack and hash tables before the first timed run.
+    perft_nodes(&mut GameState::new_game(), 1).map_err(|e| e.to_string())?;
+    for (name, fen, expected) in BENCH_CASES {
+        let game_state = GameState::from_fen(fen)?;
+        for (idx, &expected_nodes) in expected.iter().enumerate() {
+            let depth = (idx + 1) as u8;
+            if depth > options.max_depth {
+                break;
+            }
+            for mode in ["detailed", "fast"] {
+                if mode == "detailed" && depth > DETAILED_MAX_DEPTH {
+                    continue;
+                }
+                let start = Instant::now();
+                let actual = if mode == "detailed" {
+                    perft_legal(&game_state, depth)
+                        .map(|counts| counts.nodes)
+                        .map_err(|e| e.to_string())?
+                } else {
+                    let table = PerftTable::new_with_mb(64);
+                    perft_nodes_hashed(&mut game_state.clone(), depth, &table)
+                        .map_err(|e| e.to_string())?
+                };
+                let seconds = start.elapsed().as_secs_f64();
+                all_match &= actual == expected_nodes;
+                let _ = writeln!(
+                    csv,
+                    "{name},{mode},{depth},{expected_nodes},{actual},1,{:.3},{}",
+                    seconds * 1000.0,
+                    nodes_per_second(actual, seconds)
+                );
+                println!("{name} {mode} D{depth}: {actual} nodes in {seconds:.3}s");
+            }
+        }
+    }
+    fs::write(path, csv).map_err(|e| format!("cannot write {path}: {e}"))?;
+    Ok(all_match)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_options(&args) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = match (options.bench_csv.as_deref(), options.fen.as_deref()) {
+        (Some(path), _) => run_bench_csv(path, &options),
+        (None, Some(fen)) => run_single(fen, &options).map(|()| true),
+        (None, None) => run_suites(&options),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("perft error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
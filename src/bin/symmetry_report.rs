@@ -0,0 +1,74 @@
+//! Colour-flip and mirror symmetry report for scorers and iterative engines.
+//!
+//! Run with:
+//! `cargo run --release --bin symmetry_report -- [--depth N] [--scorers-only]`
+//!
+//! Prints one line per asymmetry found over `SYMMETRY_CORPUS` and exits
+//! non-zero when any are reported.
+
+use plum_chess::engines::engine_trait::GoParams;
+use plum_chess::engines::symmetry_harness::{
+    all_board_scorers, engine_asymmetries, iterative_engines, scorer_asymmetries, SYMMETRY_CORPUS,
+};
+use std::process::ExitCode;
+
+fn run() -> Result<usize, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut depth = 3u8;
+    let mut scorers_only = false;
+    let mut i = 0usize;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--depth" => {
+                i += 1;
+                let value = args.get(i).ok_or("--depth needs a value")?;
+                depth = value
+                    .parse()
+                    .map_err(|_| format!("invalid depth '{value}'"))?;
+            }
+            "--scorers-only" => scorers_only = true,
+            other => return Err(format!("unknown argument '{other}'")),
+        }
+        i += 1;
+    }
+
+    let mut report = Vec::new();
+    for (name, scorer) in all_board_scorers() {
+        report.extend(scorer_asymmetries(name, scorer.as_ref(), &SYMMETRY_CORPUS)?);
+    }
+    if !scorers_only {
+        let params = GoParams {
+            depth: Some(depth),
+            ..GoParams::default()
+        };
+        for (name, mut engine) in iterative_engines()? {
+            report.extend(engine_asymmetries(
+                name,
+                engine.as_mut(),
+                &params,
+                &SYMMETRY_CORPUS,
+            )?);
+        }
+    }
+
+    for asymmetry in &report {
+        println!("{asymmetry}");
+    }
+    println!(
+        "{} asymmetries over {} positions",
+        report.len(),
+        SYMMETRY_CORPUS.len()
+    );
+    Ok(report.len())
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::from(2)
+        }
+    }
+}
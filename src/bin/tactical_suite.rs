@@ -0,0 +1,103 @@
+//! Tactical suite runner comparing search selectivity settings.
+//!
+//! Run with:
+//! `cargo run --release --bin tactical_suite -- [depth] [suite.epd] [--verbose]`
+//!
+//! Without a suite file the built-in Win at Chess sample is used. Each
+//! configuration reports how many positions it solves at the fixed depth and
+//! the nodes it spent.
+
+use plum_chess::search::board_scoring::EndgameTaperedScorerV14;
+use plum_chess::search::search_features::{
+    InternalIterativeReduction, ProbCut, SearchFeatures, SingularExtensions,
+};
+use plum_chess::search::tactical_suite::{
+    load_tactical_suite, parse_tactical_suite, WIN_AT_CHESS_SAMPLE,
+};
+use std::time::Instant;
+
+fn main() -> Result<(), String> {
+    let verbose = std::env::args().any(|a| a == "--verbose" || a == "-v");
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with('-'))
+        .collect();
+    let depth = args
+        .first()
+        .map(|d| d.parse::<u8>().map_err(|_| format!("invalid depth '{d}'")))
+        .transpose()?
+        .unwrap_or(7);
+    let positions = match args.get(1) {
+        Some(path) => load_tactical_suite(path)?,
+        None => parse_tactical_suite(WIN_AT_CHESS_SAMPLE)?,
+    };
+
+    let v15 = SearchFeatures::v15();
+    let configs = [
+        ("v15", v15),
+        (
+            "probcut",
+            SearchFeatures {
+                probcut: ProbCut::DEFAULT_ON,
+                ..v15
+            },
+        ),
+        (
+            "iir",
+            SearchFeatures {
+                internal_iterative_reduction: InternalIterativeReduction::DEFAULT_ON,
+                ..v15
+            },
+        ),
+        (
+            "probcut+iir",
+            SearchFeatures {
+                probcut: ProbCut::DEFAULT_ON,
+                internal_iterative_reduction: InternalIterativeReduction::DEFAULT_ON,
+                ..v15
+            },
+        ),
+        (
+            "singular",
+            SearchFeatures {
+                singular_extensions: SingularExtensions::DEFAULT_ON,
+                ..v15
+            },
+        ),
+    ];
+
+    let scorer = EndgameTaperedScorerV14::standard();
+    println!(
+        "Tactical suite: {} positions at depth {}",
+        positions.len(),
+        depth
+    );
+    println!("config,solved,total,nodes,ms");
+    for (name, features) in configs {
+        let started = Instant::now();
+        let mut solved = 0usize;
+        let mut nodes = 0u64;
+        for position in &positions {
+            let result = position.solve(&scorer, features, depth)?;
+            nodes += result.nodes;
+            if result.solved {
+                solved += 1;
+            }
+            if verbose {
+                println!(
+                    "  {name} {} found={} expected={} {}",
+                    result.id,
+                    result.found.as_deref().unwrap_or("-"),
+                    position.best_moves.join(" "),
+                    if result.solved { "ok" } else { "MISS" }
+                );
+            }
+        }
+        println!(
+            "{name},{solved},{},{nodes},{}",
+            positions.len(),
+            started.elapsed().as_millis()
+        );
+    }
+    Ok(())
+}
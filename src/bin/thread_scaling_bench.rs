@@ -1,21 +1,62 @@
-use plum_chess::engines::engine_iterative_v16::IterativeEngine;
-use plum_chess::engines::engine_trait::{Engine, GoParams};
+//! Lazy SMP time-to-depth scaling benchmark.
+//!
+//! Run with:
+//! `cargo run --release --bin thread_scaling_bench -- [max_threads] [depth] [runs]`
+//!
+//! Every thread count, including the one-thread baseline, goes through
+//! `lazy_smp_search` on a fresh `SharedTranspositionTable`, so the speedup
+//! column compares thread counts only. A discarded warm-up pass runs first and
+//! each row reports the median of `runs` timed passes over the positions.
+
 use plum_chess::game_state::game_state::GameState;
+use plum_chess::move_generation::legal_move_generator::FastLegalMoveGenerator;
+use plum_chess::search::board_scoring::EndgameTaperedScorerV14;
+use plum_chess::search::iterative_deepening::{lazy_smp_search, SearchConfig};
+use plum_chess::search::threading::SharedTranspositionTable;
 use plum_chess::utils::fen_parser::parse_fen;
 use std::env;
 use std::time::Instant;
 
+const HASH_MB: usize = 64;
+
 fn parse_arg<T: std::str::FromStr>(args: &[String], idx: usize, default: T) -> T {
     args.get(idx)
         .and_then(|s| s.parse::<T>().ok())
         .unwrap_or(default)
 }
 
+/// One pass over `positions` with `threads` threads: (elapsed ms, nodes).
+fn timed_pass(positions: &[GameState], threads: usize, depth: u8) -> (u128, u64) {
+    let scorer = EndgameTaperedScorerV14::standard();
+    let mut total_ms = 0u128;
+    let mut total_nodes = 0u64;
+    for game in positions {
+        let table = SharedTranspositionTable::new_with_mb(HASH_MB);
+        // Time to depth: Lazy SMP helpers only pay off if the shared table
+        // gets the main line to `depth` sooner.
+        let start = Instant::now();
+        let result = lazy_smp_search(
+            game,
+            &FastLegalMoveGenerator,
+            &scorer,
+            SearchConfig {
+                max_depth: depth,
+                ..SearchConfig::default()
+            },
+            &table,
+            threads - 1,
+        );
+        total_ms += start.elapsed().as_millis();
+        total_nodes += result.map_or(0, |(r, _)| r.nodes);
+    }
+    (total_ms, total_nodes)
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
-    let max_threads: usize = parse_arg(&args, 1, 8usize);
+    let max_threads: usize = parse_arg(&args, 1, 8usize).max(1);
     let depth: u8 = parse_arg(&args, 2, 4u8);
-    let runs_per_thread: usize = parse_arg(&args, 3, 3usize);
+    let runs: usize = parse_arg(&args, 3, 5usize).max(1);
 
     let positions = vec![
         GameState::new_game(),
@@ -26,49 +67,41 @@ fn main() {
     ];
 
     println!(
-        "Thread scaling benchmark: max_threads={} depth={} runs_per_thread={}",
-        max_threads, depth, runs_per_thread
+        "Thread scaling benchmark: max_threads={} depth={} runs={} available_parallelism={}",
+        max_threads,
+        depth,
+        runs,
+        std::thread::available_parallelism().map_or(1, |n| n.get())
     );
-    println!("threads,total_ms,avg_ms,positions,searched_runs");
-
-    for threads in 1..=max_threads.max(1) {
-        let mut total_ms: u128 = 0;
-        let mut searched_runs = 0usize;
+    println!("threads,median_ms,min_ms,max_ms,speedup,avg_nodes,positions,runs");
 
-        for _ in 0..runs_per_thread.max(1) {
-            for game in &positions {
-                let mut engine = IterativeEngine::new(depth);
-                let _ = engine.set_option("OwnBook", "false");
-                let _ = engine.set_option("ThreadingModel", "LazySmp");
-                let _ = engine.set_option("Threads", &threads.to_string());
-                let _ = engine.set_option("RootParallelMinDepth", "1");
-                let _ = engine.set_option("RootParallelMinMoves", "2");
+    // Warm caches and the allocator before anything is timed.
+    let _ = timed_pass(&positions, 1, depth);
 
-                let start = Instant::now();
-                let _ = engine.choose_move(
-                    game,
-                    &GoParams {
-                        depth: Some(depth),
-                        ..GoParams::default()
-                    },
-                );
-                total_ms += start.elapsed().as_millis();
-                searched_runs += 1;
-            }
+    let mut single_thread_ms = None;
+    for threads in 1..=max_threads {
+        let mut pass_ms = Vec::with_capacity(runs);
+        let mut total_nodes = 0u64;
+        for _ in 0..runs {
+            let (ms, nodes) = timed_pass(&positions, threads, depth);
+            pass_ms.push(ms);
+            total_nodes += nodes;
         }
 
-        let avg_ms = if searched_runs == 0 {
-            0.0
-        } else {
-            total_ms as f64 / searched_runs as f64
-        };
+        pass_ms.sort_unstable();
+        let median_ms = pass_ms[runs / 2];
+        let baseline_ms = *single_thread_ms.get_or_insert(median_ms);
+        let speedup = baseline_ms as f64 / median_ms.max(1) as f64;
         println!(
-            "{},{},{:.2},{},{}",
+            "{},{},{},{},{:.2},{},{},{}",
             threads,
-            total_ms,
-            avg_ms,
+            median_ms,
+            pass_ms[0],
+            pass_ms[pass_ms.len() - 1],
+            speedup,
+            total_nodes / (runs * positions.len()) as u64,
             positions.len(),
-            searched_runs
+            runs
         );
     }
 }
@@ -6,7 +6,7 @@
 use rand::prelude::IndexedRandom;
 
 use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
-use crate::game_state::chess_types::PieceKind;
+use crate::game_state::chess_types::{Move, PieceKind};
 use crate::game_state::game_state::GameState;
 use crate::move_generation::legal_move_generator::LegalMoveGenerator;
 use crate::move_generation::move_generator::MoveGenerator;
@@ -104,7 +104,7 @@ impl Engine for GreedyEngine {
             "info string greedy_engine capture_score {}",
             best_value
         ));
-        out.best_move = Some(*picked);
+        out.best_move = Some(Move::from_raw(*picked));
         Ok(out)
     }
 }
@@ -4,9 +4,10 @@
 //! selection behavior to emulate more human move quality by skill level.
 
 use crate::engines::engine_iterative_v17::IterativeEngineV17;
-use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
+use crate::engines::engine_trait::{Engine, EngineOutput, GoParams, PvLine, Wdl};
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
-use crate::utils::long_algebraic::long_algebraic_to_move_description;
+use crate::search::search_observer::SearchObserver;
 use rand::Rng;
 use std::sync::{atomic::AtomicBool, Arc};
 
@@ -71,36 +72,19 @@ struct ScoredMove {
     cp: i32,
 }
 
-fn parse_multipv_candidates(info_lines: &[String], game_state: &GameState) -> Vec<ScoredMove> {
+fn multipv_candidates(lines: &[PvLine]) -> Vec<ScoredMove> {
     let mut out = Vec::<ScoredMove>::new();
-    for line in info_lines {
-        if !line.contains(" multipv ") || !line.contains(" score cp ") || !line.contains(" pv ") {
-            continue;
-        }
-        let Some(cp_idx) = line.find(" score cp ") else {
-            continue;
-        };
-        let cp_part = &line[(cp_idx + " score cp ".len())..];
-        let Some(cp_token) = cp_part.split_whitespace().next() else {
-            continue;
-        };
-        let Ok(cp) = cp_token.parse::<i32>() else {
-            continue;
-        };
-        let Some(pv_idx) = line.find(" pv ") else {
-            continue;
-        };
-        let pv_part = &line[(pv_idx + " pv ".len())..];
-        let Some(first_lan) = pv_part.split_whitespace().next() else {
-            continue;
-        };
-        let Ok(mv) = long_algebraic_to_move_description(first_lan, game_state) else {
+    for line in lines {
+        let Some(&mv) = line.pv.first() else {
             continue;
         };
         if out.iter().any(|x| x.mv == mv) {
             continue;
         }
-        out.push(ScoredMove { mv, cp });
+        out.push(ScoredMove {
+            mv,
+            cp: line.score.centipawns(),
+        });
     }
     out.sort_by(|a, b| b.cp.cmp(&a.cp));
     out
@@ -160,6 +144,10 @@ impl Engine for HumanizedEngineV5 {
         self.inner.set_stop_signal(stop_signal);
     }
 
+    fn set_search_observer(&mut self, observer: Option<Arc<dyn SearchObserver>>) {
+        self.inner.set_search_observer(observer);
+    }
+
     fn choose_move(
         &mut self,
         game_state: &GameState,
@@ -169,19 +157,22 @@ impl Engine for HumanizedEngineV5 {
         // engine make that choice first.
         let _ = self.inner.set_option("MultiPV", "3");
         let mut out = self.inner.choose_move(game_state, params)?;
-        if out
-            .info_lines
-            .iter()
-            .any(|l| l.contains("opening book move"))
-        {
+        if out.book_move {
             return Ok(out);
         }
 
-        let candidates = parse_multipv_candidates(&out.info_lines, game_state);
+        let candidates = multipv_candidates(&out.multipv);
         let mut rng = rand::rng();
         if let Some((mv, loss, allowed)) = choose_humanized_move(&candidates, self.level, &mut rng)
         {
-            out.best_move = Some(mv);
+            out.best_move = Some(Move::from_raw(mv));
+            // Report the line that was actually played, not the inner best.
+            if let Some(line) = out.multipv.iter().find(|l| l.pv.first() == Some(&mv)) {
+                out.score = Some(line.score);
+                out.wdl = Some(Wdl::from_score(line.score));
+                out.pv = line.pv.clone();
+                out.ponder_move = line.pv.get(1).copied().map(Move::from_raw);
+            }
             out.info_lines.push(format!(
                 "info string humanized_v5 selected_cpl_loss {} allowed_cpl {} level {}",
                 loss, allowed, self.level
@@ -195,9 +186,9 @@ impl Engine for HumanizedEngineV5 {
 mod tests {
     use super::{
         allowed_cpl_loss, choose_humanized_move, cpl_bounds, default_depth_for_level,
-        parse_multipv_candidates, strength_percent, HumanizedEngineV5, ScoredMove,
+        multipv_candidates, strength_percent, HumanizedEngineV5, ScoredMove,
     };
-    use crate::engines::engine_trait::{Engine, GoParams};
+    use crate::engines::engine_trait::{Engine, GoParams, PvLine, Score};
     use crate::game_state::game_state::GameState;
     use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
     use rand::SeedableRng;
@@ -237,15 +228,23 @@ mod tests {
     }
 
     #[test]
-    fn parse_multipv_candidates_extracts_scored_moves() {
-        let game = GameState::new_game();
+    fn multipv_candidates_extracts_scored_moves() {
+        let line = |multipv: usize, cp: i32, pv: Vec<u64>| PvLine {
+            multipv,
+            depth: 3,
+            score: Score::Centipawns(cp),
+            pv,
+        };
         let lines = vec![
-            "info depth 3 multipv 1 score cp 52 pv e2e4 e7e5".to_owned(),
-            "info depth 3 multipv 2 score cp 48 pv d2d4 d7d5".to_owned(),
-            "info depth 3 multipv 3 score cp 39 pv g1f3 g8f6".to_owned(),
+            line(1, 52, vec![11, 12]),
+            line(2, 48, vec![21, 22]),
+            line(3, 39, vec![31, 32]),
+            line(4, 30, vec![11]),
+            line(5, 20, Vec::new()),
         ];
-        let parsed = parse_multipv_candidates(&lines, &game);
+        let parsed = multipv_candidates(&lines);
         assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[0], ScoredMove { mv: 11, cp: 52 });
         assert!(parsed[0].cp >= parsed[1].cp);
     }
 
@@ -288,7 +287,7 @@ mod tests {
         let best = out.best_move.expect("best move");
         let mut probe = game.clone();
         let legal = generate_legal_move_descriptions_in_place(&mut probe).expect("legal moves");
-        assert!(legal.contains(&best));
+        assert!(legal.contains(&best.raw()));
     }
 
     #[test]
@@ -338,7 +337,7 @@ mod tests {
             let best = out.best_move.expect("best move");
             let mut probe = game.clone();
             let legal = generate_legal_move_descriptions_in_place(&mut probe).expect("legal moves");
-            assert!(legal.contains(&best));
+            assert!(legal.contains(&best.raw()));
         }
     }
 }
@@ -8,27 +8,33 @@
 //! - Carries forward all prior iterative engine enhancements into one module.
 //! - Supports legacy `1/20` and adaptive budget allocation.
 
-use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
+use crate::engines::engine_trait::{Engine, EngineOutput, GoParams, PvLine, Score, Wdl};
 use crate::engines::time_management::{resolve_go_params, TimeManagementStrategy};
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
 use crate::move_generation::legal_move_apply::apply_move;
 use crate::move_generation::legal_move_checks::is_king_in_check;
 use crate::move_generation::legal_move_generator::{
     generate_legal_move_descriptions_in_place, FastLegalMoveGenerator,
 };
+use crate::move_generation::move_generator::MoveGenResult;
 use crate::moves::move_descriptions::{
     move_from, move_promotion_piece_code, move_to, piece_kind_from_code,
 };
 use crate::search::board_scoring::{BoardScorer, EndgameTaperedScorerV14, V3MaterialKind};
-use crate::search::iterative_deepening_v15::{
-    iterative_deepening_search_with_tt, principal_variation_from_tt, SearchConfig,
+use crate::search::iterative_deepening::{
+    iterative_deepening_search_with_tt, lazy_smp_search, principal_variation_from_tt, SearchConfig,
+    SearchResult,
 };
+use crate::search::search_features::{
+    InternalIterativeReduction, ProbCut, SearchFeatures, SingularExtensions,
+};
+use crate::search::search_observer::SearchObserver;
 use crate::search::threading::{
     SharedSearchState, SharedTranspositionTable, ThreadContextPool, ThreadingConfig, ThreadingModel,
 };
 use crate::search::transposition_table_v11::{Bound, TTEntry, TranspositionTable};
 use crate::tables::opening_book::OpeningBook;
-use crate::utils::long_algebraic::move_description_to_long_algebraic;
 use rand::rng;
 use std::sync::{
     atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -36,6 +42,9 @@ use std::sync::{
 };
 use std::thread;
 
+/// Depth limit of `go infinite` / `go ponder`; the stop signal ends them first.
+const UNBOUNDED_SEARCH_DEPTH: u8 = 64;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IterativeScorerKind {
     Standard,
@@ -44,6 +53,8 @@ pub enum IterativeScorerKind {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum GoControlMode {
+    /// `go infinite` / `go ponder`: search until the stop signal.
+    Infinite,
     MoveTime,
     Nodes,
     Mate,
@@ -75,17 +86,17 @@ pub struct IterativeEngine {
     deterministic_search: bool,
     root_parallel_min_depth: u8,
     root_parallel_min_moves: usize,
+    singular_extensions: bool,
+    probcut: bool,
+    probcut_margin: i32,
+    iir: bool,
+    iir_min_depth: u8,
     time_strategy: TimeManagementStrategy,
     stop_signal: Option<Arc<AtomicBool>>,
+    search_observer: Option<Arc<dyn SearchObserver>>,
 }
 
 impl IterativeEngine {
-    fn shared_tt_shard_count(&self) -> usize {
-        // Keep shard count proportional to thread count while avoiding
-        // excessive mutex fan-out at low hash sizes.
-        (self.threading.normalized_threads() * 2).clamp(2, 64)
-    }
-
     pub fn new(default_depth: u8) -> Self {
         Self::new_with_scorer(default_depth, IterativeScorerKind::Standard)
     }
@@ -109,7 +120,7 @@ impl IterativeEngine {
             opening_book: OpeningBook::load_default(),
             use_own_book: true,
             tt: TranspositionTable::new_with_mb(hash_mb),
-            shared_tt: SharedTranspositionTable::new_with_mb(hash_mb, 2),
+            shared_tt: SharedTranspositionTable::new_with_mb(hash_mb),
             hash_mb,
             multipv: 1,
             show_refutations: false,
@@ -118,8 +129,43 @@ impl IterativeEngine {
             deterministic_search: false,
             root_parallel_min_depth: 2,
             root_parallel_min_moves: 2,
+            singular_extensions: false,
+            probcut: false,
+            probcut_margin: ProbCut::DEFAULT_MARGIN,
+            iir: false,
+            iir_min_depth: InternalIterativeReduction::DEFAULT_MIN_DEPTH,
             time_strategy: TimeManagementStrategy::AdaptiveV13,
             stop_signal: None,
+            search_observer: None,
+        }
+    }
+
+    /// v15 heuristics plus the switches exposed as engine options. Mate
+    /// searches drop ProbCut and IIR, which can hide the shortest mate.
+    fn search_features(&self, mate_search: bool) -> SearchFeatures {
+        SearchFeatures {
+            singular_extensions: if self.singular_extensions {
+                SingularExtensions::DEFAULT_ON
+            } else {
+                SingularExtensions::Off
+            },
+            probcut: if self.probcut && !mate_search {
+                ProbCut::On {
+                    min_depth: ProbCut::DEFAULT_MIN_DEPTH,
+                    margin: self.probcut_margin,
+                }
+            } else {
+                ProbCut::Off
+            },
+            internal_iterative_reduction: if self.iir && !mate_search {
+                InternalIterativeReduction::On {
+                    min_depth: self.iir_min_depth,
+                    reduction: InternalIterativeReduction::DEFAULT_REDUCTION,
+                }
+            } else {
+                InternalIterativeReduction::Off
+            },
+            ..SearchFeatures::v15()
         }
     }
 }
@@ -143,17 +189,11 @@ impl Engine for IterativeEngine {
                 .map_err(|_| format!("invalid Hash value '{value}'"))?;
             self.hash_mb = parsed.max(1);
             self.tt = TranspositionTable::new_with_mb(self.hash_mb);
-            self.shared_tt =
-                SharedTranspositionTable::new_with_mb(self.hash_mb, self.shared_tt_shard_count());
+            self.shared_tt = SharedTranspositionTable::new_with_mb(self.hash_mb);
             return Ok(());
         }
         if name.eq_ignore_ascii_case("TimeStrategy") {
-            let v = value.trim().to_ascii_lowercase();
-            self.time_strategy = match v.as_str() {
-                "adaptive" | "v13" => TimeManagementStrategy::AdaptiveV13,
-                "fraction20" | "legacy" | "simple" => TimeManagementStrategy::Fraction20,
-                _ => return Err(format!("invalid TimeStrategy value '{value}'")),
-            };
+            self.time_strategy = value.parse()?;
             return Ok(());
         }
         if name.eq_ignore_ascii_case("MultiPV") {
@@ -172,8 +212,6 @@ impl Engine for IterativeEngine {
             self.threading.requested_threads = parsed.max(1);
             self.thread_contexts =
                 ThreadContextPool::with_threads(self.threading.normalized_threads());
-            self.shared_tt =
-                SharedTranspositionTable::new_with_mb(self.hash_mb, self.shared_tt_shard_count());
             return Ok(());
         }
         if name.eq_ignore_ascii_case("ThreadingModel") {
@@ -206,6 +244,37 @@ impl Engine for IterativeEngine {
             self.root_parallel_min_moves = parsed.max(2);
             return Ok(());
         }
+        if name.eq_ignore_ascii_case("SingularExtensions") {
+            let v = value.trim().to_ascii_lowercase();
+            self.singular_extensions = matches!(v.as_str(), "true" | "1" | "yes" | "on");
+            return Ok(());
+        }
+        if name.eq_ignore_ascii_case("ProbCut") {
+            let v = value.trim().to_ascii_lowercase();
+            self.probcut = matches!(v.as_str(), "true" | "1" | "yes" | "on");
+            return Ok(());
+        }
+        if name.eq_ignore_ascii_case("ProbCutMargin") {
+            let parsed = value
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| format!("invalid ProbCutMargin value '{value}'"))?;
+            self.probcut_margin = parsed.max(0);
+            return Ok(());
+        }
+        if name.eq_ignore_ascii_case("IIR") {
+            let v = value.trim().to_ascii_lowercase();
+            self.iir = matches!(v.as_str(), "true" | "1" | "yes" | "on");
+            return Ok(());
+        }
+        if name.eq_ignore_ascii_case("IIRMinDepth") {
+            let parsed = value
+                .trim()
+                .parse::<u8>()
+                .map_err(|_| format!("invalid IIRMinDepth value '{value}'"))?;
+            self.iir_min_depth = parsed.max(2);
+            return Ok(());
+        }
         if name.eq_ignore_ascii_case("UCI_ShowRefutations") {
             let v = value.trim().to_ascii_lowercase();
             self.show_refutations = matches!(v.as_str(), "true" | "1" | "yes" | "on");
@@ -218,6 +287,10 @@ impl Engine for IterativeEngine {
         self.stop_signal = stop_signal;
     }
 
+    fn set_search_observer(&mut self, observer: Option<Arc<dyn SearchObserver>>) {
+        self.search_observer = observer;
+    }
+
     fn choose_move(
         &mut self,
         game_state: &GameState,
@@ -225,7 +298,9 @@ impl Engine for IterativeEngine {
     ) -> Result<EngineOutput, String> {
         self.thread_contexts.reset();
 
-        let control_mode = if params.movetime_ms.is_some() {
+        let control_mode = if (params.infinite || params.ponder) && params.mate.is_none() {
+            GoControlMode::Infinite
+        } else if params.movetime_ms.is_some() {
             GoControlMode::MoveTime
         } else if params.nodes.is_some() {
             GoControlMode::Nodes
@@ -252,6 +327,11 @@ impl Engine for IterativeEngine {
             // In mate/nodes modes, prioritize these explicit controls over adaptive clock slicing.
             effective_params.movetime_ms = None;
         }
+        if control_mode == GoControlMode::Infinite {
+            // Clocks only apply once pondering turns into a real move, and the
+            // caller enforces that budget through the stop signal.
+            effective_params.movetime_ms = None;
+        }
         let requested_searchmoves = params.searchmoves.as_deref();
         if self.use_own_book
             && mate_mode.is_none()
@@ -267,6 +347,7 @@ impl Engine for IterativeEngine {
                     } else {
                         let mut out = EngineOutput::default();
                         out.best_move = Some(book_move);
+                        out.book_move = true;
                         out.info_lines
                             .push("info string opening book move".to_owned());
                         return Ok(out);
@@ -274,6 +355,7 @@ impl Engine for IterativeEngine {
                 } else {
                     let mut out = EngineOutput::default();
                     out.best_move = Some(book_move);
+                    out.book_move = true;
                     out.info_lines
                         .push("info string opening book move".to_owned());
                     return Ok(out);
@@ -284,36 +366,50 @@ impl Engine for IterativeEngine {
         // Honor explicit UCI depth limits first; otherwise fall back to the
         // configured difficulty depth for this engine instance.
         let mate_depth_target = mate_mode.map(|m| m.saturating_mul(2).saturating_add(1).max(1));
+        let default_depth = if control_mode == GoControlMode::Infinite {
+            UNBOUNDED_SEARCH_DEPTH
+        } else {
+            self.default_depth
+        };
         let depth = effective_params
             .depth
-            .unwrap_or(self.default_depth)
+            .unwrap_or(default_depth)
             .max(mate_depth_target.unwrap_or(1))
             .max(1);
 
-        let result = match self.scorer_kind {
-            IterativeScorerKind::Standard => iterative_deepening_search_with_tt(
+        let lazy_smp_helpers = if self.deterministic_search
+            || !matches!(self.threading.model, ThreadingModel::LazySmp)
+        {
+            0
+        } else {
+            self.threading.helper_threads()
+        };
+        let config = SearchConfig {
+            max_depth: depth,
+            movetime_ms: effective_params.movetime_ms,
+            max_nodes: node_cap,
+            stop_flag: self.stop_signal.clone(),
+            features: self.search_features(mate_mode.is_some()),
+            observer: self.search_observer.clone(),
+        };
+        let (result, lazy_smp_thread) = match self.scorer_kind {
+            IterativeScorerKind::Standard => run_main_search(
                 game_state,
                 &self.move_generator,
                 &self.standard_scorer,
-                SearchConfig {
-                    max_depth: depth,
-                    movetime_ms: effective_params.movetime_ms,
-                    max_nodes: node_cap,
-                    stop_flag: self.stop_signal.clone(),
-                },
+                config,
                 &mut self.tt,
+                &self.shared_tt,
+                lazy_smp_helpers,
             ),
-            IterativeScorerKind::AlphaZero => iterative_deepening_search_with_tt(
+            IterativeScorerKind::AlphaZero => run_main_search(
                 game_state,
                 &self.move_generator,
                 &self.alpha_zero_scorer,
-                SearchConfig {
-                    max_depth: depth,
-                    movetime_ms: effective_params.movetime_ms,
-                    max_nodes: node_cap,
-                    stop_flag: self.stop_signal.clone(),
-                },
+                config,
                 &mut self.tt,
+                &self.shared_tt,
+                lazy_smp_helpers,
             ),
         }
         .map_err(|e| e.to_string())?;
@@ -326,7 +422,7 @@ impl Engine for IterativeEngine {
             legal
                 .iter()
                 .copied()
-                .filter(|mv| allowed.contains(mv))
+                .filter(|mv| allowed.contains(&Move::from_raw(*mv)))
                 .collect()
         } else {
             legal.clone()
@@ -339,12 +435,6 @@ impl Engine for IterativeEngine {
             return Ok(out);
         }
 
-        let use_parallel_root_main = !self.deterministic_search
-            && matches!(self.threading.model, ThreadingModel::LazySmp)
-            && self.threading.normalized_threads() > 1
-            && root_legal.len() >= self.root_parallel_min_moves
-            && depth >= self.root_parallel_min_depth;
-
         let mut chosen = result
             .best_move
             .filter(|mv| root_legal.contains(mv))
@@ -372,9 +462,15 @@ impl Engine for IterativeEngine {
             }
             chosen = Some(preferred);
         }
-        out.best_move = chosen;
-
-        let ranked = if self.multipv > 1 || self.show_refutations || use_parallel_root_main {
+        out.best_move = chosen.map(Move::from_raw);
+
+        // Once stopped, root ranking could only return unsearched scores, so
+        // the deepest completed iteration of the main search stands.
+        let stopped = self
+            .stop_signal
+            .as_ref()
+            .is_some_and(|f| f.load(Ordering::Relaxed));
+        let ranked = if !stopped && (self.multipv > 1 || self.show_refutations) {
             let (ranked, workers, budget_stopped, panics, completed) = self.rank_root_candidates(
                 game_state,
                 &root_legal,
@@ -408,37 +504,35 @@ impl Engine for IterativeEngine {
             None
         };
 
-        if use_parallel_root_main {
-            out.info_lines
-                .push("info string iterative_engine_v16 parallel_root_main enabled".to_owned());
-        }
-        if use_parallel_root_main {
-            if let Some(ref ranked) = ranked {
-                if let Some(top) = ranked.first() {
-                    chosen = Some(top.mv);
-                    out.best_move = chosen;
-                }
-            }
+        if let Some(thread) = lazy_smp_thread {
+            out.info_lines.push(format!(
+                "info string iterative_engine_v16 lazy_smp helpers={} result_thread={}",
+                lazy_smp_helpers, thread
+            ));
         }
 
         if self.multipv > 1 {
             if let Some(ref ranked) = ranked {
-                let multipv_lines = self.build_multipv_lines(game_state, ranked, depth);
-                out.info_lines.extend(multipv_lines);
+                out.multipv = self.build_multipv(ranked, depth);
             }
         }
 
         if self.show_refutations {
             if let Some(ref ranked) = ranked {
-                let refutation_lines = self.build_refutation_lines(game_state, ranked);
-                out.info_lines.extend(refutation_lines);
+                out.refutations = build_refutations(ranked);
             }
         }
 
-        out.info_lines.push(format!(
-            "info depth {} score cp {} nodes {} time {} nps {}",
-            result.reached_depth, result.best_score, result.nodes, result.elapsed_ms, result.nps
-        ));
+        let score = Score::from_search(result.best_score);
+        out.score = Some(score);
+        out.wdl = Some(Wdl::from_score(score));
+        out.depth = result.reached_depth;
+        out.seldepth = result.seldepth;
+        out.nodes = result.nodes;
+        out.time_ms = result.elapsed_ms;
+        out.nps = result.nps;
+        out.hashfull = result.hashfull;
+        out.tt_stats = Some(result.tt_stats);
         out.info_lines.push(format!(
             "info string iterative_engine_v16 default_depth {}",
             self.default_depth
@@ -490,14 +584,10 @@ impl Engine for IterativeEngine {
             "info string iterative_engine_v16 root_parallel_thresholds depth>={} moves>={}",
             self.root_parallel_min_depth, self.root_parallel_min_moves
         ));
-        let shared_stats = self.shared_tt.stats();
-        out.info_lines.push(format!(
-            "info string iterative_engine_v16 shared_tt probes={} hits={} stores={}",
-            shared_stats.probes, shared_stats.hits, shared_stats.stores
-        ));
         out.info_lines.push(format!(
-            "info string iterative_engine_v16 shared_tt_shards {}",
-            self.shared_tt.shard_count()
+            "info string iterative_engine_v16 shared_tt entries {} hashfull {}",
+            self.shared_tt.len(),
+            self.shared_tt.hashfull()
         ));
         out.info_lines.push(format!(
             "info string iterative_engine_v16 multipv {}",
@@ -517,25 +607,15 @@ impl Engine for IterativeEngine {
                 node_cap
             ));
         }
-        if params.ponder {
-            out.info_lines.push(
-                "info string iterative_engine_v16 note ponder mode parsed; search remains synchronous"
-                    .to_owned(),
-            );
-        }
-        if params.infinite {
-            out.info_lines.push(
-                "info string iterative_engine_v16 note infinite parsed; bounded iterative search is used in synchronous mode"
-                    .to_owned(),
-            );
-        }
         out.info_lines.push(format!(
             "info string iterative_engine_v16 go_resolved movetime={:?}",
             effective_params.movetime_ms
         ));
         out.info_lines.push(format!(
             "info string iterative_engine_v16 movetime_source {}",
-            if control_mode == GoControlMode::MoveTime {
+            if control_mode == GoControlMode::Infinite {
+                "until_stop"
+            } else if control_mode == GoControlMode::MoveTime {
                 "explicit"
             } else if control_mode == GoControlMode::Mate {
                 "mate_mode_unbounded"
@@ -565,29 +645,13 @@ impl Engine for IterativeEngine {
             self.tt.len()
         ));
 
-        let pv = principal_variation_from_tt(game_state, &mut self.tt, result.reached_depth);
-        if pv.moves.len() >= 2 {
-            out.ponder_move = Some(pv.moves[1]);
-        }
-        if !pv.moves.is_empty() {
-            let mut pv_lan = Vec::with_capacity(pv.moves.len());
-            let mut state = game_state.clone();
-            for m in pv.moves {
-                if let Ok(lan) = move_description_to_long_algebraic(m, &state) {
-                    pv_lan.push(lan);
-                } else {
-                    break;
-                }
-                if let Ok(next) = crate::move_generation::legal_move_apply::apply_move(&state, m) {
-                    state = next;
-                } else {
-                    break;
-                }
-            }
-            if !pv_lan.is_empty() {
-                out.info_lines.push(format!("info pv {}", pv_lan.join(" ")));
-            }
-        }
+        // The search line only stands if the root choice was not overridden.
+        out.pv = if result.pv.first().copied() == chosen {
+            result.pv
+        } else {
+            chosen.into_iter().collect()
+        };
+        out.ponder_move = out.pv.get(1).copied().map(Move::from_raw);
 
         Ok(out)
     }
@@ -787,67 +851,64 @@ impl IterativeEngine {
         (merged, shared.should_stop(), panics, completed)
     }
 
-    fn build_multipv_lines(
-        &self,
-        game_state: &GameState,
-        ranked: &[RankedCandidate],
-        depth: u8,
-    ) -> Vec<String> {
-        let n = ranked.len().min(self.multipv);
-        let mut lines = Vec::with_capacity(n);
-        for (idx, candidate) in ranked.iter().take(n).enumerate() {
-            if let Ok(lan) = move_description_to_long_algebraic(candidate.mv, game_state) {
-                let mut pv_lan = vec![lan];
-                if !candidate.continuation.is_empty() {
-                    let mut state = match apply_move(game_state, candidate.mv) {
-                        Ok(s) => s,
-                        Err(_) => continue,
-                    };
-                    for reply in &candidate.continuation {
-                        if let Ok(reply_lan) = move_description_to_long_algebraic(*reply, &state) {
-                            pv_lan.push(reply_lan);
-                        } else {
-                            break;
-                        }
-                        if let Ok(next) = apply_move(&state, *reply) {
-                            state = next;
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                lines.push(format!(
-                    "info depth {} multipv {} score cp {} pv {}",
+    fn build_multipv(&self, ranked: &[RankedCandidate], depth: u8) -> Vec<PvLine> {
+        ranked
+            .iter()
+            .take(self.multipv)
+            .enumerate()
+            .map(|(idx, candidate)| {
+                let mut pv = Vec::with_capacity(candidate.continuation.len() + 1);
+                pv.push(candidate.mv);
+                pv.extend_from_slice(&candidate.continuation);
+                PvLine {
+                    multipv: idx + 1,
                     depth,
-                    idx + 1,
-                    candidate.cp,
-                    pv_lan.join(" ")
-                ));
-            }
-        }
-        lines
+                    score: Score::from_search(candidate.cp),
+                    pv,
+                }
+            })
+            .collect()
     }
+}
 
-    fn build_refutation_lines(
-        &self,
-        game_state: &GameState,
-        ranked: &[RankedCandidate],
-    ) -> Vec<String> {
-        if ranked.len() < 2 {
-            return Vec::new();
-        }
-        let Ok(best_lan) = move_description_to_long_algebraic(ranked[0].mv, game_state) else {
-            return Vec::new();
-        };
-        let limit = ranked.len().min(4);
-        let mut lines = Vec::with_capacity(limit.saturating_sub(1));
-        for candidate in ranked.iter().skip(1).take(limit - 1) {
-            if let Ok(alt_lan) = move_description_to_long_algebraic(candidate.mv, game_state) {
-                lines.push(format!("info refutation {} {}", alt_lan, best_lan));
-            }
-        }
-        lines
+/// Main search on the engine's own table, or Lazy SMP on the shared table
+/// when helper threads are enabled; also returns the Lazy SMP result thread.
+fn run_main_search(
+    game_state: &GameState,
+    move_generator: &FastLegalMoveGenerator,
+    scorer: &impl BoardScorer,
+    config: SearchConfig,
+    tt: &mut TranspositionTable,
+    shared_tt: &SharedTranspositionTable,
+    helper_threads: usize,
+) -> MoveGenResult<(SearchResult, Option<usize>)> {
+    if helper_threads == 0 {
+        return iterative_deepening_search_with_tt(game_state, move_generator, scorer, config, tt)
+            .map(|result| (result, None));
     }
+    shared_tt.new_generation();
+    lazy_smp_search(
+        game_state,
+        move_generator,
+        scorer,
+        config,
+        shared_tt,
+        helper_threads,
+    )
+    .map(|(result, thread)| (result, Some(thread)))
+}
+
+/// Each runner-up root move paired with the chosen best move.
+fn build_refutations(ranked: &[RankedCandidate]) -> Vec<(u64, u64)> {
+    let Some(best) = ranked.first() else {
+        return Vec::new();
+    };
+    ranked
+        .iter()
+        .skip(1)
+        .take(3)
+        .map(|candidate| (candidate.mv, best.mv))
+        .collect()
 }
 
 fn score_root_candidate(
@@ -919,6 +980,8 @@ fn score_root_candidate(
             movetime_ms: refine_time,
             max_nodes: refine_nodes,
             stop_flag: stop_signal,
+            features: SearchFeatures::v15(),
+            observer: None,
         },
         tt,
     );
@@ -1091,6 +1154,7 @@ mod tests {
     use crate::move_generation::legal_move_apply::apply_move;
     use crate::move_generation::legal_move_checks::is_king_in_check;
     use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+    use crate::search::search_features::{InternalIterativeReduction, ProbCut};
 
     #[test]
     fn iterative_engine_honors_go_depth_override() {
@@ -1106,10 +1170,10 @@ mod tests {
             .expect("engine should choose a move");
         let joined = out.info_lines.join("\n");
 
-        assert!(
-            joined.contains("info depth 1"),
-            "expected depth-1 search info"
-        );
+        assert_eq!(out.depth, 1, "expected depth-1 search info");
+        assert!(out.seldepth >= 1);
+        assert!(out.score.is_some());
+        assert_eq!(out.pv.first().copied(), out.best_move.map(|mv| mv.raw()));
         assert!(
             joined.contains("used_depth 1"),
             "expected used_depth=1 info"
@@ -1130,7 +1194,7 @@ mod tests {
             .choose_move(&game, &params)
             .expect("engine should choose a move");
         let best = out.best_move.expect("best move should exist");
-        let next = apply_move(&game, best).expect("best move should apply");
+        let next = apply_move(&game, best.raw()).expect("best move should apply");
         let mut probe = next.clone();
         let replies =
             generate_legal_move_descriptions_in_place(&mut probe).expect("legal moves should run");
@@ -1141,6 +1205,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn iterative_engine_selectivity_options_disabled_in_mate_mode() {
+        let mut engine = IterativeEngine::new(4);
+        engine.set_option("ProbCut", "true").expect("probcut");
+        engine.set_option("ProbCutMargin", "150").expect("margin");
+        engine.set_option("IIR", "true").expect("iir");
+        engine.set_option("IIRMinDepth", "3").expect("iir depth");
+        assert!(engine.set_option("IIRMinDepth", "deep").is_err());
+
+        let normal = engine.search_features(false);
+        assert_eq!(
+            normal.probcut,
+            ProbCut::On {
+                min_depth: ProbCut::DEFAULT_MIN_DEPTH,
+                margin: 150
+            }
+        );
+        assert_eq!(
+            normal.internal_iterative_reduction,
+            InternalIterativeReduction::On {
+                min_depth: 3,
+                reduction: InternalIterativeReduction::DEFAULT_REDUCTION
+            }
+        );
+        let mate = engine.search_features(true);
+        assert_eq!(mate.probcut, ProbCut::Off);
+        assert_eq!(
+            mate.internal_iterative_reduction,
+            InternalIterativeReduction::Off
+        );
+
+        let game =
+            GameState::from_fen("6k1/5Q2/6K1/8/8/8/8/8 w - - 0 1").expect("FEN should parse");
+        let out = engine
+            .choose_move(
+                &game,
+                &GoParams {
+                    mate: Some(1),
+                    ..GoParams::default()
+                },
+            )
+            .expect("engine should choose a move");
+        let next = apply_move(&game, out.best_move.expect("best move").raw()).expect("apply");
+        assert!(is_king_in_check(&next, next.side_to_move));
+    }
+
     #[test]
     fn iterative_engine_precedence_movetime_overrides_nodes_and_mate() {
         let game = GameState::new_game();
@@ -1203,9 +1313,13 @@ mod tests {
         let out = engine
             .choose_move(&game, &params)
             .expect("engine should choose a move");
-        let joined = out.info_lines.join("\n");
-        assert!(joined.contains("multipv 1"));
-        assert!(joined.contains("multipv 2"));
+        let ranks: Vec<usize> = out.multipv.iter().map(|line| line.multipv).collect();
+        assert_eq!(ranks, vec![1, 2, 3]);
+        assert!(out
+            .multipv
+            .windows(2)
+            .all(|w| w[0].score.centipawns() >= w[1].score.centipawns()));
+        assert!(out.multipv.iter().all(|line| !line.pv.is_empty()));
     }
 
     #[test]
@@ -1225,8 +1339,8 @@ mod tests {
         let out = engine
             .choose_move(&game, &params)
             .expect("engine should choose a move");
-        let joined = out.info_lines.join("\n");
-        assert!(joined.contains("info refutation "));
+        assert!(!out.refutations.is_empty());
+        assert!(out.refutations.len() <= 3);
     }
 
     #[test]
@@ -1259,7 +1373,7 @@ mod tests {
         assert!(joined.contains("threading model=SingleThreaded threads=4 helpers=3"));
         assert!(joined.contains("thread_contexts workers=4 helpers=3"));
         assert!(joined.contains("root_parallel_thresholds depth>=3 moves>=4"));
-        assert!(joined.contains("shared_tt_shards 8"));
+        assert!(joined.contains("shared_tt entries"));
     }
 
     #[test]
@@ -1288,11 +1402,11 @@ mod tests {
         let joined = out.info_lines.join("\n");
         assert!(joined.contains("parallel_root workers="));
         assert!(joined.contains("parallel_root split_stats"));
-        assert!(joined.contains("parallel_root_main enabled"));
+        assert!(joined.contains("lazy_smp helpers=3"));
     }
 
     #[test]
-    fn iterative_engine_parallel_root_main_runs_without_multipv() {
+    fn iterative_engine_lazy_smp_runs_without_multipv() {
         let game = GameState::new_game();
         let mut engine = IterativeEngine::new(2);
         engine
@@ -1312,7 +1426,12 @@ mod tests {
             .choose_move(&game, &params)
             .expect("engine should choose a move");
         let joined = out.info_lines.join("\n");
-        assert!(joined.contains("parallel_root_main enabled"));
+        assert!(joined.contains("lazy_smp helpers=2"));
+        assert!(!joined.contains("parallel_root workers="));
+        let best = out.best_move.expect("lazy smp should return a move");
+        let mut probe = game.clone();
+        let legal = generate_legal_move_descriptions_in_place(&mut probe).expect("legal moves");
+        assert!(legal.contains(&best.raw()));
     }
 
     #[test]
@@ -1341,6 +1460,7 @@ mod tests {
         let joined = out.info_lines.join("\n");
         assert!(joined.contains("deterministic_search true"));
         assert!(!joined.contains("parallel_root workers="));
+        assert!(!joined.contains("lazy_smp"));
     }
 
     #[test]
@@ -7,8 +7,8 @@
 use crate::engines::engine_iterative_v16::{
     IterativeEngine as IterativeEngineV16, IterativeScorerKind,
 };
-use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
-use crate::game_state::chess_types::{Color, PieceKind};
+use crate::engines::engine_trait::{Engine, EngineOutput, GoParams, Score};
+use crate::game_state::chess_types::{Color, Move, PieceKind};
 use crate::game_state::game_state::GameState;
 use crate::move_generation::legal_move_apply::apply_move;
 use crate::move_generation::legal_move_checks::is_king_in_check;
@@ -17,6 +17,7 @@ use crate::moves::move_descriptions::{
     move_captured_piece_code, move_from, move_moved_piece_code, move_promotion_piece_code, move_to,
     piece_kind_from_code, FLAG_CAPTURE, NO_PIECE_CODE,
 };
+use crate::search::search_observer::SearchObserver;
 use crate::search::zobrist::refresh_game_state_hashes;
 use std::collections::{HashMap, HashSet};
 use std::panic::{catch_unwind, AssertUnwindSafe};
@@ -59,6 +60,10 @@ impl Engine for IterativeEngineV17 {
         self.inner.set_stop_signal(stop_signal);
     }
 
+    fn set_search_observer(&mut self, observer: Option<Arc<dyn SearchObserver>>) {
+        self.inner.set_search_observer(observer);
+    }
+
     fn choose_move(
         &mut self,
         game_state: &GameState,
@@ -74,36 +79,38 @@ impl Engine for IterativeEngineV17 {
         }
         if in_kpk {
             if let Some(best) = select_kpk_best_move(game_state) {
-                out.best_move = Some(best);
+                out.best_move = Some(Move::from_raw(best));
                 special_solver_applied = true;
                 out.info_lines
                     .push("info string iterative_engine_v17 kpk_exact_applied".to_owned());
             }
         }
         if let Some(best) = select_kbnk_best_move(game_state) {
-            out.best_move = Some(best);
+            out.best_move = Some(Move::from_raw(best));
             special_solver_applied = true;
             out.info_lines
                 .push("info string iterative_engine_v17 kbnk_logic_applied".to_owned());
         }
         if in_endgame_mode && !special_solver_applied {
-            if let Some(best) = select_endgame_verified_move(game_state, params, out.best_move) {
-                out.best_move = Some(best);
+            if let Some(best) =
+                select_endgame_verified_move(game_state, params, out.best_move.map(Move::raw))
+            {
+                out.best_move = Some(Move::from_raw(best));
                 out.info_lines.push(
                     "info string iterative_engine_v17 endgame_selectivity_applied".to_owned(),
                 );
             }
         }
-        let winning_cp = extract_last_cp_score(&out.info_lines).unwrap_or(0);
+        let winning_cp = out.score.map_or(0, Score::centipawns);
         if in_endgame_mode && winning_cp >= 200 {
-            if let Some(chosen) = out.best_move {
+            if let Some(chosen) = out.best_move.map(Move::raw) {
                 if would_be_threefold_after_move(game_state, chosen) {
                     let legal = try_generate_legal_moves(game_state)
                         .ok_or_else(|| "failed to generate legal moves".to_owned())?;
                     if let Some(replacement) =
                         select_non_repetition_best_material_move(game_state, &legal, chosen)
                     {
-                        out.best_move = Some(replacement);
+                        out.best_move = Some(Move::from_raw(replacement));
                         out.info_lines.push(
                             "info string iterative_engine_v17 strong_draw_avoidance_applied"
                                 .to_owned(),
@@ -118,30 +125,11 @@ impl Engine for IterativeEngineV17 {
     }
 }
 
-fn extract_last_cp_score(info_lines: &[String]) -> Option<i32> {
-    for line in info_lines.iter().rev() {
-        if let Some(idx) = line.find(" score cp ") {
-            let cp_part = &line[(idx + " score cp ".len())..];
-            if let Some(cp_tok) = cp_part.split_whitespace().next() {
-                if let Ok(cp) = cp_tok.parse::<i32>() {
-                    return Some(cp);
-                }
-            }
-        }
-    }
-    None
-}
-
 fn would_be_threefold_after_move(game_state: &GameState, mv: u64) -> bool {
     let Some(next) = try_apply_move(game_state, mv) else {
         return false;
     };
-    let current = next.zobrist_key;
-    next.repetition_history
-        .iter()
-        .filter(|&&k| k == current)
-        .count()
-        >= 3
+    next.repetition_count() >= 3
 }
 
 fn select_non_repetition_best_material_move(
@@ -844,12 +832,17 @@ mod tests {
     #[test]
     fn threefold_detection_helper_detects_repetition() {
         let mut game = GameState::new_game();
-        let mut probe = game.clone();
-        let legal = generate_legal_move_descriptions_in_place(&mut probe).expect("legal");
-        let mv = legal[0];
-        let next = crate::move_generation::legal_move_apply::apply_move(&game, mv).expect("apply");
-        game.repetition_history = vec![next.zobrist_key, next.zobrist_key];
-        assert!(super::would_be_threefold_after_move(&game, mv));
+        for lan in ["g1f3", "g8f6", "f3g1", "f6g8", "g1f3", "g8f6", "f3g1"] {
+            let mv = crate::utils::long_algebraic::long_algebraic_to_move_description(lan, &game)
+                .expect("legal LAN");
+            game = crate::move_generation::legal_move_apply::apply_move(&game, mv).expect("apply");
+        }
+        let back = crate::utils::long_algebraic::long_algebraic_to_move_description("f6g8", &game)
+            .expect("legal LAN");
+        let other = crate::utils::long_algebraic::long_algebraic_to_move_description("e7e5", &game)
+            .expect("legal LAN");
+        assert!(super::would_be_threefold_after_move(&game, back));
+        assert!(!super::would_be_threefold_after_move(&game, other));
     }
 
     #[test]
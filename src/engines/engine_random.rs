@@ -6,6 +6,7 @@
 use rand::prelude::IndexedRandom;
 
 use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
 use crate::move_generation::legal_move_generator::LegalMoveGenerator;
 use crate::move_generation::move_generator::MoveGenerator;
@@ -63,7 +64,7 @@ impl Engine for RandomEngine {
             .choose(&mut rng)
             .ok_or("failed to choose a random move")?;
 
-        out.best_move = Some(picked.move_description);
+        out.best_move = Some(Move::from_raw(picked.move_description));
         Ok(out)
     }
 }
@@ -3,7 +3,12 @@
 //! Defines common input parameters and output payloads so different engine
 //! strategies can be selected at runtime behind a single trait interface.
 
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
+use crate::search::board_scoring::MATE_SCORE;
+use crate::search::search_observer::SearchObserver;
+use crate::search::transposition_table_v11::TTStats;
+use std::fmt;
 use std::sync::{atomic::AtomicBool, Arc};
 
 #[derive(Debug, Clone, Default)]
@@ -19,13 +24,126 @@ pub struct GoParams {
     pub winc_ms: Option<u64>,
     pub binc_ms: Option<u64>,
     pub movestogo: Option<u16>,
-    pub searchmoves: Option<Vec<u64>>,
+    pub searchmoves: Option<Vec<Move>>,
 }
 
+/// Evaluation from the side to move's point of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Score {
+    Centipawns(i32),
+    /// Mate in N moves; negative when the side to move is getting mated.
+    Mate(i32),
+    /// Fail-high: the true score is at least this many centipawns.
+    LowerBound(i32),
+    /// Fail-low: the true score is at most this many centipawns.
+    UpperBound(i32),
+}
+
+impl Score {
+    /// Classify a raw search score, turning the `MATE_SCORE` band into `Mate`.
+    pub fn from_search(cp: i32) -> Self {
+        let abs_cp = cp.abs();
+        if abs_cp < MATE_SCORE - 256 {
+            return Self::Centipawns(cp);
+        }
+        let mate_plies = (MATE_SCORE - abs_cp).max(1);
+        let mate_moves = (mate_plies + 1) / 2;
+        Self::Mate(if cp >= 0 { mate_moves } else { -mate_moves })
+    }
+
+    /// Centipawn equivalent; mates map back into the `MATE_SCORE` band.
+    pub fn centipawns(self) -> i32 {
+        match self {
+            Self::Centipawns(cp) | Self::LowerBound(cp) | Self::UpperBound(cp) => cp,
+            Self::Mate(moves) if moves >= 0 => MATE_SCORE - (2 * moves - 1).max(1),
+            Self::Mate(moves) => -(MATE_SCORE - 2 * moves.abs()),
+        }
+    }
+}
+
+/// UCI `score` payload: `cp N`, `mate N`, optionally with a bound suffix.
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Centipawns(cp) => write!(f, "cp {}", cp),
+            Self::Mate(moves) => write!(f, "mate {}", moves),
+            Self::LowerBound(cp) => write!(f, "cp {} lowerbound", cp),
+            Self::UpperBound(cp) => write!(f, "cp {} upperbound", cp),
+        }
+    }
+}
+
+/// Win/draw/loss expectation in per mille for the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Wdl {
+    pub win: u16,
+    pub draw: u16,
+    pub loss: u16,
+}
+
+impl Wdl {
+    pub fn from_score(score: Score) -> Self {
+        let cp_f = score.centipawns() as f64;
+        let win_sigmoid = 1.0 / (1.0 + (-cp_f / 180.0).exp());
+        let draw = (0.30 - (cp_f.abs() / 1200.0)).clamp(0.05, 0.30);
+        let decisive = 1.0 - draw;
+        let win = decisive * win_sigmoid;
+        let loss = decisive * (1.0 - win_sigmoid);
+
+        let mut w = (win * 1000.0).round() as i32;
+        let mut d = (draw * 1000.0).round() as i32;
+        let mut l = (loss * 1000.0).round() as i32;
+        let delta = 1000 - (w + d + l);
+        if delta > 0 {
+            d += delta;
+        } else if delta < 0 {
+            let remove = (-delta).min(d);
+            d -= remove;
+        }
+        w = w.clamp(0, 1000);
+        d = d.clamp(0, 1000);
+        l = (1000 - w - d).clamp(0, 1000);
+        Self {
+            win: w as u16,
+            draw: d as u16,
+            loss: l as u16,
+        }
+    }
+}
+
+/// One ranked root line of a `MultiPV` search; `multipv` is 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PvLine {
+    pub multipv: usize,
+    pub depth: u8,
+    pub score: Score,
+    pub pv: Vec<u64>,
+}
+
+/// Result of one `choose_move` call. Search fields stay at their defaults
+/// for engines that do not search (random, greedy, book moves); the UCI
+/// layer renders everything into `info` lines.
 #[derive(Debug, Clone, Default)]
 pub struct EngineOutput {
-    pub best_move: Option<u64>,
-    pub ponder_move: Option<u64>,
+    pub best_move: Option<Move>,
+    pub ponder_move: Option<Move>,
+    pub score: Option<Score>,
+    pub pv: Vec<u64>,
+    /// Ranked root lines, best first; empty unless `MultiPV` > 1.
+    pub multipv: Vec<PvLine>,
+    /// `(alternative, best)` root-move pairs for `UCI_ShowRefutations`.
+    pub refutations: Vec<(u64, u64)>,
+    pub wdl: Option<Wdl>,
+    pub depth: u8,
+    pub seldepth: u8,
+    pub nodes: u64,
+    pub time_ms: u64,
+    pub nps: u64,
+    pub hashfull: u16,
+    pub tt_stats: Option<TTStats>,
+    /// `best_move` was taken from the opening book without searching.
+    pub book_move: bool,
+    /// Free-form diagnostics, emitted verbatim as `info string` lines.
     pub info_lines: Vec<String>,
 }
 
@@ -35,6 +153,8 @@ pub trait Engine: Send {
         Ok(())
     }
     fn set_stop_signal(&mut self, _stop_signal: Option<Arc<AtomicBool>>) {}
+    /// Subscribe to live reports from the engine's main search, if it has one.
+    fn set_search_observer(&mut self, _observer: Option<Arc<dyn SearchObserver>>) {}
 
     fn choose_move(
         &mut self,
@@ -42,3 +162,32 @@ pub trait Engine: Send {
         params: &GoParams,
     ) -> Result<EngineOutput, String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Score, Wdl};
+
+    #[test]
+    fn score_from_search_detects_mate_band() {
+        assert_eq!(Score::from_search(29_999), Score::Mate(1));
+        assert_eq!(Score::from_search(-29_998), Score::Mate(-1));
+        assert_eq!(Score::from_search(500), Score::Centipawns(500));
+        assert_eq!(Score::Mate(1).centipawns(), 29_999);
+        assert_eq!(Score::Mate(-1).centipawns(), -29_998);
+        assert_eq!(Score::Mate(-3).to_string(), "mate -3");
+        assert_eq!(Score::LowerBound(40).to_string(), "cp 40 lowerbound");
+    }
+
+    #[test]
+    fn wdl_sums_to_one_thousand_and_tracks_score() {
+        let level = Wdl::from_score(Score::Centipawns(0));
+        let winning = Wdl::from_score(Score::Centipawns(400));
+        let mating = Wdl::from_score(Score::Mate(2));
+        for wdl in [level, winning, mating] {
+            assert_eq!(wdl.win + wdl.draw + wdl.loss, 1000);
+        }
+        assert_eq!(level.win, level.loss);
+        assert!(winning.win > level.win);
+        assert!(mating.win > winning.win);
+    }
+}
@@ -0,0 +1,272 @@
+//! Evaluation and search symmetry checks.
+//!
+//! Runs every `BoardScorer` and the iterative engines over a position corpus
+//! and over the flipped (and, where legal, mirrored) copies of each position,
+//! reporting any score or best-move that does not carry over.
+
+use std::fmt;
+
+use crate::engines::engine_iterative_v16::IterativeEngine;
+use crate::engines::engine_iterative_v17::IterativeEngineV17;
+use crate::engines::engine_trait::{Engine, GoParams, Score};
+use crate::game_state::chess_types::Move;
+use crate::game_state::game_state::GameState;
+use crate::game_state::position_transforms::{flip_move_description, mirror_move_description};
+use crate::search::board_scoring::{
+    AlphaZeroMetric, AlphaZeroPlusLegalMoves, BoardScorer, EndgameTaperedScorerV14,
+    EndgameTaperedScorerV3, MaterialScorer, StandardScorer,
+};
+use crate::utils::long_algebraic::move_description_to_long_algebraic;
+
+/// Openings, middlegames, endgames and special-move positions, all from the
+/// side that has the move.
+pub const SYMMETRY_CORPUS: [&str; 10] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+    "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+    "2r3k1/pp3ppp/4p3/3n4/3P4/2N2P2/PP4PP/2R3K1 w - - 0 22",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "8/8/4k3/8/2P5/8/4K3/8 w - - 0 1",
+    "8/5pk1/6p1/8/3R4/6P1/5PK1/3r4 b - - 0 40",
+    "6k1/5ppp/8/8/8/8/2B2PPP/3N2K1 w - - 0 30",
+];
+
+/// A named engine under test.
+pub type NamedEngine = (&'static str, Box<dyn Engine>);
+
+/// A transformed position and the map taking original moves into it.
+type TransformedPosition = (SymmetryTransform, GameState, fn(u64) -> u64);
+
+/// Which symmetry a report refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymmetryTransform {
+    /// Colours swapped, board mirrored top to bottom.
+    Flip,
+    /// Board mirrored left to right (positions without castling rights only).
+    Mirror,
+}
+
+/// One observed asymmetry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Asymmetry {
+    /// Scorer or engine name.
+    pub subject: String,
+    pub fen: String,
+    pub transform: SymmetryTransform,
+    /// What differed, e.g. "score 35 vs 31" or "best move e2e4 vs d2d4".
+    pub detail: String,
+}
+
+impl fmt::Display for Asymmetry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?}: {} [{}]",
+            self.subject, self.transform, self.detail, self.fen
+        )
+    }
+}
+
+/// Every scorer in `search::board_scoring`, with a display name.
+pub fn all_board_scorers() -> Vec<(&'static str, Box<dyn BoardScorer>)> {
+    vec![
+        ("MaterialScorer", Box::new(MaterialScorer)),
+        ("AlphaZeroMetric", Box::new(AlphaZeroMetric)),
+        ("AlphaZeroPlusLegalMoves", Box::new(AlphaZeroPlusLegalMoves)),
+        (
+            "EndgameTaperedScorerV3::standard",
+            Box::new(EndgameTaperedScorerV3::standard()),
+        ),
+        (
+            "EndgameTaperedScorerV3::alpha_zero",
+            Box::new(EndgameTaperedScorerV3::alpha_zero()),
+        ),
+        (
+            "EndgameTaperedScorerV14::standard",
+            Box::new(EndgameTaperedScorerV14::standard()),
+        ),
+        (
+            "EndgameTaperedScorerV14::alpha_zero",
+            Box::new(EndgameTaperedScorerV14::alpha_zero()),
+        ),
+        ("StandardScorer", Box::new(StandardScorer)),
+    ]
+}
+
+/// The iterative engines, configured for reproducible single-threaded search
+/// without the opening book.
+pub fn iterative_engines() -> Result<Vec<NamedEngine>, String> {
+    let mut engines: Vec<NamedEngine> = vec![
+        ("IterativeEngine", Box::new(IterativeEngine::new(8))),
+        ("IterativeEngineV17", Box::new(IterativeEngineV17::new(8))),
+    ];
+    for (_, engine) in &mut engines {
+        engine.set_option("OwnBook", "false")?;
+        engine.set_option("Threads", "1")?;
+        engine.set_option("DeterministicSearch", "true")?;
+    }
+    Ok(engines)
+}
+
+/// Transformed copies of `game_state` together with their move mapping.
+fn transformed_positions(game_state: &GameState) -> Vec<TransformedPosition> {
+    let mut out: Vec<TransformedPosition> = vec![(
+        SymmetryTransform::Flip,
+        game_state.flipped(),
+        flip_move_description,
+    )];
+    if let Ok(mirrored) = game_state.mirrored() {
+        out.push((SymmetryTransform::Mirror, mirrored, mirror_move_description));
+    }
+    out
+}
+
+/// Scores of `scorer` that change under flip or mirror.
+pub fn scorer_asymmetries(
+    name: &str,
+    scorer: &dyn BoardScorer,
+    fens: &[&str],
+) -> Result<Vec<Asymmetry>, String> {
+    let mut found = Vec::new();
+    for &fen in fens {
+        let game_state = GameState::from_fen(fen)?;
+        let score = scorer.score(&game_state);
+        for (transform, transformed, _) in transformed_positions(&game_state) {
+            let transformed_score = scorer.score(&transformed);
+            if transformed_score != score {
+                found.push(Asymmetry {
+                    subject: name.to_owned(),
+                    fen: fen.to_owned(),
+                    transform,
+                    detail: format!("score {score} vs {transformed_score}"),
+                });
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Final scores and best moves of `engine` that change under flip or mirror.
+///
+/// A different best move is only reported when the original position scores
+/// the transformed choice worse; equal-scoring alternatives are tie-breaks.
+///
+/// The engine is reset with `new_game` before every search so no table state
+/// leaks between a position and its transform.
+pub fn engine_asymmetries(
+    name: &str,
+    engine: &mut dyn Engine,
+    params: &GoParams,
+    fens: &[&str],
+) -> Result<Vec<Asymmetry>, String> {
+    let mut found = Vec::new();
+    for &fen in fens {
+        let game_state = GameState::from_fen(fen)?;
+        let (best, score) = search(engine, &game_state, params)?;
+        for (transform, transformed, map_move) in transformed_positions(&game_state) {
+            let (t_best, t_score) = search(engine, &transformed, params)?;
+            let mut report = |detail: String| {
+                found.push(Asymmetry {
+                    subject: name.to_owned(),
+                    fen: fen.to_owned(),
+                    transform,
+                    detail,
+                })
+            };
+            if t_score != score {
+                let text = |score: Option<Score>| {
+                    score.map_or_else(|| "none".to_owned(), |s| s.to_string())
+                };
+                report(format!("score {} vs {}", text(score), text(t_score)));
+            }
+            if t_best != best.map(map_move)
+                && !is_tie(engine, &game_state, params, t_best.map(map_move), &score)?
+            {
+                let lan = |mv: Option<u64>, state: &GameState| {
+                    mv.and_then(|mv| move_description_to_long_algebraic(mv, state).ok())
+                        .unwrap_or_else(|| "none".to_owned())
+                };
+                report(format!(
+                    "best move {} vs {}",
+                    lan(best, &game_state),
+                    lan(t_best, &transformed)
+                ));
+            }
+        }
+    }
+    Ok(found)
+}
+
+/// Whether `alternative` scores the same as the chosen move, i.e. the engines
+/// merely broke a tie differently.
+fn is_tie(
+    engine: &mut dyn Engine,
+    game_state: &GameState,
+    params: &GoParams,
+    alternative: Option<u64>,
+    score: &Option<Score>,
+) -> Result<bool, String> {
+    let Some(alternative) = alternative else {
+        return Ok(false);
+    };
+    let restricted = GoParams {
+        searchmoves: Some(vec![Move::from_raw(alternative)]),
+        ..params.clone()
+    };
+    let (_, alternative_score) = search(engine, game_state, &restricted)?;
+    Ok(alternative_score == *score)
+}
+
+/// Best move and final score of one fresh search.
+fn search(
+    engine: &mut dyn Engine,
+    game_state: &GameState,
+    params: &GoParams,
+) -> Result<(Option<u64>, Option<Score>), String> {
+    engine.new_game();
+    let output = engine.choose_move(game_state, params)?;
+    Ok((output.best_move.map(|mv| mv.raw()), output.score))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all_board_scorers, scorer_asymmetries, SymmetryTransform, SYMMETRY_CORPUS};
+    use crate::game_state::chess_types::PieceKind;
+    use crate::game_state::game_state::GameState;
+    use crate::search::board_scoring::BoardScorer;
+
+    /// Counts the side to move's a-file pawns: survives a flip, not a mirror.
+    struct AFilePawnScorer;
+
+    impl BoardScorer for AFilePawnScorer {
+        fn score(&self, game_state: &GameState) -> i32 {
+            let pawns = game_state.pieces[game_state.side_to_move.index()][PieceKind::Pawn.index()];
+            (pawns & 0x0101_0101_0101_0101).count_ones() as i32
+        }
+    }
+
+    #[test]
+    fn scorer_report_names_the_failing_transform() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "4k3/8/8/8/8/8/P7/4K3 w - - 0 1",
+        ];
+        let report = scorer_asymmetries("AFilePawn", &AFilePawnScorer, &fens).unwrap();
+        assert_eq!(report.len(), 1, "{report:?}");
+        assert_eq!(report[0].transform, SymmetryTransform::Mirror);
+        assert_eq!(report[0].fen, fens[1]);
+        assert_eq!(report[0].detail, "score 1 vs 0");
+    }
+
+    #[test]
+    fn material_scorers_report_no_asymmetries() {
+        for (name, scorer) in all_board_scorers() {
+            if matches!(name, "MaterialScorer" | "AlphaZeroMetric") {
+                let report = scorer_asymmetries(name, scorer.as_ref(), &SYMMETRY_CORPUS).unwrap();
+                assert!(report.is_empty(), "{name}: {report:?}");
+            }
+        }
+    }
+}
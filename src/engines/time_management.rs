@@ -15,6 +15,19 @@ pub enum TimeManagementStrategy {
     AdaptiveV13,
 }
 
+/// Parses the UCI `TimeStrategy` option value.
+impl std::str::FromStr for TimeManagementStrategy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "adaptive" | "v13" => Ok(Self::AdaptiveV13),
+            "fraction20" | "legacy" | "simple" => Ok(Self::Fraction20),
+            _ => Err(format!("invalid TimeStrategy value '{value}'")),
+        }
+    }
+}
+
 pub fn resolve_go_params(
     game_state: &GameState,
     params: &GoParams,
@@ -0,0 +1,127 @@
+//! Chess960 (Fischer Random) starting positions.
+//!
+//! Maps the 960 start-position indices to back-rank layouts using the
+//! standard Scharnagl numbering, where index 518 is the classical setup.
+
+use crate::game_state::game_state::GameState;
+
+/// Number of distinct Chess960 starting positions.
+pub const CHESS960_POSITION_COUNT: u16 = 960;
+
+/// Scharnagl index of the classical `RNBQKBNR` setup.
+pub const CHESS960_STANDARD_INDEX: u16 = 518;
+
+/// Knight-pair placements among the five squares left after bishops and queen.
+const KNIGHT_PLACEMENTS: [(usize, usize); 10] = [
+    (0, 1),
+    (0, 2),
+    (0, 3),
+    (0, 4),
+    (1, 2),
+    (1, 3),
+    (1, 4),
+    (2, 3),
+    (2, 4),
+    (3, 4),
+];
+
+/// Back-rank piece letters (uppercase, a-file first) for Chess960 `index`.
+pub fn chess960_back_rank(index: u16) -> Result<[char; 8], String> {
+    if index >= CHESS960_POSITION_COUNT {
+        return Err(format!(
+            "Chess960 index {index} out of range 0..{CHESS960_POSITION_COUNT}"
+        ));
+    }
+
+    let mut rank = [' '; 8];
+    let mut n = usize::from(index);
+
+    // Light-squared bishop on b/d/f/h, dark-squared bishop on a/c/e/g.
+    rank[(n % 4) * 2 + 1] = 'B';
+    n /= 4;
+    rank[(n % 4) * 2] = 'B';
+    n /= 4;
+
+    let nth_empty = |rank: &[char; 8], nth: usize| {
+        (0..8)
+            .filter(|file| rank[*file] == ' ')
+            .nth(nth)
+            .expect("enough empty files remain")
+    };
+
+    let queen = nth_empty(&rank, n % 6);
+    rank[queen] = 'Q';
+    n /= 6;
+
+    let (first, second) = KNIGHT_PLACEMENTS[n];
+    let first_file = nth_empty(&rank, first);
+    let second_file = nth_empty(&rank, second);
+    rank[first_file] = 'N';
+    rank[second_file] = 'N';
+
+    // Remaining three files take rook, king, rook from left to right.
+    for piece in ['R', 'K', 'R'] {
+        let file = nth_empty(&rank, 0);
+        rank[file] = piece;
+    }
+
+    Ok(rank)
+}
+
+/// Starting FEN (X-FEN castling field) for Chess960 `index`.
+pub fn chess960_start_fen(index: u16) -> Result<String, String> {
+    let back_rank = chess960_back_rank(index)?.iter().collect::<String>();
+    Ok(format!(
+        "{}/pppppppp/8/8/8/8/PPPPPPPP/{} w KQkq - 0 1",
+        back_rank.to_ascii_lowercase(),
+        back_rank
+    ))
+}
+
+/// Starting `GameState` for Chess960 `index`, with Chess960 notation enabled.
+pub fn chess960_start_position(index: u16) -> Result<GameState, String> {
+    let mut game_state = GameState::from_fen(&chess960_start_fen(index)?)?;
+    game_state.chess960 = true;
+    Ok(game_state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chess960_back_rank, chess960_start_fen, chess960_start_position};
+    use crate::game_state::chess_rules::STARTING_POSITION_FEN;
+
+    #[test]
+    fn known_indices_match_reference_layouts() {
+        assert_eq!(
+            chess960_back_rank(0).unwrap().iter().collect::<String>(),
+            "BBQNNRKR"
+        );
+        assert_eq!(
+            chess960_back_rank(959).unwrap().iter().collect::<String>(),
+            "RKRNNQBB"
+        );
+        assert_eq!(chess960_start_fen(518).unwrap(), STARTING_POSITION_FEN);
+        assert!(chess960_back_rank(960).is_err());
+    }
+
+    #[test]
+    fn every_index_is_a_distinct_valid_setup() {
+        let mut seen = std::collections::HashSet::new();
+        for index in 0..960 {
+            let rank = chess960_back_rank(index).unwrap();
+            let file_of = |piece| rank.iter().position(|p| *p == piece).unwrap();
+            let bishops = (0..8).filter(|f| rank[*f] == 'B').collect::<Vec<_>>();
+            assert_eq!(bishops.len(), 2);
+            assert_ne!(bishops[0] % 2, bishops[1] % 2, "bishops share a colour");
+            let rooks = (0..8).filter(|f| rank[*f] == 'R').collect::<Vec<_>>();
+            assert!(rooks[0] < file_of('K') && file_of('K') < rooks[1]);
+            assert!(seen.insert(rank));
+
+            let game = chess960_start_position(index).unwrap();
+            assert_eq!(
+                game.castling_rook_files,
+                [[rooks[1] as u8, rooks[0] as u8]; 2]
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Fundamental chess data types used across the codebase.
 //!
-//! Includes color/piece enums, square and move aliases, and castling-right
+//! Includes color/piece enums, square and move newtypes, and castling-right
 //! bit flags that are shared by move generation, search, UCI, and utilities.
 
 /// Core game state representation for a high-performance bitboard engine.
@@ -9,6 +9,16 @@
 pub use crate::game_state::game_state::GameState;
 pub use crate::game_state::undo_state::UndoState;
 
+use std::fmt;
+use std::str::FromStr;
+
+use crate::moves::move_descriptions::{
+    move_captured_piece_code, move_from, move_moved_piece_code, move_promotion_piece_code, move_to,
+    piece_kind_from_code, FLAG_CAPTURE, FLAG_CASTLING, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT,
+    NO_PIECE_CODE, NULL_MOVE,
+};
+use crate::utils::algebraic::algebraic_to_square;
+
 /// Side to move.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Color {
@@ -59,9 +69,6 @@ impl PieceKind {
     }
 }
 
-/// Packed move type placeholder. The exact bit layout is defined later.
-pub type Move = u32;
-
 /// Compact castling rights bitmask placeholder.
 pub const CASTLE_LIGHT_KINGSIDE: CastlingRights = 1 << 0;
 pub const CASTLE_LIGHT_QUEENSIDE: CastlingRights = 1 << 1;
@@ -69,5 +76,265 @@ pub const CASTLE_DARK_KINGSIDE: CastlingRights = 1 << 2;
 pub const CASTLE_DARK_QUEENSIDE: CastlingRights = 1 << 3;
 pub type CastlingRights = u8;
 
-/// Board square index placeholder (`0..=63`).
-pub type Square = u8;
+/// Castling side index into [`CastlingRookFiles`]: kingside (h-side) rook.
+pub const CASTLE_SIDE_KING: usize = 0;
+/// Castling side index into [`CastlingRookFiles`]: queenside (a-side) rook.
+pub const CASTLE_SIDE_QUEEN: usize = 1;
+
+/// Original file of each castling rook, indexed `[color][castle_side]`.
+///
+/// Standard chess always uses the h- and a-files; Chess960 positions record
+/// wherever the rooks started so castling rights survive arbitrary setups.
+pub type CastlingRookFiles = [[u8; 2]; 2];
+pub const STANDARD_CASTLING_ROOK_FILES: CastlingRookFiles = [[7, 0], [7, 0]];
+
+/// Castling-right bit for `color` on `castle_side`.
+#[inline]
+pub const fn castling_right_flag(color: Color, castle_side: usize) -> CastlingRights {
+    match (color, castle_side) {
+        (Color::Light, CASTLE_SIDE_KING) => CASTLE_LIGHT_KINGSIDE,
+        (Color::Light, _) => CASTLE_LIGHT_QUEENSIDE,
+        (Color::Dark, CASTLE_SIDE_KING) => CASTLE_DARK_KINGSIDE,
+        (Color::Dark, _) => CASTLE_DARK_QUEENSIDE,
+    }
+}
+
+/// Raw board square index (`0..=63`, a1 = 0) used on bitboard hot paths.
+pub type SquareIndex = u8;
+
+/// Board square (`a1` = 0 .. `h8` = 63).
+///
+/// Public APIs take and return `Square` so square indices cannot be mixed up
+/// with other integers; `Display`/`FromStr` use algebraic form (`e4`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Square(SquareIndex);
+
+impl Square {
+    /// Square for a raw index, or `None` when outside `0..=63`.
+    #[inline]
+    pub const fn new(index: SquareIndex) -> Option<Self> {
+        if index < 64 {
+            Some(Self(index))
+        } else {
+            None
+        }
+    }
+
+    /// Square from zero-based file (`a` = 0) and rank (`1` = 0).
+    #[inline]
+    pub const fn from_file_rank(file: u8, rank: u8) -> Option<Self> {
+        if file < 8 && rank < 8 {
+            Some(Self(rank * 8 + file))
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub const fn index(self) -> SquareIndex {
+        self.0
+    }
+
+    /// Zero-based file (`a` = 0).
+    #[inline]
+    pub const fn file(self) -> u8 {
+        self.0 % 8
+    }
+
+    /// Zero-based rank (`1` = 0).
+    #[inline]
+    pub const fn rank(self) -> u8 {
+        self.0 / 8
+    }
+
+    /// One-hot bitboard for this square.
+    #[inline]
+    pub const fn bitboard(self) -> u64 {
+        1u64 << self.0
+    }
+}
+
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            char::from(b'a' + self.file()),
+            char::from(b'1' + self.rank())
+        )
+    }
+}
+
+impl FromStr for Square {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        algebraic_to_square(s).map(Self)
+    }
+}
+
+impl TryFrom<SquareIndex> for Square {
+    type Error = String;
+
+    fn try_from(index: SquareIndex) -> Result<Self, Self::Error> {
+        Self::new(index).ok_or_else(|| format!("Square index out of bounds: {index}"))
+    }
+}
+
+impl From<Square> for SquareIndex {
+    #[inline]
+    fn from(square: Square) -> Self {
+        square.0
+    }
+}
+
+/// Packed move description (see `moves::move_descriptions` for the layout).
+///
+/// Wraps the raw `u64` encoding produced by move generation so public APIs
+/// cannot confuse moves with hashes, counters or square indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Move(u64);
+
+impl Move {
+    /// Null (passing) move recorded by `make_null_move_in_place`.
+    pub const NULL: Self = Self(NULL_MOVE);
+
+    /// Wrap a raw packed move description.
+    #[inline]
+    pub const fn from_raw(move_description: u64) -> Self {
+        Self(move_description)
+    }
+
+    /// Raw packed move description for generation/search internals.
+    #[inline]
+    pub const fn raw(self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    pub fn from(self) -> Square {
+        Square(move_from(self.0))
+    }
+
+    #[inline]
+    pub fn to(self) -> Square {
+        Square(move_to(self.0))
+    }
+
+    #[inline]
+    pub fn moved_piece(self) -> Option<PieceKind> {
+        piece_kind_from_code(move_moved_piece_code(self.0))
+    }
+
+    #[inline]
+    pub fn captured(self) -> Option<PieceKind> {
+        piece_kind_from_code(move_captured_piece_code(self.0))
+    }
+
+    #[inline]
+    pub fn promotion(self) -> Option<PieceKind> {
+        piece_kind_from_code(move_promotion_piece_code(self.0))
+    }
+
+    #[inline]
+    pub const fn is_capture(self) -> bool {
+        (self.0 & FLAG_CAPTURE) != 0
+    }
+
+    #[inline]
+    pub const fn is_en_passant(self) -> bool {
+        (self.0 & FLAG_EN_PASSANT) != 0
+    }
+
+    #[inline]
+    pub const fn is_double_pawn_push(self) -> bool {
+        (self.0 & FLAG_DOUBLE_PAWN_PUSH) != 0
+    }
+
+    #[inline]
+    pub const fn is_castle(self) -> bool {
+        (self.0 & FLAG_CASTLING) != 0
+    }
+
+    #[inline]
+    pub const fn is_null(self) -> bool {
+        self.0 == NULL_MOVE
+    }
+
+    #[inline]
+    pub fn is_promotion(self) -> bool {
+        move_promotion_piece_code(self.0) != NO_PIECE_CODE
+    }
+}
+
+impl From<Move> for u64 {
+    #[inline]
+    fn from(mv: Move) -> Self {
+        mv.0
+    }
+}
+
+/// Coordinate form (`e2e4`, `a7a8q`); castles print the king's destination.
+///
+/// Use `utils::long_algebraic::move_description_to_long_algebraic` when the
+/// output must honour Chess960 king-takes-rook notation.
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.from(), self.to())?;
+        match self.promotion() {
+            Some(PieceKind::Knight) => write!(f, "n"),
+            Some(PieceKind::Bishop) => write!(f, "b"),
+            Some(PieceKind::Rook) => write!(f, "r"),
+            Some(PieceKind::Queen) => write!(f, "q"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Move, PieceKind, Square};
+    use crate::game_state::game_state::GameState;
+    use crate::utils::long_algebraic::long_algebraic_to_move_description;
+
+    #[test]
+    fn square_file_rank_and_algebraic_round_trip() {
+        let e4: Square = "e4".parse().expect("square should parse");
+        assert_eq!((e4.file(), e4.rank(), e4.index()), (4, 3, 28));
+        assert_eq!(e4.to_string(), "e4");
+        assert_eq!(
+            Square::from_file_rank(7, 7)
+                .map(|s| s.to_string())
+                .as_deref(),
+            Some("h8")
+        );
+        assert_eq!(Square::new(64), None);
+        assert!(Square::try_from(64u8).is_err());
+        assert!("i9".parse::<Square>().is_err());
+    }
+
+    #[test]
+    fn move_accessors_decode_packed_description() {
+        let game =
+            GameState::from_fen("4k3/1P6/8/8/8/8/8/R3K3 w Q - 0 1").expect("FEN should parse");
+
+        let promo = Move::from_raw(
+            long_algebraic_to_move_description("b7b8n", &game).expect("LAN should parse"),
+        );
+        assert_eq!(promo.from().to_string(), "b7");
+        assert_eq!(promo.to().to_string(), "b8");
+        assert_eq!(promo.moved_piece(), Some(PieceKind::Pawn));
+        assert_eq!(promo.promotion(), Some(PieceKind::Knight));
+        assert_eq!(promo.captured(), None);
+        assert!(promo.is_promotion() && !promo.is_capture() && !promo.is_castle());
+        assert_eq!(promo.to_string(), "b7b8n");
+
+        let castle = Move::from_raw(
+            long_algebraic_to_move_description("e1c1", &game).expect("LAN should parse"),
+        );
+        assert!(castle.is_castle());
+        assert_eq!(castle.moved_piece(), Some(PieceKind::King));
+        assert_eq!(u64::from(castle), castle.raw());
+    }
+}
@@ -4,10 +4,15 @@
 //! occupancy caches, turn/state flags, clocks, and history stacks used by
 //! make/unmake style workflows and higher-level engine systems.
 
+use crate::game_state::chess960::chess960_start_position;
 use crate::game_state::chess_rules::STARTING_POSITION_FEN;
 use crate::game_state::chess_types::*;
+use crate::game_state::game_status::{game_status, is_draw_by_rule, repetition_count, GameStatus};
+use crate::game_state::position_transforms::{flip_position, mirror_position};
+use crate::game_state::position_validation::{position_issues, PositionIssue};
+use crate::search::eval_accumulator::EvalAccumulator;
 use crate::utils::fen_generator::generate_fen;
-use crate::utils::fen_parser::parse_fen;
+use crate::utils::fen_parser::{parse_fen, parse_fen_with_mode, FenParseMode};
 
 /// Incremental game state optimized for fast move making/unmaking.
 #[derive(Debug, Clone)]
@@ -23,7 +28,10 @@ pub struct GameState {
     // --- Side and state flags ---
     pub side_to_move: Color,
     pub castling_rights: CastlingRights,
-    pub en_passant_square: Option<Square>,
+    pub castling_rook_files: CastlingRookFiles,
+    pub en_passant_square: Option<SquareIndex>,
+    /// Chess960 mode: castling is written king-takes-rook in long algebraic.
+    pub chess960: bool,
 
     // --- Clocks / move counters ---
     pub halfmove_clock: u16,
@@ -33,6 +41,9 @@ pub struct GameState {
     pub zobrist_key: u64,
     pub pawn_zobrist_key: u64,
 
+    // --- Incremental evaluation terms ---
+    pub eval: EvalAccumulator,
+
     // --- Search / repetition support ---
     pub ply: u16,
     pub repetition_history: Vec<u64>,
@@ -50,7 +61,9 @@ impl Default for GameState {
 
             side_to_move: Color::Light,
             castling_rights: 0,
+            castling_rook_files: STANDARD_CASTLING_ROOK_FILES,
             en_passant_square: None,
+            chess960: false,
 
             halfmove_clock: 0,
             fullmove_number: 1,
@@ -58,6 +71,8 @@ impl Default for GameState {
             zobrist_key: 0,
             pawn_zobrist_key: 0,
 
+            eval: EvalAccumulator::default(),
+
             ply: 0,
             repetition_history: Vec::new(),
             undo_stack: Vec::new(),
@@ -77,11 +92,74 @@ impl GameState {
         parse_fen(STARTING_POSITION_FEN).expect("starting FEN should always parse")
     }
 
+    /// Chess960 starting position for Scharnagl `index` (`0..960`).
+    #[inline]
+    pub fn new_chess960_game(index: u16) -> Result<Self, String> {
+        chess960_start_position(index)
+    }
+
     #[inline]
     pub fn from_fen(fen: &str) -> Result<Self, String> {
         parse_fen(fen)
     }
 
+    /// Parse `fen`, rejecting positions that fail `validate`.
+    #[inline]
+    pub fn from_fen_strict(fen: &str) -> Result<Self, String> {
+        parse_fen_with_mode(fen, FenParseMode::Strict)
+    }
+
+    /// Check that this is a legal chess position, listing every problem found.
+    pub fn validate(&self) -> Result<(), Vec<PositionIssue>> {
+        let issues = position_issues(self);
+        if issues.is_empty() {
+            Ok(())
+        } else {
+            Err(issues)
+        }
+    }
+
+    /// Checkmate, stalemate, or the automatic/claimable draw that applies here.
+    #[inline]
+    pub fn status(&self) -> GameStatus {
+        game_status(self)
+    }
+
+    /// Times the current position has occurred since the last irreversible move.
+    #[inline]
+    pub fn repetition_count(&self) -> usize {
+        repetition_count(self)
+    }
+
+    /// Fifty-move, threefold or material draw, without generating moves.
+    #[inline]
+    pub fn is_draw_by_rule(&self) -> bool {
+        is_draw_by_rule(self)
+    }
+
+    /// Same position with colours swapped and the board mirrored top to bottom.
+    #[inline]
+    pub fn flipped(&self) -> GameState {
+        flip_position(self)
+    }
+
+    /// Same position mirrored left to right; fails if any castling right remains.
+    #[inline]
+    pub fn mirrored(&self) -> Result<GameState, String> {
+        mirror_position(self)
+    }
+
+    /// Recompute occupancy caches from the piece bitboards.
+    pub fn refresh_occupancy(&mut self) {
+        for color in [Color::Light, Color::Dark] {
+            self.occupancy_by_color[color.index()] = self.pieces[color.index()]
+                .iter()
+                .fold(0u64, |acc, bb| acc | bb);
+        }
+        self.occupancy_all = self.occupancy_by_color[Color::Light.index()]
+            | self.occupancy_by_color[Color::Dark.index()];
+    }
+
     #[inline]
     pub fn get_fen(&self) -> String {
         generate_fen(self)
@@ -0,0 +1,259 @@
+//! Game termination and draw rules.
+//!
+//! `game_status` classifies a position as ongoing, decisive, or drawn under
+//! the FIDE rules (including claimable draws), so the match harness, UCI layer
+//! and search share one definition of "game over".
+
+use std::fmt;
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::move_generation::legal_move_checks::is_king_in_check;
+use crate::move_generation::legal_move_generator::generate_legal_move_list;
+
+const LIGHT_SQUARES: u64 = 0x55AA_55AA_55AA_55AA;
+
+/// Result-relevant state of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    Ongoing,
+    /// Side to move is mated.
+    Checkmate {
+        winner: Color,
+    },
+    Stalemate,
+    /// Same position five times: drawn without a claim.
+    FivefoldRepetition,
+    /// 75 moves per side without capture or pawn move: drawn without a claim.
+    SeventyFiveMoveRule,
+    /// Bare kings, or king and one minor piece against a bare king.
+    InsufficientMaterial,
+    /// No sequence of legal moves can mate (simple cases: only same-coloured bishops).
+    DeadPosition,
+    /// Same position three times: a draw either player may claim.
+    ThreefoldRepetition,
+    /// 50 moves per side without capture or pawn move: a draw either player may claim.
+    FiftyMoveRule,
+}
+
+impl GameStatus {
+    /// The game has ended without any player claiming a draw.
+    pub fn is_game_over(self) -> bool {
+        !matches!(
+            self,
+            Self::Ongoing | Self::ThreefoldRepetition | Self::FiftyMoveRule
+        )
+    }
+
+    /// Drawn, either automatically or by claim.
+    pub fn is_draw(self) -> bool {
+        !matches!(self, Self::Ongoing | Self::Checkmate { .. })
+    }
+
+    /// Draw that only takes effect when a player claims it.
+    pub fn is_claimable_draw(self) -> bool {
+        matches!(self, Self::ThreefoldRepetition | Self::FiftyMoveRule)
+    }
+
+    pub fn winner(self) -> Option<Color> {
+        match self {
+            Self::Checkmate { winner } => Some(winner),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for GameStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ongoing => write!(f, "ongoing"),
+            Self::Checkmate { winner } => write!(f, "checkmate, {winner:?} wins"),
+            Self::Stalemate => write!(f, "stalemate"),
+            Self::FivefoldRepetition => write!(f, "fivefold repetition"),
+            Self::SeventyFiveMoveRule => write!(f, "seventy-five-move rule"),
+            Self::InsufficientMaterial => write!(f, "insufficient material"),
+            Self::DeadPosition => write!(f, "dead position"),
+            Self::ThreefoldRepetition => write!(f, "threefold repetition (claimable)"),
+            Self::FiftyMoveRule => write!(f, "fifty-move rule (claimable)"),
+        }
+    }
+}
+
+/// Classify `game_state`. Checkmate takes precedence over every draw rule.
+pub fn game_status(game_state: &GameState) -> GameStatus {
+    if generate_legal_move_list(game_state).is_empty() {
+        let mover = game_state.side_to_move;
+        return if is_king_in_check(game_state, mover) {
+            GameStatus::Checkmate {
+                winner: mover.opposite(),
+            }
+        } else {
+            GameStatus::Stalemate
+        };
+    }
+
+    let repetitions = repetition_count(game_state);
+    if repetitions >= 5 {
+        return GameStatus::FivefoldRepetition;
+    }
+    if game_state.halfmove_clock >= 150 {
+        return GameStatus::SeventyFiveMoveRule;
+    }
+    if let Some(status) = material_draw(game_state) {
+        return status;
+    }
+    if repetitions >= 3 {
+        return GameStatus::ThreefoldRepetition;
+    }
+    if game_state.halfmove_clock >= 100 {
+        return GameStatus::FiftyMoveRule;
+    }
+    GameStatus::Ongoing
+}
+
+/// Occurrences of the current position in `repetition_history`, itself included.
+///
+/// Only same-side-to-move entries since the last irreversible move (bounded by
+/// the halfmove clock, which null moves also reset) are scanned.
+pub fn repetition_count(game_state: &GameState) -> usize {
+    let history = &game_state.repetition_history;
+    let current = game_state.zobrist_key;
+    let max_scan = usize::from(game_state.halfmove_clock)
+        .saturating_add(1)
+        .min(history.len());
+    history
+        .iter()
+        .rev()
+        .take(max_scan)
+        .step_by(2)
+        .filter(|&&key| key == current)
+        .count()
+}
+
+/// Draw by fifty-move rule, threefold repetition, or lack of mating material.
+///
+/// Cheap search-side check: no move generation, so a mate delivered on the
+/// hundredth reversible ply is not distinguished from a fifty-move draw.
+#[inline]
+pub fn is_draw_by_rule(game_state: &GameState) -> bool {
+    game_state.halfmove_clock >= 100
+        || (game_state.halfmove_clock >= 4 && repetition_count(game_state) >= 3)
+        || material_draw(game_state).is_some()
+}
+
+/// `InsufficientMaterial` / `DeadPosition` when neither side can ever mate.
+pub fn material_draw(game_state: &GameState) -> Option<GameStatus> {
+    let light = &game_state.pieces[Color::Light.index()];
+    let dark = &game_state.pieces[Color::Dark.index()];
+    let heavy = |side: &[u64; 6]| {
+        side[PieceKind::Pawn.index()]
+            | side[PieceKind::Rook.index()]
+            | side[PieceKind::Queen.index()]
+    };
+    if heavy(light) | heavy(dark) != 0 {
+        return None;
+    }
+
+    let knights = light[PieceKind::Knight.index()] | dark[PieceKind::Knight.index()];
+    let bishops = light[PieceKind::Bishop.index()] | dark[PieceKind::Bishop.index()];
+    let minors = (knights | bishops).count_ones();
+    if minors <= 1 {
+        return Some(GameStatus::InsufficientMaterial);
+    }
+    if knights == 0 && (bishops & LIGHT_SQUARES == 0 || bishops & !LIGHT_SQUARES == 0) {
+        return Some(GameStatus::DeadPosition);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameStatus;
+    use crate::game_state::chess_types::Color;
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_apply::make_move_in_place;
+    use crate::move_generation::legal_move_generator::generate_legal_move_list;
+    use crate::utils::long_algebraic::long_algebraic_to_move_description;
+
+    fn status(fen: &str) -> GameStatus {
+        GameState::from_fen(fen).expect("FEN should parse").status()
+    }
+
+    #[test]
+    fn classifies_terminal_and_material_draws() {
+        assert_eq!(
+            status("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3"),
+            GameStatus::Checkmate {
+                winner: Color::Dark
+            }
+        );
+        assert_eq!(
+            status("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"),
+            GameStatus::Stalemate
+        );
+        assert_eq!(
+            status("4k3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            GameStatus::InsufficientMaterial
+        );
+        assert_eq!(
+            status("4k3/8/8/8/8/8/8/4KN2 w - - 0 1"),
+            GameStatus::InsufficientMaterial
+        );
+        assert_eq!(
+            status("4kb2/8/8/8/8/8/8/2B1K3 w - - 0 1"),
+            GameStatus::DeadPosition
+        );
+        assert_eq!(
+            status("4k1b1/8/8/8/8/8/8/2B1K3 w - - 0 1"),
+            GameStatus::Ongoing
+        );
+        assert_eq!(
+            status("4k3/8/8/8/8/8/8/4K1NN w - - 0 1"),
+            GameStatus::Ongoing
+        );
+    }
+
+    #[test]
+    fn classifies_move_clock_draws_after_checkmate() {
+        assert_eq!(
+            status("4k3/8/8/8/8/8/4P3/4K3 w - - 100 80"),
+            GameStatus::FiftyMoveRule
+        );
+        assert_eq!(
+            status("4k3/8/8/8/8/8/4P3/4K3 w - - 150 110"),
+            GameStatus::SeventyFiveMoveRule
+        );
+        assert_eq!(
+            status("R5k1/5ppp/8/8/8/8/8/6K1 b - - 150 110"),
+            GameStatus::Checkmate {
+                winner: Color::Light
+            }
+        );
+        assert!(GameStatus::FiftyMoveRule.is_claimable_draw());
+        assert!(!GameStatus::FiftyMoveRule.is_game_over());
+        assert!(GameStatus::SeventyFiveMoveRule.is_game_over());
+    }
+
+    #[test]
+    fn counts_threefold_and_fivefold_repetition() {
+        let mut game = GameState::from_fen("4k3/8/8/8/8/8/4P3/4K1N1 w - - 0 1").unwrap();
+        let shuffle = ["g1f3", "e8d8", "f3g1", "d8e8"];
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            for lan in shuffle {
+                let mv = long_algebraic_to_move_description(lan, &game).expect("legal LAN");
+                assert!(generate_legal_move_list(&game).contains(&mv));
+                make_move_in_place(&mut game, mv).expect("make move");
+            }
+            seen.push(game.status());
+        }
+        assert_eq!(
+            seen,
+            vec![
+                GameStatus::Ongoing,
+                GameStatus::ThreefoldRepetition,
+                GameStatus::ThreefoldRepetition,
+                GameStatus::FivefoldRepetition,
+            ]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Programmatic position construction.
+//!
+//! `PositionBuilder` places pieces and sets side, castling and en-passant
+//! state piece by piece, then validates the result so only legal positions
+//! come out of `build`.
+
+use crate::game_state::position_validation::{position_issues, PositionIssue};
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::search::eval_accumulator::refresh_eval_accumulator;
+use crate::search::zobrist::refresh_game_state_hashes;
+
+/// Incrementally assembled position; `build` returns a validated `GameState`.
+#[derive(Debug, Clone)]
+pub struct PositionBuilder {
+    draft: GameState,
+}
+
+impl Default for PositionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PositionBuilder {
+    /// Empty board, light to move, no castling rights.
+    pub fn new() -> Self {
+        Self {
+            draft: GameState::new_empty(),
+        }
+    }
+
+    /// Start from the pieces, rights and clocks of an existing position.
+    pub fn from_game_state(game_state: &GameState) -> Self {
+        let mut draft = GameState::new_empty();
+        draft.pieces = game_state.pieces;
+        draft.side_to_move = game_state.side_to_move;
+        draft.castling_rights = game_state.castling_rights;
+        draft.castling_rook_files = game_state.castling_rook_files;
+        draft.en_passant_square = game_state.en_passant_square;
+        draft.chess960 = game_state.chess960;
+        draft.halfmove_clock = game_state.halfmove_clock;
+        draft.fullmove_number = game_state.fullmove_number;
+        Self { draft }
+    }
+
+    /// Put a piece on `square`, replacing whatever stood there.
+    pub fn place_piece(&mut self, square: Square, color: Color, piece: PieceKind) -> &mut Self {
+        self.remove_piece(square);
+        self.draft.pieces[color.index()][piece.index()] |= square.bitboard();
+        self
+    }
+
+    /// Clear `square`.
+    pub fn remove_piece(&mut self, square: Square) -> &mut Self {
+        for side in &mut self.draft.pieces {
+            for bb in side.iter_mut() {
+                *bb &= !square.bitboard();
+            }
+        }
+        self
+    }
+
+    pub fn side_to_move(&mut self, color: Color) -> &mut Self {
+        self.draft.side_to_move = color;
+        self
+    }
+
+    /// Castling rights bitmask (`CASTLE_*` flags).
+    pub fn castling_rights(&mut self, rights: CastlingRights) -> &mut Self {
+        self.draft.castling_rights = rights;
+        self
+    }
+
+    /// Rook file for one castling right, for Chess960 setups.
+    pub fn castling_rook_file(&mut self, color: Color, castle_side: usize, file: u8) -> &mut Self {
+        self.draft.castling_rook_files[color.index()][castle_side] = file;
+        self
+    }
+
+    pub fn chess960(&mut self, enabled: bool) -> &mut Self {
+        self.draft.chess960 = enabled;
+        self
+    }
+
+    pub fn en_passant(&mut self, square: Option<Square>) -> &mut Self {
+        self.draft.en_passant_square = square.map(Square::index);
+        self
+    }
+
+    pub fn halfmove_clock(&mut self, halfmove_clock: u16) -> &mut Self {
+        self.draft.halfmove_clock = halfmove_clock;
+        self
+    }
+
+    pub fn fullmove_number(&mut self, fullmove_number: u16) -> &mut Self {
+        self.draft.fullmove_number = fullmove_number;
+        self
+    }
+
+    /// Finish the position, rejecting it with every issue found if illegal.
+    pub fn build(&self) -> Result<GameState, Vec<PositionIssue>> {
+        let mut game_state = self.draft.clone();
+        game_state.refresh_occupancy();
+        refresh_game_state_hashes(&mut game_state);
+        refresh_eval_accumulator(&mut game_state);
+        game_state.repetition_history.clear();
+        game_state.repetition_history.push(game_state.zobrist_key);
+
+        let issues = position_issues(&game_state);
+        if issues.is_empty() {
+            Ok(game_state)
+        } else {
+            Err(issues)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PositionBuilder;
+    use crate::game_state::chess_types::*;
+    use crate::game_state::game_state::GameState;
+    use crate::game_state::position_validation::PositionIssue;
+
+    fn sq(name: &str) -> Square {
+        name.parse().expect("square should parse")
+    }
+
+    #[test]
+    fn builds_start_position_matching_fen() {
+        let mut builder = PositionBuilder::new();
+        let back_rank = [
+            PieceKind::Rook,
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Queen,
+            PieceKind::King,
+            PieceKind::Bishop,
+            PieceKind::Knight,
+            PieceKind::Rook,
+        ];
+        for (file, piece) in back_rank.into_iter().enumerate() {
+            let file = file as u8;
+            builder
+                .place_piece(
+                    Square::from_file_rank(file, 0).unwrap(),
+                    Color::Light,
+                    piece,
+                )
+                .place_piece(
+                    Square::from_file_rank(file, 1).unwrap(),
+                    Color::Light,
+                    PieceKind::Pawn,
+                )
+                .place_piece(
+                    Square::from_file_rank(file, 6).unwrap(),
+                    Color::Dark,
+                    PieceKind::Pawn,
+                )
+                .place_piece(Square::from_file_rank(file, 7).unwrap(), Color::Dark, piece);
+        }
+        builder.castling_rights(
+            CASTLE_LIGHT_KINGSIDE
+                | CASTLE_LIGHT_QUEENSIDE
+                | CASTLE_DARK_KINGSIDE
+                | CASTLE_DARK_QUEENSIDE,
+        );
+
+        let built = builder.build().expect("start position is legal");
+        let reference = GameState::new_game();
+        assert_eq!(built.get_fen(), reference.get_fen());
+        assert_eq!(built.zobrist_key, reference.zobrist_key);
+        assert_eq!(built.occupancy_all, reference.occupancy_all);
+    }
+
+    #[test]
+    fn rejects_illegal_positions_and_accepts_fixes() {
+        let mut builder = PositionBuilder::new();
+        builder
+            .place_piece(sq("e1"), Color::Light, PieceKind::King)
+            .place_piece(sq("e8"), Color::Dark, PieceKind::King)
+            .place_piece(sq("e4"), Color::Light, PieceKind::Rook)
+            .side_to_move(Color::Light)
+            .en_passant(Some(sq("d6")));
+
+        let issues = builder
+            .build()
+            .expect_err("black king in check with white to move");
+        assert!(issues.contains(&PositionIssue::OpponentInCheck { color: Color::Dark }));
+        assert!(issues.contains(&PositionIssue::InvalidEnPassant { square: sq("d6") }));
+
+        builder
+            .place_piece(sq("d5"), Color::Dark, PieceKind::Pawn)
+            .side_to_move(Color::Light)
+            .remove_piece(sq("e4"));
+        let game = builder.build().expect("fixed position is legal");
+        assert_eq!(game.get_fen(), "4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1");
+
+        let mut round_trip = PositionBuilder::from_game_state(&game);
+        round_trip.place_piece(sq("e1"), Color::Light, PieceKind::Pawn);
+        assert!(round_trip.build().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Colour-flip and mirror transforms of positions and moves.
+//!
+//! A flipped position swaps the colours and mirrors the board top to bottom;
+//! a mirrored one reflects it left to right. Both are evaluation-equivalent to
+//! the original, which makes them the basis of symmetry checks on scorers and
+//! engines.
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::moves::move_descriptions::{move_from, move_to, NULL_MOVE};
+use crate::search::eval_accumulator::refresh_eval_accumulator;
+use crate::search::zobrist::refresh_game_state_hashes;
+
+/// Square seen from the other side of the board (a1 <-> a8).
+#[inline]
+pub const fn flip_square(square: SquareIndex) -> SquareIndex {
+    square ^ 56
+}
+
+/// Square reflected across the d/e file boundary (a1 <-> h1).
+#[inline]
+pub const fn mirror_square(square: SquareIndex) -> SquareIndex {
+    square ^ 7
+}
+
+/// Colour-swapped, vertically mirrored copy of `game_state`.
+///
+/// Castling rights and rook files, the en-passant square and the side to move
+/// are carried over; history is reset as for a freshly parsed FEN.
+pub fn flip_position(game_state: &GameState) -> GameState {
+    let mut flipped = GameState::new_empty();
+    for color in [Color::Light, Color::Dark] {
+        for (piece, &bb) in game_state.pieces[color.index()].iter().enumerate() {
+            flipped.pieces[color.opposite().index()][piece] = bb.swap_bytes();
+        }
+    }
+
+    let rights = game_state.castling_rights;
+    flipped.castling_rights = ((rights & 0b0011) << 2) | ((rights & 0b1100) >> 2);
+    flipped.castling_rook_files = [
+        game_state.castling_rook_files[Color::Dark.index()],
+        game_state.castling_rook_files[Color::Light.index()],
+    ];
+    flipped.en_passant_square = game_state.en_passant_square.map(flip_square);
+    flipped.side_to_move = game_state.side_to_move.opposite();
+    finish_transform(game_state, flipped)
+}
+
+/// Left-right mirrored copy of `game_state`.
+///
+/// Castling is not symmetric under this reflection, so positions with any
+/// castling right are rejected.
+pub fn mirror_position(game_state: &GameState) -> Result<GameState, String> {
+    if game_state.castling_rights != 0 {
+        return Err("cannot mirror a position with castling rights".to_owned());
+    }
+    let mut mirrored = GameState::new_empty();
+    for color in [Color::Light, Color::Dark] {
+        for (piece, &bb) in game_state.pieces[color.index()].iter().enumerate() {
+            mirrored.pieces[color.index()][piece] = mirror_bitboard(bb);
+        }
+    }
+    mirrored.en_passant_square = game_state.en_passant_square.map(mirror_square);
+    mirrored.side_to_move = game_state.side_to_move;
+    Ok(finish_transform(game_state, mirrored))
+}
+
+/// Move description corresponding to `move_description` in the flipped position.
+#[inline]
+pub fn flip_move_description(move_description: u64) -> u64 {
+    transform_move_squares(move_description, flip_square)
+}
+
+/// Move description corresponding to `move_description` in the mirrored position.
+#[inline]
+pub fn mirror_move_description(move_description: u64) -> u64 {
+    transform_move_squares(move_description, mirror_square)
+}
+
+fn transform_move_squares(move_description: u64, map: fn(SquareIndex) -> SquareIndex) -> u64 {
+    if move_description == NULL_MOVE {
+        return NULL_MOVE;
+    }
+    let from = u64::from(map(move_from(move_description)));
+    let to = u64::from(map(move_to(move_description)));
+    (move_description & !0xFFF) | from | (to << 6)
+}
+
+fn mirror_bitboard(bb: u64) -> u64 {
+    u64::from_le_bytes(bb.to_le_bytes().map(u8::reverse_bits))
+}
+
+/// Copy clocks and mode flags, then rebuild caches, hashes and history.
+fn finish_transform(original: &GameState, mut game_state: GameState) -> GameState {
+    game_state.chess960 = original.chess960;
+    game_state.halfmove_clock = original.halfmove_clock;
+    game_state.fullmove_number = original.fullmove_number;
+    game_state.refresh_occupancy();
+    refresh_game_state_hashes(&mut game_state);
+    refresh_eval_accumulator(&mut game_state);
+    game_state.repetition_history.push(game_state.zobrist_key);
+    game_state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flip_move_description, mirror_move_description};
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_generator::generate_legal_move_list;
+
+    const POSITIONS: [&str; 4] = [
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ];
+
+    fn sorted(mut moves: Vec<u64>) -> Vec<u64> {
+        moves.sort_unstable();
+        moves
+    }
+
+    #[test]
+    fn flip_is_an_involution_and_maps_legal_moves() {
+        assert_eq!(
+            GameState::new_game().flipped().get_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1"
+        );
+        for fen in POSITIONS {
+            let game = GameState::from_fen(fen).expect("FEN should parse");
+            let flipped = game.flipped();
+            assert_eq!(flipped.flipped().get_fen(), game.get_fen());
+            assert!(flipped.validate().is_ok(), "{}", flipped.get_fen());
+            assert_eq!(flipped.eval.material, -game.eval.material);
+            assert_eq!(flipped.eval.pst_mg, -game.eval.pst_mg);
+
+            let expected = generate_legal_move_list(&game)
+                .iter()
+                .map(|&mv| flip_move_description(mv))
+                .collect();
+            let actual = generate_legal_move_list(&flipped).to_vec();
+            assert_eq!(sorted(actual), sorted(expected), "{fen}");
+        }
+    }
+
+    #[test]
+    fn mirror_requires_no_castling_and_maps_legal_moves() {
+        assert!(GameState::new_game().mirrored().is_err());
+        let game = GameState::from_fen(POSITIONS[3]).expect("FEN should parse");
+        let mirrored = game.mirrored().expect("no castling rights");
+        assert_eq!(
+            mirrored.get_fen(),
+            "8/5p2/4p3/r5PK/k1p3R1/8/1P1P4/8 w - - 0 1"
+        );
+        assert_eq!(mirrored.mirrored().unwrap().get_fen(), game.get_fen());
+
+        let expected = generate_legal_move_list(&game)
+            .iter()
+            .map(|&mv| mirror_move_description(mv))
+            .collect();
+        let actual = generate_legal_move_list(&mirrored).to_vec();
+        assert_eq!(sorted(actual), sorted(expected));
+    }
+}
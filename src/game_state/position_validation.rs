@@ -0,0 +1,282 @@
+//! Legality checks for externally supplied positions.
+//!
+//! FEN input and hand-built positions can describe boards that no game could
+//! reach. `position_issues` reports every structural problem so callers
+//! can reject such positions before search trips over them.
+
+use std::fmt;
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::move_generation::legal_move_checks::is_king_in_check;
+
+const BACK_RANKS: u64 = 0xFF00_0000_0000_00FF;
+
+/// A single reason a position is not a legal chess position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionIssue {
+    /// Side does not have exactly one king.
+    KingCount { color: Color, count: u32 },
+    /// More than eight pawns for one side.
+    TooManyPawns { color: Color, count: u32 },
+    /// More than sixteen pieces for one side.
+    TooManyPieces { color: Color, count: u32 },
+    /// Pawn on the first or eighth rank.
+    PawnOnBackRank { square: Square },
+    /// Two piece bitboards claim the same square.
+    OverlappingPieces { square: Square },
+    /// Occupancy caches disagree with the piece bitboards.
+    OccupancyMismatch,
+    /// The side that just moved left its king in check.
+    OpponentInCheck { color: Color },
+    /// Castling right whose king or rook is not on its original square.
+    InvalidCastlingRight { color: Color, castle_side: usize },
+    /// En-passant target without a double-pushed pawn in front of it.
+    InvalidEnPassant { square: Square },
+    /// En-passant index outside the board.
+    EnPassantOffBoard,
+}
+
+impl fmt::Display for PositionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KingCount { color, count } => {
+                write!(f, "{color:?} has {count} kings, expected exactly 1")
+            }
+            Self::TooManyPawns { color, count } => {
+                write!(f, "{color:?} has {count} pawns, at most 8 allowed")
+            }
+            Self::TooManyPieces { color, count } => {
+                write!(f, "{color:?} has {count} pieces, at most 16 allowed")
+            }
+            Self::PawnOnBackRank { square } => write!(f, "pawn on back rank square {square}"),
+            Self::OverlappingPieces { square } => {
+                write!(f, "more than one piece on square {square}")
+            }
+            Self::OccupancyMismatch => write!(f, "occupancy caches do not match piece bitboards"),
+            Self::OpponentInCheck { color } => {
+                write!(
+                    f,
+                    "{color:?} king is in check but it is not {color:?} to move"
+                )
+            }
+            Self::InvalidCastlingRight { color, castle_side } => {
+                let side = if *castle_side == CASTLE_SIDE_KING {
+                    "king"
+                } else {
+                    "queen"
+                };
+                write!(
+                    f,
+                    "{color:?} {side}side castling right without king and rook in place"
+                )
+            }
+            Self::InvalidEnPassant { square } => {
+                write!(f, "en-passant square {square} has no capturable pawn")
+            }
+            Self::EnPassantOffBoard => write!(f, "en-passant square is off the board"),
+        }
+    }
+}
+
+/// Every legality problem in `game_state`; empty when the position is valid.
+pub fn position_issues(game_state: &GameState) -> Vec<PositionIssue> {
+    let mut issues = Vec::new();
+
+    let mut seen = 0u64;
+    for color in [Color::Light, Color::Dark] {
+        for bb in game_state.pieces[color.index()] {
+            let mut overlap = seen & bb;
+            while overlap != 0 {
+                let sq = overlap.trailing_zeros() as SquareIndex;
+                overlap &= overlap - 1;
+                if let Some(square) = Square::new(sq) {
+                    issues.push(PositionIssue::OverlappingPieces { square });
+                }
+            }
+            seen |= bb;
+        }
+    }
+
+    let light_occ = game_state.pieces[Color::Light.index()]
+        .iter()
+        .fold(0u64, |acc, bb| acc | bb);
+    let dark_occ = game_state.pieces[Color::Dark.index()]
+        .iter()
+        .fold(0u64, |acc, bb| acc | bb);
+    if game_state.occupancy_by_color != [light_occ, dark_occ]
+        || game_state.occupancy_all != (light_occ | dark_occ)
+    {
+        issues.push(PositionIssue::OccupancyMismatch);
+    }
+
+    let mut kings_ok = true;
+    for color in [Color::Light, Color::Dark] {
+        let ours = &game_state.pieces[color.index()];
+        let kings = ours[PieceKind::King.index()].count_ones();
+        if kings != 1 {
+            kings_ok = false;
+            issues.push(PositionIssue::KingCount {
+                color,
+                count: kings,
+            });
+        }
+        let pawns = ours[PieceKind::Pawn.index()].count_ones();
+        if pawns > 8 {
+            issues.push(PositionIssue::TooManyPawns {
+                color,
+                count: pawns,
+            });
+        }
+        let pieces = ours.iter().map(|bb| bb.count_ones()).sum::<u32>();
+        if pieces > 16 {
+            issues.push(PositionIssue::TooManyPieces {
+                color,
+                count: pieces,
+            });
+        }
+
+        let mut back_rank_pawns = ours[PieceKind::Pawn.index()] & BACK_RANKS;
+        while back_rank_pawns != 0 {
+            let sq = back_rank_pawns.trailing_zeros() as SquareIndex;
+            back_rank_pawns &= back_rank_pawns - 1;
+            if let Some(square) = Square::new(sq) {
+                issues.push(PositionIssue::PawnOnBackRank { square });
+            }
+        }
+    }
+
+    let waiting = game_state.side_to_move.opposite();
+    if kings_ok && is_king_in_check(game_state, waiting) {
+        issues.push(PositionIssue::OpponentInCheck { color: waiting });
+    }
+
+    for color in [Color::Light, Color::Dark] {
+        for castle_side in [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN] {
+            if (game_state.castling_rights & castling_right_flag(color, castle_side)) != 0
+                && !castling_pieces_in_place(game_state, color, castle_side)
+            {
+                issues.push(PositionIssue::InvalidCastlingRight { color, castle_side });
+            }
+        }
+    }
+
+    if let Some(ep) = game_state.en_passant_square {
+        if !en_passant_square_is_consistent(game_state, ep) {
+            issues.push(match Square::new(ep) {
+                Some(square) => PositionIssue::InvalidEnPassant { square },
+                None => PositionIssue::EnPassantOffBoard,
+            });
+        }
+    }
+
+    issues
+}
+
+/// King and castling rook both stand on the back rank, rook on the castling side.
+fn castling_pieces_in_place(game_state: &GameState, color: Color, castle_side: usize) -> bool {
+    let base = if color == Color::Light { 0u8 } else { 56u8 };
+    let back_rank = 0xFFu64 << base;
+    let kings = game_state.pieces[color.index()][PieceKind::King.index()] & back_rank;
+    if kings.count_ones() != 1 {
+        return false;
+    }
+    let king_file = (kings.trailing_zeros() as u8) % 8;
+    let rook_file = game_state.castling_rook_files[color.index()][castle_side];
+    let rook_present = rook_file < 8
+        && (game_state.pieces[color.index()][PieceKind::Rook.index()]
+            & (1u64 << (base + rook_file)))
+            != 0;
+    let correct_side = if castle_side == CASTLE_SIDE_KING {
+        rook_file > king_file
+    } else {
+        rook_file < king_file
+    };
+    rook_present && correct_side
+}
+
+/// En-passant target sits behind a pawn that could just have double-pushed.
+fn en_passant_square_is_consistent(game_state: &GameState, ep: SquareIndex) -> bool {
+    if ep >= 64 {
+        return false;
+    }
+    let mover = game_state.side_to_move;
+    let (target_rank, pawn_sq, origin_sq) = if mover == Color::Light {
+        (5u8, ep.wrapping_sub(8), ep.wrapping_add(8))
+    } else {
+        (2u8, ep.wrapping_add(8), ep.wrapping_sub(8))
+    };
+    if ep / 8 != target_rank {
+        return false;
+    }
+    let pushed_pawn = (game_state.pieces[mover.opposite().index()][PieceKind::Pawn.index()]
+        & (1u64 << pawn_sq))
+        != 0;
+    let path_empty = (game_state.occupancy_all & ((1u64 << ep) | (1u64 << origin_sq))) == 0;
+    pushed_pawn && path_empty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PositionIssue;
+    use crate::game_state::chess_types::{Color, CASTLE_SIDE_KING};
+    use crate::game_state::game_state::GameState;
+
+    fn issues(fen: &str) -> Vec<PositionIssue> {
+        match GameState::from_fen(fen)
+            .expect("FEN should parse")
+            .validate()
+        {
+            Ok(()) => Vec::new(),
+            Err(issues) => issues,
+        }
+    }
+
+    #[test]
+    fn reachable_positions_have_no_issues() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1",
+        ] {
+            assert_eq!(issues(fen), Vec::new(), "{fen}");
+        }
+    }
+
+    #[test]
+    fn reports_each_kind_of_illegal_setup() {
+        assert!(
+            issues("4k3/8/8/8/8/8/8/3KK3 w - - 0 1").contains(&PositionIssue::KingCount {
+                color: Color::Light,
+                count: 2
+            })
+        );
+        assert!(
+            issues("4k3/8/8/8/8/8/8/8 w - - 0 1").contains(&PositionIssue::KingCount {
+                color: Color::Light,
+                count: 0
+            })
+        );
+        assert!(matches!(
+            issues("4k3/8/8/8/8/8/8/P3K3 w - - 0 1").as_slice(),
+            [PositionIssue::PawnOnBackRank { square }] if square.to_string() == "a1"
+        ));
+        assert_eq!(
+            issues("4k2R/8/8/8/8/8/8/4K3 w - - 0 1"),
+            vec![PositionIssue::OpponentInCheck { color: Color::Dark }]
+        );
+        assert_eq!(
+            issues("4k3/8/8/8/8/8/8/4K3 w K - 0 1"),
+            vec![PositionIssue::InvalidCastlingRight {
+                color: Color::Light,
+                castle_side: CASTLE_SIDE_KING
+            }]
+        );
+        assert_eq!(
+            issues("4k3/8/8/8/8/8/8/4K3 w - e6 0 1"),
+            vec![PositionIssue::InvalidEnPassant {
+                square: "e6".parse().expect("square should parse")
+            }]
+        );
+    }
+}
@@ -4,6 +4,7 @@
 //! previous positions efficiently without snapshotting full piece arrays.
 
 use crate::game_state::chess_types::*;
+use crate::search::eval_accumulator::EvalAccumulator;
 
 /// Single undo record for `make_move` / `unmake_move`.
 #[derive(Debug, Clone)]
@@ -14,7 +15,7 @@ pub struct UndoState {
 
     pub prev_side_to_move: Color,
     pub prev_castling_rights: CastlingRights,
-    pub prev_en_passant_square: Option<Square>,
+    pub prev_en_passant_square: Option<SquareIndex>,
     pub prev_halfmove_clock: u16,
     pub prev_fullmove_number: u16,
     pub prev_ply: u16,
@@ -22,4 +23,5 @@ pub struct UndoState {
 
     pub prev_zobrist_key: u64,
     pub prev_pawn_zobrist_key: u64,
+    pub prev_eval: EvalAccumulator,
 }
@@ -5,9 +5,14 @@
 //! tests, and external tooling can import stable module paths.
 
 pub mod game_state {
+    pub mod chess960;
     pub mod chess_rules;
     pub mod chess_types;
     pub mod game_state;
+    pub mod game_status;
+    pub mod position_builder;
+    pub mod position_transforms;
+    pub mod position_validation;
     pub mod undo_state;
 }
 
@@ -15,6 +20,7 @@ pub mod moves {
     pub mod bishop_moves;
     pub mod king_moves;
     pub mod knight_moves;
+    pub mod magic_bitboards;
     pub mod move_descriptions;
     pub mod pawn_moves;
     pub mod queen_moves;
@@ -25,7 +31,9 @@ pub mod move_generation {
     pub mod legal_move_apply;
     pub mod legal_move_checks;
     pub mod legal_move_generator;
+    pub mod legal_move_pin_mask;
     pub mod legal_move_shared;
+    pub mod legal_move_staged;
     pub mod legal_moves_bishop;
     pub mod legal_moves_king;
     pub mod legal_moves_knight;
@@ -33,17 +41,21 @@ pub mod move_generation {
     pub mod legal_moves_queen;
     pub mod legal_moves_rook;
     pub mod move_generator;
+    pub mod move_legality;
+    pub mod move_list;
     pub mod perft;
+    pub mod perft_suite;
+    pub mod perft_table;
 }
 
 pub mod search {
     pub mod board_scoring;
+    pub mod eval_accumulator;
     pub mod iterative_deepening;
     pub mod iterative_deepening_v10;
     pub mod iterative_deepening_v11;
     pub mod iterative_deepening_v12;
     pub mod iterative_deepening_v14;
-    pub mod iterative_deepening_v15;
     pub mod iterative_deepening_v3;
     pub mod iterative_deepening_v4;
     pub mod iterative_deepening_v5;
@@ -51,6 +63,11 @@ pub mod search {
     pub mod iterative_deepening_v7;
     pub mod iterative_deepening_v8;
     pub mod iterative_deepening_v9;
+    pub mod move_picker;
+    pub mod polyglot;
+    pub mod search_features;
+    pub mod search_observer;
+    pub mod tactical_suite;
     pub mod threading;
     pub mod transposition_table;
     pub mod transposition_table_v11;
@@ -69,6 +86,7 @@ pub mod engines {
     pub mod engine_iterative_v17;
     pub mod engine_random;
     pub mod engine_trait;
+    pub mod symmetry_harness;
     pub mod time_management;
 }
 
@@ -4,6 +4,7 @@
 //! manages command parsing, engine selection, and move responses over stdio.
 
 fn main() {
+    plum_chess::moves::magic_bitboards::init_magic_tables();
     if let Err(err) = plum_chess::uci::uci_top::run_stdio_loop() {
         eprintln!("uci loop error: {}", err);
     }
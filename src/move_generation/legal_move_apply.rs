@@ -9,8 +9,9 @@ use crate::moves::move_descriptions::{
     move_from, move_promotion_piece_code, move_to, pack_move_description, piece_kind_from_code,
     FLAG_CAPTURE, FLAG_CASTLING, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT,
 };
+use crate::search::eval_accumulator::compute_eval_accumulator;
 use crate::search::zobrist::{
-    castling_key, en_passant_file_key, piece_square_key, side_to_move_key,
+    castling_rights_key, en_passant_key, piece_square_key, side_to_move_key,
 };
 
 pub fn apply_move(game_state: &GameState, move_description: u64) -> Result<GameState, String> {
@@ -31,7 +32,8 @@ pub fn make_move_in_place(game_state: &mut GameState, move_description: u64) ->
     let moving_color = game_state.side_to_move;
     let enemy_color = moving_color.opposite();
     let prev_castling = game_state.castling_rights;
-    let prev_en_passant = game_state.en_passant_square;
+    // Taken before any piece moves: Polyglot mode hashes ep only if capturable.
+    let prev_en_passant_key = en_passant_key(game_state);
 
     let moved_piece = piece_on_square_any(game_state, from)
         .ok_or_else(|| format!("No piece on from-square {from}"))?
@@ -46,7 +48,7 @@ pub fn make_move_in_place(game_state: &mut GameState, move_description: u64) ->
     };
 
     let undo = UndoState {
-        mv: move_description as Move,
+        mv: Move::from_raw(move_description),
         moved_piece,
         captured_piece,
         prev_side_to_move: game_state.side_to_move,
@@ -58,11 +60,23 @@ pub fn make_move_in_place(game_state: &mut GameState, move_description: u64) ->
         prev_repetition_len: game_state.repetition_history.len(),
         prev_zobrist_key: game_state.zobrist_key,
         prev_pawn_zobrist_key: game_state.pawn_zobrist_key,
+        prev_eval: game_state.eval,
     };
     game_state.undo_stack.push(undo);
 
-    // Remove moved piece from origin.
+    // Castling rook squares; the king may land on the rook's origin in Chess960.
+    let castling_rook = if (move_description & FLAG_CASTLING) != 0 && moved_piece == PieceKind::King
+    {
+        Some(castling_rook_squares(game_state, moving_color, to))
+    } else {
+        None
+    };
+
+    // Remove moved piece (and castling rook) from origin.
     remove_piece(game_state, moving_color, moved_piece, from);
+    if let Some((rook_from, _)) = castling_rook {
+        remove_piece(game_state, moving_color, PieceKind::Rook, rook_from);
+    }
 
     // Handle captures.
     if (move_description & FLAG_EN_PASSANT) != 0 {
@@ -88,36 +102,25 @@ pub fn make_move_in_place(game_state: &mut GameState, move_description: u64) ->
         add_piece(game_state, moving_color, moved_piece, to);
     }
 
-    // Castling rook move.
-    if (move_description & FLAG_CASTLING) != 0 && moved_piece == PieceKind::King {
-        match (moving_color, from, to) {
-            (Color::Light, 4, 6) => move_rook(game_state, moving_color, 7, 5)?,
-            (Color::Light, 4, 2) => move_rook(game_state, moving_color, 0, 3)?,
-            (Color::Dark, 60, 62) => move_rook(game_state, moving_color, 63, 61)?,
-            (Color::Dark, 60, 58) => move_rook(game_state, moving_color, 56, 59)?,
-            _ => {}
-        }
+    if let Some((_, rook_to)) = castling_rook {
+        add_piece(game_state, moving_color, PieceKind::Rook, rook_to);
     }
 
     // Update castling rights.
     update_castling_rights(game_state, moving_color, from, to, moved_piece);
     if prev_castling != game_state.castling_rights {
-        game_state.zobrist_key ^= castling_key(prev_castling);
-        game_state.zobrist_key ^= castling_key(game_state.castling_rights);
+        let rook_files = game_state.castling_rook_files;
+        game_state.zobrist_key ^= castling_rights_key(prev_castling, &rook_files);
+        game_state.zobrist_key ^= castling_rights_key(game_state.castling_rights, &rook_files);
     }
 
-    // Update en-passant square.
-    if let Some(prev_ep) = prev_en_passant {
-        game_state.zobrist_key ^= en_passant_file_key(prev_ep % 8);
-    }
+    // Update en-passant square; its key is added once the side has flipped.
+    game_state.zobrist_key ^= prev_en_passant_key;
     game_state.en_passant_square = if (move_description & FLAG_DOUBLE_PAWN_PUSH) != 0 {
         Some((from + to) / 2)
     } else {
         None
     };
-    if let Some(new_ep) = game_state.en_passant_square {
-        game_state.zobrist_key ^= en_passant_file_key(new_ep % 8);
-    }
 
     // Update clocks.
     if moved_piece == PieceKind::Pawn || (move_description & FLAG_CAPTURE) != 0 {
@@ -131,6 +134,7 @@ pub fn make_move_in_place(game_state: &mut GameState, move_description: u64) ->
 
     game_state.zobrist_key ^= side_to_move_key();
     game_state.side_to_move = enemy_color;
+    game_state.zobrist_key ^= en_passant_key(game_state);
     game_state.ply = game_state.ply.saturating_add(1);
 
     game_state.occupancy_all = game_state.occupancy_by_color[Color::Light.index()]
@@ -145,20 +149,27 @@ pub fn make_move_in_place(game_state: &mut GameState, move_description: u64) ->
         game_state.pawn_zobrist_key,
         crate::search::zobrist::compute_pawn_zobrist_key(game_state)
     );
+    debug_assert_eq!(game_state.eval, compute_eval_accumulator(game_state));
 
     Ok(())
 }
 
 /// Undo the last in-place move applied with `make_move_in_place`.
+///
+/// A null move on top of the undo stack is undone as well.
 pub fn unmake_move_in_place(game_state: &mut GameState) -> Result<(), String> {
     let undo = game_state
         .undo_stack
         .pop()
         .ok_or("undo stack is empty; cannot unmake move")?;
+    if undo.mv.is_null() {
+        restore_null_move(game_state, &undo);
+        return Ok(());
+    }
 
     let moving_color = undo.prev_side_to_move;
     let enemy_color = moving_color.opposite();
-    let mv = undo.mv as u64;
+    let mv = undo.mv.raw();
     let from = move_from(mv);
     let to = move_to(mv);
 
@@ -166,24 +177,26 @@ pub fn unmake_move_in_place(game_state: &mut GameState) -> Result<(), String> {
     // aligns with the pre-move perspective.
     game_state.side_to_move = moving_color;
 
-    // Remove moved/promoted piece from destination and restore mover on origin.
+    let castling_rook = if (mv & FLAG_CASTLING) != 0 && undo.moved_piece == PieceKind::King {
+        Some(castling_rook_squares(game_state, moving_color, to))
+    } else {
+        None
+    };
+
+    // Remove moved/promoted piece (and castling rook) from destination, then
+    // restore both on their origins.
     let promotion_piece = piece_kind_from_code(move_promotion_piece_code(mv));
     if let Some(promo) = promotion_piece {
         remove_piece(game_state, moving_color, promo, to);
     } else {
         remove_piece(game_state, moving_color, undo.moved_piece, to);
     }
+    if let Some((_, rook_to)) = castling_rook {
+        remove_piece(game_state, moving_color, PieceKind::Rook, rook_to);
+    }
     add_piece(game_state, moving_color, undo.moved_piece, from);
-
-    // Undo castling rook move.
-    if (mv & FLAG_CASTLING) != 0 && undo.moved_piece == PieceKind::King {
-        match (moving_color, from, to) {
-            (Color::Light, 4, 6) => move_rook(game_state, moving_color, 5, 7)?,
-            (Color::Light, 4, 2) => move_rook(game_state, moving_color, 3, 0)?,
-            (Color::Dark, 60, 62) => move_rook(game_state, moving_color, 61, 63)?,
-            (Color::Dark, 60, 58) => move_rook(game_state, moving_color, 59, 56)?,
-            _ => {}
-        }
+    if let Some((rook_from, _)) = castling_rook {
+        add_piece(game_state, moving_color, PieceKind::Rook, rook_from);
     }
 
     // Restore captured piece, if any.
@@ -207,6 +220,7 @@ pub fn unmake_move_in_place(game_state: &mut GameState) -> Result<(), String> {
     game_state.ply = undo.prev_ply;
     game_state.zobrist_key = undo.prev_zobrist_key;
     game_state.pawn_zobrist_key = undo.prev_pawn_zobrist_key;
+    game_state.eval = undo.prev_eval;
     game_state
         .repetition_history
         .truncate(undo.prev_repetition_len);
@@ -221,14 +235,82 @@ pub fn unmake_move_in_place(game_state: &mut GameState) -> Result<(), String> {
         game_state.pawn_zobrist_key,
         crate::search::zobrist::compute_pawn_zobrist_key(game_state)
     );
+    debug_assert_eq!(game_state.eval, compute_eval_accumulator(game_state));
+
+    Ok(())
+}
+
+/// Pass the turn without moving a piece, pushing an undo record.
+///
+/// Clears en passant and resets the halfmove clock, so repetition scans
+/// (bounded by the halfmove clock) never match positions from before the
+/// null move. Callers must not pass while in check.
+pub fn make_null_move_in_place(game_state: &mut GameState) {
+    let moving_color = game_state.side_to_move;
+    game_state.undo_stack.push(UndoState {
+        mv: Move::NULL,
+        moved_piece: PieceKind::King,
+        captured_piece: None,
+        prev_side_to_move: moving_color,
+        prev_castling_rights: game_state.castling_rights,
+        prev_en_passant_square: game_state.en_passant_square,
+        prev_halfmove_clock: game_state.halfmove_clock,
+        prev_fullmove_number: game_state.fullmove_number,
+        prev_ply: game_state.ply,
+        prev_repetition_len: game_state.repetition_history.len(),
+        prev_zobrist_key: game_state.zobrist_key,
+        prev_pawn_zobrist_key: game_state.pawn_zobrist_key,
+        prev_eval: game_state.eval,
+    });
+
+    game_state.zobrist_key ^= en_passant_key(game_state);
+    game_state.en_passant_square = None;
+    game_state.halfmove_clock = 0;
+    if moving_color == Color::Dark {
+        game_state.fullmove_number = game_state.fullmove_number.saturating_add(1);
+    }
+    game_state.zobrist_key ^= side_to_move_key();
+    game_state.side_to_move = moving_color.opposite();
+    game_state.ply = game_state.ply.saturating_add(1);
+    game_state.repetition_history.push(game_state.zobrist_key);
+
+    debug_assert_eq!(
+        game_state.zobrist_key,
+        crate::search::zobrist::compute_zobrist_key(game_state)
+    );
+}
 
+/// Undo the null move on top of the undo stack.
+pub fn unmake_null_move_in_place(game_state: &mut GameState) -> Result<(), String> {
+    let undo = game_state
+        .undo_stack
+        .last()
+        .ok_or("undo stack is empty; cannot unmake null move")?;
+    if !undo.mv.is_null() {
+        return Err("top of undo stack is not a null move".to_owned());
+    }
+    if let Some(undo) = game_state.undo_stack.pop() {
+        restore_null_move(game_state, &undo);
+    }
     Ok(())
 }
 
+fn restore_null_move(game_state: &mut GameState, undo: &UndoState) {
+    game_state.side_to_move = undo.prev_side_to_move;
+    game_state.en_passant_square = undo.prev_en_passant_square;
+    game_state.halfmove_clock = undo.prev_halfmove_clock;
+    game_state.fullmove_number = undo.prev_fullmove_number;
+    game_state.ply = undo.prev_ply;
+    game_state.zobrist_key = undo.prev_zobrist_key;
+    game_state
+        .repetition_history
+        .truncate(undo.prev_repetition_len);
+}
+
 #[inline]
 pub fn build_move(
-    from: Square,
-    to: Square,
+    from: SquareIndex,
+    to: SquareIndex,
     moved_piece: PieceKind,
     captured_piece: Option<PieceKind>,
     promotion_piece: Option<PieceKind>,
@@ -245,43 +327,66 @@ pub fn build_move(
 }
 
 #[inline]
-fn remove_piece(game_state: &mut GameState, color: Color, piece: PieceKind, square: Square) {
+fn remove_piece(game_state: &mut GameState, color: Color, piece: PieceKind, square: SquareIndex) {
     let mask = 1u64 << square;
     game_state.pieces[color.index()][piece.index()] &= !mask;
     game_state.occupancy_by_color[color.index()] &= !mask;
     game_state.zobrist_key ^= piece_square_key(color, piece, square);
+    game_state.eval.remove_piece(color, piece, square);
     if matches!(piece, PieceKind::Pawn | PieceKind::King) {
         game_state.pawn_zobrist_key ^= piece_square_key(color, piece, square);
     }
 }
 
 #[inline]
-fn add_piece(game_state: &mut GameState, color: Color, piece: PieceKind, square: Square) {
+fn add_piece(game_state: &mut GameState, color: Color, piece: PieceKind, square: SquareIndex) {
     let mask = 1u64 << square;
     game_state.pieces[color.index()][piece.index()] |= mask;
     game_state.occupancy_by_color[color.index()] |= mask;
     game_state.zobrist_key ^= piece_square_key(color, piece, square);
+    game_state.eval.add_piece(color, piece, square);
     if matches!(piece, PieceKind::Pawn | PieceKind::King) {
         game_state.pawn_zobrist_key ^= piece_square_key(color, piece, square);
     }
 }
 
-fn move_rook(
-    game_state: &mut GameState,
+/// Castling rook `(from, to)` squares for a castle whose king lands on `king_to`.
+///
+/// Castles are encoded by king destination (g- or c-file); the rook origin
+/// comes from the recorded castling rook files so Chess960 setups work.
+#[inline]
+pub(crate) fn castling_rook_squares(
+    game_state: &GameState,
     color: Color,
-    from: Square,
-    to: Square,
-) -> Result<(), String> {
-    remove_piece(game_state, color, PieceKind::Rook, from);
-    add_piece(game_state, color, PieceKind::Rook, to);
-    Ok(())
+    king_to: SquareIndex,
+) -> (SquareIndex, SquareIndex) {
+    let base = back_rank_base(color);
+    if king_to % 8 == 6 {
+        (
+            base + game_state.castling_rook_files[color.index()][CASTLE_SIDE_KING],
+            base + 5,
+        )
+    } else {
+        (
+            base + game_state.castling_rook_files[color.index()][CASTLE_SIDE_QUEEN],
+            base + 3,
+        )
+    }
+}
+
+#[inline]
+pub(crate) const fn back_rank_base(color: Color) -> SquareIndex {
+    match color {
+        Color::Light => 0,
+        Color::Dark => 56,
+    }
 }
 
 fn update_castling_rights(
     game_state: &mut GameState,
     moving_color: Color,
-    from: Square,
-    to: Square,
+    from: SquareIndex,
+    to: SquareIndex,
     moved_piece: PieceKind,
 ) {
     if moved_piece == PieceKind::King {
@@ -292,29 +397,24 @@ fn update_castling_rights(
         }
     }
 
-    if moved_piece == PieceKind::Rook {
-        match from {
-            0 => game_state.castling_rights &= !CASTLE_LIGHT_QUEENSIDE,
-            7 => game_state.castling_rights &= !CASTLE_LIGHT_KINGSIDE,
-            56 => game_state.castling_rights &= !CASTLE_DARK_QUEENSIDE,
-            63 => game_state.castling_rights &= !CASTLE_DARK_KINGSIDE,
-            _ => {}
+    // Moving a castling rook, or capturing one on its origin, removes the right.
+    for color in [Color::Light, Color::Dark] {
+        for side in [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN] {
+            let rook_sq =
+                back_rank_base(color) + game_state.castling_rook_files[color.index()][side];
+            if (moved_piece == PieceKind::Rook && from == rook_sq) || to == rook_sq {
+                game_state.castling_rights &= !castling_right_flag(color, side);
+            }
         }
     }
-
-    // Capturing rook on original squares also removes rights.
-    match to {
-        0 => game_state.castling_rights &= !CASTLE_LIGHT_QUEENSIDE,
-        7 => game_state.castling_rights &= !CASTLE_LIGHT_KINGSIDE,
-        56 => game_state.castling_rights &= !CASTLE_DARK_QUEENSIDE,
-        63 => game_state.castling_rights &= !CASTLE_DARK_KINGSIDE,
-        _ => {}
-    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{make_move_in_place, unmake_move_in_place};
+    use super::{
+        make_move_in_place, make_null_move_in_place, unmake_move_in_place,
+        unmake_null_move_in_place,
+    };
     use crate::game_state::game_state::GameState;
     use crate::search::zobrist::{compute_pawn_zobrist_key, compute_zobrist_key};
     use crate::utils::long_algebraic::long_algebraic_to_move_description;
@@ -340,4 +440,84 @@ mod tests {
         assert_eq!(state.zobrist_key, compute_zobrist_key(&state));
         assert_eq!(state.pawn_zobrist_key, compute_pawn_zobrist_key(&state));
     }
+
+    #[test]
+    fn chess960_castle_onto_rook_square_round_trips() {
+        // King f1, rook g1: kingside castle lands the king on the rook's origin.
+        let original = GameState::from_fen("1r2k1r1/8/8/8/8/8/8/1R3KR1 w GBgb - 0 1")
+            .expect("FEN should parse");
+        let mut state = original.clone();
+        let mv = long_algebraic_to_move_description("f1g1", &state).expect("castle should parse");
+        make_move_in_place(&mut state, mv).expect("make castle");
+        assert_eq!(state.get_fen(), "1r2k1r1/8/8/8/8/8/8/1R3RK1 b kq - 1 1");
+        assert_eq!(state.zobrist_key, compute_zobrist_key(&state));
+
+        let queenside =
+            long_algebraic_to_move_description("e8b8", &state).expect("castle should parse");
+        make_move_in_place(&mut state, queenside).expect("make castle");
+        assert_eq!(state.get_fen(), "2kr2r1/8/8/8/8/8/8/1R3RK1 w - - 2 2");
+
+        unmake_move_in_place(&mut state).expect("unmake castle");
+        unmake_move_in_place(&mut state).expect("unmake castle");
+        assert_eq!(state.get_fen(), original.get_fen());
+        assert_eq!(state.zobrist_key, original.zobrist_key);
+    }
+
+    #[test]
+    fn null_move_round_trips_hash_and_clears_en_passant() {
+        let original =
+            GameState::from_fen("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3")
+                .expect("FEN should parse");
+        let mut state = original.clone();
+
+        make_null_move_in_place(&mut state);
+        assert_eq!(
+            state.get_fen(),
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 3"
+        );
+        assert_eq!(state.zobrist_key, compute_zobrist_key(&state));
+        assert_ne!(state.zobrist_key, original.zobrist_key);
+        assert_eq!(state.repetition_history.last(), Some(&state.zobrist_key));
+
+        let mv = long_algebraic_to_move_description("g8f6", &state).expect("move parse");
+        make_move_in_place(&mut state, mv).expect("make move");
+        assert!(unmake_null_move_in_place(&mut state).is_err());
+        unmake_move_in_place(&mut state).expect("unmake move");
+        unmake_null_move_in_place(&mut state).expect("unmake null move");
+
+        assert_eq!(state.get_fen(), original.get_fen());
+        assert_eq!(state.zobrist_key, original.zobrist_key);
+        assert_eq!(state.repetition_history, original.repetition_history);
+        assert!(state.undo_stack.is_empty());
+
+        make_null_move_in_place(&mut state);
+        unmake_move_in_place(&mut state).expect("generic unmake handles null moves");
+        assert_eq!(state.zobrist_key, original.zobrist_key);
+    }
+
+    #[test]
+    fn repetition_window_never_reaches_across_null_move() {
+        let mut state = GameState::new_game();
+        for lan in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            let mv = long_algebraic_to_move_description(lan, &state).expect("move parse");
+            make_move_in_place(&mut state, mv).expect("make move");
+        }
+        assert_eq!(state.halfmove_clock, 4);
+        let start_key = state.zobrist_key;
+
+        // Two null moves bring the start position back on the board, but the
+        // halfmove clock bounds every repetition scan to the post-null window.
+        make_null_move_in_place(&mut state);
+        make_null_move_in_place(&mut state);
+        assert_eq!(state.zobrist_key, start_key);
+        assert_eq!(state.halfmove_clock, 0);
+        let window = usize::from(state.halfmove_clock) + 1;
+        let recent = &state.repetition_history[state.repetition_history.len() - window..];
+        assert_eq!(recent.iter().filter(|k| **k == start_key).count(), 1);
+
+        unmake_null_move_in_place(&mut state).expect("unmake null move");
+        unmake_null_move_in_place(&mut state).expect("unmake null move");
+        assert_eq!(state.halfmove_clock, 4);
+        assert_eq!(state.repetition_history.len(), 5);
+    }
 }
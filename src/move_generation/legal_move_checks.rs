@@ -11,12 +11,12 @@ use crate::moves::pawn_moves::pawn_attacks;
 use crate::moves::rook_moves::rook_attacks;
 
 #[inline]
-pub fn king_square(game_state: &GameState, color: Color) -> Option<Square> {
+pub fn king_square(game_state: &GameState, color: Color) -> Option<SquareIndex> {
     let kings = game_state.pieces[color.index()][PieceKind::King.index()];
     if kings == 0 {
         None
     } else {
-        Some(kings.trailing_zeros() as Square)
+        Some(kings.trailing_zeros() as SquareIndex)
     }
 }
 
@@ -28,13 +28,17 @@ pub fn is_king_in_check(game_state: &GameState, color: Color) -> bool {
     is_square_attacked(game_state, king_sq, color.opposite())
 }
 
-pub fn is_square_attacked(game_state: &GameState, square: Square, attacker_color: Color) -> bool {
+pub fn is_square_attacked(
+    game_state: &GameState,
+    square: SquareIndex,
+    attacker_color: Color,
+) -> bool {
     let target_mask = 1u64 << square;
 
     let attacker_pawns = game_state.pieces[attacker_color.index()][PieceKind::Pawn.index()];
     let mut pawns = attacker_pawns;
     while pawns != 0 {
-        let from = pawns.trailing_zeros() as Square;
+        let from = pawns.trailing_zeros() as SquareIndex;
         if pawn_attacks(attacker_color, from) & target_mask != 0 {
             return true;
         }
@@ -66,17 +70,53 @@ pub fn is_square_attacked(game_state: &GameState, square: Square, attacker_color
     false
 }
 
+/// Bitboard of `attacker_color` pieces attacking `square` under `occupancy`.
+///
+/// Taking occupancy explicitly lets callers ask "what if" questions, such as
+/// whether a king destination stays attacked once the king has left its square.
+#[inline]
+pub fn attackers_bitboard(
+    game_state: &GameState,
+    square: SquareIndex,
+    attacker_color: Color,
+    occupancy: u64,
+) -> u64 {
+    let pieces = &game_state.pieces[attacker_color.index()];
+    let queens = pieces[PieceKind::Queen.index()];
+    (pawn_attacks(attacker_color.opposite(), square) & pieces[PieceKind::Pawn.index()])
+        | (knight_attacks(square) & pieces[PieceKind::Knight.index()])
+        | (king_attacks(square) & pieces[PieceKind::King.index()])
+        | (bishop_attacks(square, occupancy) & (pieces[PieceKind::Bishop.index()] | queens))
+        | (rook_attacks(square, occupancy) & (pieces[PieceKind::Rook.index()] | queens))
+}
+
+/// Squares strictly between `a` and `b` when aligned on a rank, file or diagonal.
+#[inline]
+pub fn squares_between(a: SquareIndex, b: SquareIndex) -> u64 {
+    let df = (b % 8) as i8 - (a % 8) as i8;
+    let dr = (b / 8) as i8 - (a / 8) as i8;
+    if a == b {
+        0
+    } else if df == 0 || dr == 0 {
+        rook_attacks(a, 1u64 << b) & rook_attacks(b, 1u64 << a)
+    } else if df.abs() == dr.abs() {
+        bishop_attacks(a, 1u64 << b) & bishop_attacks(b, 1u64 << a)
+    } else {
+        0
+    }
+}
+
 pub fn attackers_to_square(
     game_state: &GameState,
-    square: Square,
+    square: SquareIndex,
     attacker_color: Color,
-) -> Vec<(Square, PieceKind)> {
+) -> Vec<(SquareIndex, PieceKind)> {
     let target_mask = 1u64 << square;
-    let mut attackers = Vec::<(Square, PieceKind)>::new();
+    let mut attackers = Vec::<(SquareIndex, PieceKind)>::new();
 
     let mut pawns = game_state.pieces[attacker_color.index()][PieceKind::Pawn.index()];
     while pawns != 0 {
-        let from = pawns.trailing_zeros() as Square;
+        let from = pawns.trailing_zeros() as SquareIndex;
         if pawn_attacks(attacker_color, from) & target_mask != 0 {
             attackers.push((from, PieceKind::Pawn));
         }
@@ -85,7 +125,7 @@ pub fn attackers_to_square(
 
     let mut knights = game_state.pieces[attacker_color.index()][PieceKind::Knight.index()];
     while knights != 0 {
-        let from = knights.trailing_zeros() as Square;
+        let from = knights.trailing_zeros() as SquareIndex;
         if knight_attacks(from) & target_mask != 0 {
             attackers.push((from, PieceKind::Knight));
         }
@@ -94,7 +134,7 @@ pub fn attackers_to_square(
 
     let mut bishops = game_state.pieces[attacker_color.index()][PieceKind::Bishop.index()];
     while bishops != 0 {
-        let from = bishops.trailing_zeros() as Square;
+        let from = bishops.trailing_zeros() as SquareIndex;
         if bishop_attacks(from, game_state.occupancy_all) & target_mask != 0 {
             attackers.push((from, PieceKind::Bishop));
         }
@@ -103,7 +143,7 @@ pub fn attackers_to_square(
 
     let mut rooks = game_state.pieces[attacker_color.index()][PieceKind::Rook.index()];
     while rooks != 0 {
-        let from = rooks.trailing_zeros() as Square;
+        let from = rooks.trailing_zeros() as SquareIndex;
         if rook_attacks(from, game_state.occupancy_all) & target_mask != 0 {
             attackers.push((from, PieceKind::Rook));
         }
@@ -112,7 +152,7 @@ pub fn attackers_to_square(
 
     let mut queens = game_state.pieces[attacker_color.index()][PieceKind::Queen.index()];
     while queens != 0 {
-        let from = queens.trailing_zeros() as Square;
+        let from = queens.trailing_zeros() as SquareIndex;
         let attacks = bishop_attacks(from, game_state.occupancy_all)
             | rook_attacks(from, game_state.occupancy_all);
         if attacks & target_mask != 0 {
@@ -123,7 +163,7 @@ pub fn attackers_to_square(
 
     let mut kings = game_state.pieces[attacker_color.index()][PieceKind::King.index()];
     while kings != 0 {
-        let from = kings.trailing_zeros() as Square;
+        let from = kings.trailing_zeros() as SquareIndex;
         if king_attacks(from) & target_mask != 0 {
             attackers.push((from, PieceKind::King));
         }
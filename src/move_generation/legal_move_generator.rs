@@ -10,6 +10,7 @@ use crate::move_generation::legal_move_apply::{
 use crate::move_generation::legal_move_checks::{
     attackers_to_square, is_king_in_check, king_square,
 };
+use crate::move_generation::legal_move_pin_mask::generate_legal_move_descriptions_pin_mask;
 use crate::move_generation::legal_moves_bishop::generate_bishop_moves;
 use crate::move_generation::legal_moves_king::generate_king_moves;
 use crate::move_generation::legal_moves_knight::generate_knight_moves;
@@ -19,6 +20,7 @@ use crate::move_generation::legal_moves_rook::generate_rook_moves;
 use crate::move_generation::move_generator::{
     GeneratedMove, MoveAnnotations, MoveGenResult, MoveGenerationError, MoveGenerator,
 };
+use crate::move_generation::move_list::MoveList;
 use crate::moves::move_descriptions::{
     move_from, move_moved_piece_code, move_promotion_piece_code, move_to, piece_kind_from_code,
 };
@@ -26,14 +28,35 @@ use crate::moves::move_descriptions::{
 pub struct LegalMoveGenerator;
 pub struct FastLegalMoveGenerator;
 
-/// Generate legal move descriptions using in-place make/unmake.
+/// Generate legal move descriptions for search hot paths.
 ///
-/// This path avoids allocating future `GameState` snapshots per move and is
-/// intended for search hot paths.
+/// Delegates to the pin/check-mask generator, which needs no per-move
+/// make/unmake; the `&mut` receiver is kept for call-site compatibility.
 pub fn generate_legal_move_descriptions_in_place(
     game_state: &mut GameState,
 ) -> MoveGenResult<Vec<u64>> {
-    let mut moves = Vec::<u64>::with_capacity(128);
+    Ok(generate_legal_move_list(game_state).to_vec())
+}
+
+/// Generate legal move descriptions into a stack-allocated `MoveList`.
+///
+/// Allocation-free counterpart of `generate_legal_move_descriptions_in_place`
+/// for search and perft hot paths.
+#[inline]
+pub fn generate_legal_move_list(game_state: &GameState) -> MoveList {
+    let mut moves = MoveList::new();
+    generate_legal_move_descriptions_pin_mask(game_state, &mut moves);
+    moves
+}
+
+/// Generate legal move descriptions by filtering pseudo-legal moves with
+/// in-place make/unmake.
+///
+/// Reference implementation used to cross-check the pin/check-mask path.
+pub fn generate_legal_move_descriptions_filtered(
+    game_state: &mut GameState,
+) -> MoveGenResult<Vec<u64>> {
+    let mut moves = MoveList::new();
     generate_pawn_moves(game_state, &mut moves);
     generate_knight_moves(game_state, &mut moves);
     generate_bishop_moves(game_state, &mut moves);
@@ -42,9 +65,8 @@ pub fn generate_legal_move_descriptions_in_place(
     generate_king_moves(game_state, &mut moves);
 
     let side_before = game_state.side_to_move;
-    let mut write_idx = 0usize;
-    for read_idx in 0..moves.len() {
-        let mv = moves[read_idx];
+    let mut legal = Vec::<u64>::with_capacity(moves.len());
+    for mv in moves {
         make_move_in_place(game_state, mv).map_err(|x| {
             MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
         })?;
@@ -53,12 +75,10 @@ pub fn generate_legal_move_descriptions_in_place(
             MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
         })?;
         if !illegal {
-            moves[write_idx] = mv;
-            write_idx += 1;
+            legal.push(mv);
         }
     }
-    moves.truncate(write_idx);
-    Ok(moves)
+    Ok(legal)
 }
 
 impl MoveGenerator for LegalMoveGenerator {
@@ -79,7 +99,7 @@ impl LegalMoveGenerator {
         game_state: &GameState,
         annotate: bool,
     ) -> MoveGenResult<Vec<GeneratedMove>> {
-        let mut pseudo = Vec::<u64>::with_capacity(128);
+        let mut pseudo = MoveList::new();
 
         generate_pawn_moves(game_state, &mut pseudo);
         generate_knight_moves(game_state, &mut pseudo);
@@ -100,7 +120,11 @@ impl LegalMoveGenerator {
             }
 
             let annotations = if annotate {
-                classify_move_annotations(self, game_state, mv, &next)?
+                classify_move_annotations(game_state, mv, &next, |reply_state| {
+                    Ok(self
+                        .generate_legal_moves_internal(reply_state, false)?
+                        .len())
+                })?
             } else {
                 MoveAnnotations::default()
             };
@@ -116,11 +140,15 @@ impl LegalMoveGenerator {
     }
 }
 
-fn classify_move_annotations(
-    generator: &LegalMoveGenerator,
+/// Classify check/discovery/double-check/mate metadata for `prev -> next`.
+///
+/// `count_replies` supplies the legal reply count used for mate detection so
+/// each generator can use its own (cheapest) counting path.
+pub(crate) fn classify_move_annotations(
     prev: &GameState,
     move_description: u64,
     next: &GameState,
+    count_replies: impl FnOnce(&GameState) -> MoveGenResult<usize>,
 ) -> MoveGenResult<MoveAnnotations> {
     let Some(defender_king_sq) = king_square(next, next.side_to_move) else {
         return Ok(MoveAnnotations::default());
@@ -151,7 +179,7 @@ fn classify_move_annotations(
         false
     };
 
-    let reply_count = generator.generate_legal_moves_internal(next, false)?.len();
+    let reply_count = count_replies(next)?;
 
     Ok(MoveAnnotations {
         gives_check: true,
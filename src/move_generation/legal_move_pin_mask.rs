@@ -0,0 +1,550 @@
+//! Fully legal move generation from check and pin masks.
+//!
+//! Checkers, pin rays and the check-evasion mask are computed once per
+//! position, so every emitted move is legal without a per-move make/unmake.
+//! Emission order matches the piece-wise pseudo-legal generators.
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::move_generation::legal_move_apply::{apply_move, build_move};
+use crate::move_generation::legal_move_checks::{attackers_bitboard, squares_between};
+use crate::move_generation::legal_move_generator::classify_move_annotations;
+use crate::move_generation::legal_move_shared::enemy_piece_on;
+use crate::move_generation::legal_moves_king::generate_castling_moves;
+use crate::move_generation::move_generator::{
+    GeneratedMove, MoveAnnotations, MoveGenResult, MoveGenerationError, MoveGenerator,
+};
+use crate::move_generation::move_list::MoveList;
+use crate::moves::bishop_moves::bishop_attacks;
+use crate::moves::king_moves::king_attacks;
+use crate::moves::knight_moves::knight_attacks;
+use crate::moves::move_descriptions::{FLAG_CAPTURE, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT};
+use crate::moves::pawn_moves::pawn_attacks;
+use crate::moves::rook_moves::rook_attacks;
+
+/// Pin/check-mask generator with check/mate annotations.
+pub struct PinMaskMoveGenerator;
+/// Pin/check-mask generator without annotations (search/perft hot path).
+pub struct FastPinMaskMoveGenerator;
+
+const PROMOTION_PIECES: [PieceKind; 4] = [
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+];
+
+/// Per-position legality context shared by all piece emitters.
+struct LegalityMasks {
+    king_sq: SquareIndex,
+    checkers: u64,
+    /// Destination squares that resolve the current check (all squares if none).
+    check_mask: u64,
+    pinned: u64,
+    pin_rays: [u64; 64],
+}
+
+impl LegalityMasks {
+    fn new(game_state: &GameState, king_sq: SquareIndex) -> Self {
+        let side = game_state.side_to_move;
+        let enemy = side.opposite();
+        let occ = game_state.occupancy_all;
+        let own_occ = game_state.occupancy_by_color[side.index()];
+        let enemy_occ = game_state.occupancy_by_color[enemy.index()];
+
+        let checkers = attackers_bitboard(game_state, king_sq, enemy, occ);
+        let check_mask = match checkers.count_ones() {
+            0 => !0u64,
+            1 => checkers | squares_between(king_sq, checkers.trailing_zeros() as SquareIndex),
+            _ => 0,
+        };
+
+        let enemy_pieces = &game_state.pieces[enemy.index()];
+        let enemy_queens = enemy_pieces[PieceKind::Queen.index()];
+        let mut snipers = (rook_attacks(king_sq, enemy_occ)
+            & (enemy_pieces[PieceKind::Rook.index()] | enemy_queens))
+            | (bishop_attacks(king_sq, enemy_occ)
+                & (enemy_pieces[PieceKind::Bishop.index()] | enemy_queens));
+
+        let mut pinned = 0u64;
+        let mut pin_rays = [!0u64; 64];
+        while snipers != 0 {
+            let sniper = snipers.trailing_zeros() as SquareIndex;
+            let between = squares_between(king_sq, sniper);
+            let blockers = between & occ;
+            if blockers.count_ones() == 1 && (blockers & own_occ) != 0 {
+                pinned |= blockers;
+                pin_rays[blockers.trailing_zeros() as usize] = between | (1u64 << sniper);
+            }
+            snipers &= snipers - 1;
+        }
+
+        Self {
+            king_sq,
+            checkers,
+            check_mask,
+            pinned,
+            pin_rays,
+        }
+    }
+
+    #[inline]
+    fn allowed_targets(&self, from: SquareIndex) -> u64 {
+        if (self.pinned & (1u64 << from)) != 0 {
+            self.check_mask & self.pin_rays[from as usize]
+        } else {
+            self.check_mask
+        }
+    }
+}
+
+/// Which legal moves an emitter pass should produce.
+///
+/// "Tactical" covers captures, en-passant and every promotion; "quiet" is the
+/// complement (including castling and double pushes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MoveSelection {
+    pub tactical: bool,
+    pub quiet: bool,
+    /// Only pieces standing on these squares are considered.
+    pub from_mask: u64,
+}
+
+impl MoveSelection {
+    pub(crate) const ALL: Self = Self {
+        tactical: true,
+        quiet: true,
+        from_mask: !0,
+    };
+    pub(crate) const TACTICAL: Self = Self {
+        tactical: true,
+        quiet: false,
+        from_mask: !0,
+    };
+    pub(crate) const QUIET: Self = Self {
+        tactical: false,
+        quiet: true,
+        from_mask: !0,
+    };
+
+    #[inline]
+    fn piece_targets(self, game_state: &GameState) -> u64 {
+        let enemy_occ = game_state.occupancy_by_color[game_state.side_to_move.opposite().index()];
+        let mut targets = 0u64;
+        if self.tactical {
+            targets |= enemy_occ;
+        }
+        if self.quiet {
+            targets |= !game_state.occupancy_all;
+        }
+        targets
+    }
+}
+
+/// Generate fully legal move descriptions without make/unmake filtering.
+///
+/// The output order is identical to pseudo-legal generation followed by
+/// self-check filtering, so callers can switch paths transparently.
+pub fn generate_legal_move_descriptions_pin_mask(game_state: &GameState, out: &mut MoveList) {
+    generate_selected_legal_moves(game_state, MoveSelection::ALL, out);
+}
+
+/// Emit the legal moves accepted by `selection`, preserving generator order.
+pub(crate) fn generate_selected_legal_moves(
+    game_state: &GameState,
+    selection: MoveSelection,
+    out: &mut MoveList,
+) {
+    let side = game_state.side_to_move;
+    let king_bb = game_state.pieces[side.index()][PieceKind::King.index()];
+    if king_bb == 0 {
+        return;
+    }
+    let masks = LegalityMasks::new(game_state, king_bb.trailing_zeros() as SquareIndex);
+
+    if masks.checkers.count_ones() < 2 {
+        emit_pawn_moves(game_state, &masks, selection, out);
+        emit_knight_moves(game_state, &masks, selection, out);
+        emit_slider_moves(game_state, &masks, selection, PieceKind::Bishop, out);
+        emit_slider_moves(game_state, &masks, selection, PieceKind::Rook, out);
+        emit_slider_moves(game_state, &masks, selection, PieceKind::Queen, out);
+    }
+    if (king_bb & selection.from_mask) != 0 {
+        emit_king_moves(game_state, &masks, selection, out);
+    }
+}
+
+/// Whether the side to move is in check, using the same attacker query as the
+/// mask builder.
+#[inline]
+pub(crate) fn side_to_move_in_check(game_state: &GameState) -> bool {
+    let side = game_state.side_to_move;
+    let king_bb = game_state.pieces[side.index()][PieceKind::King.index()];
+    king_bb != 0
+        && attackers_bitboard(
+            game_state,
+            king_bb.trailing_zeros() as SquareIndex,
+            side.opposite(),
+            game_state.occupancy_all,
+        ) != 0
+}
+
+#[inline]
+fn push_target_moves(
+    game_state: &GameState,
+    from: SquareIndex,
+    piece: PieceKind,
+    mut targets: u64,
+    out: &mut MoveList,
+) {
+    let enemy_occ = game_state.occupancy_by_color[game_state.side_to_move.opposite().index()];
+    while targets != 0 {
+        let to = targets.trailing_zeros() as SquareIndex;
+        let is_capture = ((1u64 << to) & enemy_occ) != 0;
+        let captured = if is_capture {
+            enemy_piece_on(game_state, to)
+        } else {
+            None
+        };
+        out.push(build_move(
+            from,
+            to,
+            piece,
+            captured,
+            None,
+            if is_capture { FLAG_CAPTURE } else { 0 },
+        ));
+        targets &= targets - 1;
+    }
+}
+
+fn emit_knight_moves(
+    game_state: &GameState,
+    masks: &LegalityMasks,
+    selection: MoveSelection,
+    out: &mut MoveList,
+) {
+    let side = game_state.side_to_move;
+    let class_targets = selection.piece_targets(game_state);
+    // A pinned knight can never stay on its pin ray.
+    let mut knights = game_state.pieces[side.index()][PieceKind::Knight.index()]
+        & !masks.pinned
+        & selection.from_mask;
+    while knights != 0 {
+        let from = knights.trailing_zeros() as SquareIndex;
+        let targets = knight_attacks(from) & class_targets & masks.check_mask;
+        push_target_moves(game_state, from, PieceKind::Knight, targets, out);
+        knights &= knights - 1;
+    }
+}
+
+fn emit_slider_moves(
+    game_state: &GameState,
+    masks: &LegalityMasks,
+    selection: MoveSelection,
+    piece: PieceKind,
+    out: &mut MoveList,
+) {
+    let side = game_state.side_to_move;
+    let class_targets = selection.piece_targets(game_state);
+    let occ = game_state.occupancy_all;
+    let mut sliders = game_state.pieces[side.index()][piece.index()] & selection.from_mask;
+    while sliders != 0 {
+        let from = sliders.trailing_zeros() as SquareIndex;
+        let attacks = match piece {
+            PieceKind::Bishop => bishop_attacks(from, occ),
+            PieceKind::Rook => rook_attacks(from, occ),
+            _ => bishop_attacks(from, occ) | rook_attacks(from, occ),
+        };
+        let targets = attacks & class_targets & masks.allowed_targets(from);
+        push_target_moves(game_state, from, piece, targets, out);
+        sliders &= sliders - 1;
+    }
+}
+
+fn emit_king_moves(
+    game_state: &GameState,
+    masks: &LegalityMasks,
+    selection: MoveSelection,
+    out: &mut MoveList,
+) {
+    let enemy = game_state.side_to_move.opposite();
+    // Remove the king so sliders see through it along the checking ray.
+    let occ_without_king = game_state.occupancy_all & !(1u64 << masks.king_sq);
+
+    let mut candidates = king_attacks(masks.king_sq) & selection.piece_targets(game_state);
+    let mut safe = 0u64;
+    while candidates != 0 {
+        let to = candidates.trailing_zeros() as SquareIndex;
+        if attackers_bitboard(game_state, to, enemy, occ_without_king) == 0 {
+            safe |= 1u64 << to;
+        }
+        candidates &= candidates - 1;
+    }
+    push_target_moves(game_state, masks.king_sq, PieceKind::King, safe, out);
+
+    if selection.quiet && masks.checkers == 0 {
+        generate_castling_moves(game_state, out, masks.king_sq);
+    }
+}
+
+fn emit_pawn_moves(
+    game_state: &GameState,
+    masks: &LegalityMasks,
+    selection: MoveSelection,
+    out: &mut MoveList,
+) {
+    let side = game_state.side_to_move;
+    let enemy_occ = game_state.occupancy_by_color[side.opposite().index()];
+    let empty = !game_state.occupancy_all;
+    let (promotion_rank, start_rank) = if side == Color::Light { (7, 1) } else { (0, 6) };
+
+    let mut pawns = game_state.pieces[side.index()][PieceKind::Pawn.index()] & selection.from_mask;
+    while pawns != 0 {
+        let from = pawns.trailing_zeros() as SquareIndex;
+        let allowed = masks.allowed_targets(from);
+
+        let one_step = if side == Color::Light {
+            from + 8
+        } else {
+            from - 8
+        };
+        if ((1u64 << one_step) & empty) != 0 {
+            if ((1u64 << one_step) & allowed) != 0 {
+                if one_step / 8 == promotion_rank {
+                    if selection.tactical {
+                        for promo in PROMOTION_PIECES {
+                            out.push(build_move(
+                                from,
+                                one_step,
+                                PieceKind::Pawn,
+                                None,
+                                Some(promo),
+                                0,
+                            ));
+                        }
+                    }
+                } else if selection.quiet {
+                    out.push(build_move(from, one_step, PieceKind::Pawn, None, None, 0));
+                }
+            }
+
+            if selection.quiet && from / 8 == start_rank {
+                let two_step = if side == Color::Light {
+                    from + 16
+                } else {
+                    from - 16
+                };
+                let two_mask = 1u64 << two_step;
+                if (two_mask & empty & allowed) != 0 {
+                    out.push(build_move(
+                        from,
+                        two_step,
+                        PieceKind::Pawn,
+                        None,
+                        None,
+                        FLAG_DOUBLE_PAWN_PUSH,
+                    ));
+                }
+            }
+        }
+
+        if !selection.tactical {
+            pawns &= pawns - 1;
+            continue;
+        }
+
+        // Captures in ascending target order match the pseudo-legal generator.
+        let mut captures = pawn_attacks(side, from);
+        while captures != 0 {
+            let to = captures.trailing_zeros() as SquareIndex;
+            let to_mask = 1u64 << to;
+            if (to_mask & enemy_occ) != 0 {
+                if (to_mask & allowed) != 0 {
+                    let captured = enemy_piece_on(game_state, to);
+                    if to / 8 == promotion_rank {
+                        for promo in PROMOTION_PIECES {
+                            out.push(build_move(
+                                from,
+                                to,
+                                PieceKind::Pawn,
+                                captured,
+                                Some(promo),
+                                FLAG_CAPTURE,
+                            ));
+                        }
+                    } else {
+                        out.push(build_move(
+                            from,
+                            to,
+                            PieceKind::Pawn,
+                            captured,
+                            None,
+                            FLAG_CAPTURE,
+                        ));
+                    }
+                }
+            } else if game_state.en_passant_square == Some(to)
+                && en_passant_is_legal(game_state, masks.king_sq, from, to)
+            {
+                out.push(build_move(
+                    from,
+                    to,
+                    PieceKind::Pawn,
+                    Some(PieceKind::Pawn),
+                    None,
+                    FLAG_CAPTURE | FLAG_EN_PASSANT,
+                ));
+            }
+            captures &= captures - 1;
+        }
+
+        pawns &= pawns - 1;
+    }
+}
+
+/// En-passant removes two pawns from one rank, which can expose the king to a
+/// slider that no pin ray sees. Re-test king safety on the post-capture board.
+fn en_passant_is_legal(
+    game_state: &GameState,
+    king_sq: SquareIndex,
+    from: SquareIndex,
+    to: SquareIndex,
+) -> bool {
+    let side = game_state.side_to_move;
+    let enemy = side.opposite();
+    let captured_sq = if side == Color::Light { to - 8 } else { to + 8 };
+    let occ_after =
+        (game_state.occupancy_all & !(1u64 << from) & !(1u64 << captured_sq)) | (1u64 << to);
+
+    let attackers = attackers_bitboard(game_state, king_sq, enemy, occ_after);
+    (attackers & !(1u64 << captured_sq)) == 0
+}
+
+impl MoveGenerator for PinMaskMoveGenerator {
+    fn generate_legal_moves(&self, game_state: &GameState) -> MoveGenResult<Vec<GeneratedMove>> {
+        generate_with_states(game_state, true)
+    }
+}
+
+impl MoveGenerator for FastPinMaskMoveGenerator {
+    fn generate_legal_moves(&self, game_state: &GameState) -> MoveGenResult<Vec<GeneratedMove>> {
+        generate_with_states(game_state, false)
+    }
+}
+
+fn generate_with_states(
+    game_state: &GameState,
+    annotate: bool,
+) -> MoveGenResult<Vec<GeneratedMove>> {
+    let mut moves = MoveList::new();
+    generate_legal_move_descriptions_pin_mask(game_state, &mut moves);
+
+    let mut legal = Vec::<GeneratedMove>::with_capacity(moves.len());
+    for mv in moves {
+        let next = apply_move(game_state, mv)
+            .map_err(|x| MoveGenerationError::InvalidState(format!("apply_move failed: {x}")))?;
+        let annotations = if annotate {
+            classify_move_annotations(game_state, mv, &next, count_legal_replies)?
+        } else {
+            MoveAnnotations::default()
+        };
+        legal.push(GeneratedMove {
+            move_description: mv,
+            game_after_move: next,
+            annotations,
+        });
+    }
+    Ok(legal)
+}
+
+fn count_legal_replies(game_state: &GameState) -> MoveGenResult<usize> {
+    let mut replies = MoveList::new();
+    generate_legal_move_descriptions_pin_mask(game_state, &mut replies);
+    Ok(replies.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_legal_move_descriptions_pin_mask, PinMaskMoveGenerator};
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_apply::apply_move;
+    use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_filtered;
+    use crate::move_generation::move_generator::MoveGenerator;
+    use crate::move_generation::move_list::MoveList;
+
+    fn filtered_moves(game: &GameState) -> Vec<u64> {
+        let mut probe = game.clone();
+        generate_legal_move_descriptions_filtered(&mut probe).expect("filtered generation")
+    }
+
+    fn pin_mask_moves(game: &GameState) -> Vec<u64> {
+        let mut out = MoveList::new();
+        generate_legal_move_descriptions_pin_mask(game, &mut out);
+        out.to_vec()
+    }
+
+    #[test]
+    fn en_passant_exposing_rank_check_is_rejected() {
+        // Capturing exd6 e.p. would empty the 5th rank between Kh5 and Ra5.
+        let game = GameState::from_fen("8/8/8/r2pP2K/8/8/8/4k3 w - d6 0 1").expect("FEN");
+        let moves = pin_mask_moves(&game);
+        assert_eq!(moves, filtered_moves(&game));
+        assert!(moves
+            .iter()
+            .all(|mv| (mv & crate::moves::move_descriptions::FLAG_EN_PASSANT) == 0));
+    }
+
+    #[test]
+    fn pinned_piece_moves_only_along_pin_ray() {
+        // Bd2 is pinned by Bb4 against Ke1; only Bxb4 and Bc3 keep the pin ray.
+        let game = GameState::from_fen("4k3/8/8/8/1b6/8/3B4/4K3 w - - 0 1").expect("FEN");
+        assert_eq!(pin_mask_moves(&game), filtered_moves(&game));
+    }
+
+    #[test]
+    fn pin_mask_matches_filtered_generation_along_random_playouts() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        ];
+        let mut rng = StdRng::seed_from_u64(2024);
+        for fen in fens {
+            for _ in 0..20 {
+                let mut game = GameState::from_fen(fen).expect("FEN");
+                for _ in 0..40 {
+                    let expected = filtered_moves(&game);
+                    assert_eq!(
+                        pin_mask_moves(&game),
+                        expected,
+                        "mismatch in {}",
+                        game.get_fen()
+                    );
+                    if expected.is_empty() {
+                        break;
+                    }
+                    let mv = expected[rng.random_range(0..expected.len())];
+                    game = apply_move(&game, mv).expect("legal move applies");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn annotated_generator_reports_checkmate() {
+        // Fool's mate: black to play Qh4#.
+        let game =
+            GameState::from_fen("rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2")
+                .expect("FEN");
+        let moves = PinMaskMoveGenerator
+            .generate_legal_moves(&game)
+            .expect("generation should succeed");
+        assert_eq!(
+            moves.iter().filter(|m| m.annotations.is_checkmate).count(),
+            1
+        );
+    }
+}
@@ -18,7 +18,7 @@ pub const ALL_PIECE_KINDS: [PieceKind; 6] = [
 pub fn piece_on_square_for_color(
     game_state: &GameState,
     color: Color,
-    square: Square,
+    square: SquareIndex,
 ) -> Option<PieceKind> {
     let mask = 1u64 << square;
     for piece in ALL_PIECE_KINDS {
@@ -30,12 +30,15 @@ pub fn piece_on_square_for_color(
 }
 
 #[inline]
-pub fn enemy_piece_on(game_state: &GameState, square: Square) -> Option<PieceKind> {
+pub fn enemy_piece_on(game_state: &GameState, square: SquareIndex) -> Option<PieceKind> {
     piece_on_square_for_color(game_state, game_state.side_to_move.opposite(), square)
 }
 
 #[inline]
-pub fn piece_on_square_any(game_state: &GameState, square: Square) -> Option<(Color, PieceKind)> {
+pub fn piece_on_square_any(
+    game_state: &GameState,
+    square: SquareIndex,
+) -> Option<(Color, PieceKind)> {
     if let Some(piece) = piece_on_square_for_color(game_state, Color::Light, square) {
         return Some((Color::Light, piece));
     }
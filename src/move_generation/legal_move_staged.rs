@@ -0,0 +1,182 @@
+//! Staged legal move generation entry points.
+//!
+//! Splits legal generation into tactical, quiet, evasion and quiet-check
+//! slices so search can generate only what a node actually consumes.
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::move_generation::legal_move_apply::apply_move;
+use crate::move_generation::legal_move_checks::{is_king_in_check, king_square};
+use crate::move_generation::legal_move_pin_mask::{
+    generate_selected_legal_moves, side_to_move_in_check, MoveSelection,
+};
+use crate::move_generation::move_list::MoveList;
+use crate::moves::bishop_moves::bishop_attacks;
+use crate::moves::knight_moves::knight_attacks;
+use crate::moves::move_descriptions::{
+    move_from, move_moved_piece_code, move_promotion_piece_code, move_to, piece_kind_from_code,
+    FLAG_CASTLING, FLAG_EN_PASSANT, NO_PIECE_CODE,
+};
+use crate::moves::pawn_moves::pawn_attacks;
+use crate::moves::rook_moves::rook_attacks;
+
+/// Append legal captures, en-passant captures and all promotions.
+pub fn generate_captures(game_state: &GameState, out: &mut MoveList) {
+    generate_selected_legal_moves(game_state, MoveSelection::TACTICAL, out);
+}
+
+/// Append legal non-capturing, non-promoting moves (including castling).
+pub fn generate_quiets(game_state: &GameState, out: &mut MoveList) {
+    generate_selected_legal_moves(game_state, MoveSelection::QUIET, out);
+}
+
+/// Append every legal check evasion; appends nothing when not in check.
+pub fn generate_evasions(game_state: &GameState, out: &mut MoveList) {
+    if side_to_move_in_check(game_state) {
+        generate_selected_legal_moves(game_state, MoveSelection::ALL, out);
+    }
+}
+
+/// Append legal quiet moves that give check (direct or discovered).
+pub fn generate_quiet_checks(game_state: &GameState, out: &mut MoveList) {
+    let mut quiets = MoveList::new();
+    generate_quiets(game_state, &mut quiets);
+    out.extend(quiets.into_iter().filter(|mv| gives_check(game_state, *mv)));
+}
+
+/// Whether the side to move has at least one legal move.
+pub fn has_legal_move(game_state: &GameState) -> bool {
+    let mut moves = MoveList::new();
+    generate_captures(game_state, &mut moves);
+    if !moves.is_empty() {
+        return true;
+    }
+    generate_quiets(game_state, &mut moves);
+    !moves.is_empty()
+}
+
+/// Whether legal `move_description` checks the opponent king.
+///
+/// Ordinary moves are resolved from bitboards; castling, en-passant and
+/// promotions fall back to applying the move.
+pub fn gives_check(game_state: &GameState, move_description: u64) -> bool {
+    let side = game_state.side_to_move;
+    let enemy = side.opposite();
+    let Some(enemy_king) = king_square(game_state, enemy) else {
+        return false;
+    };
+
+    if (move_description & (FLAG_CASTLING | FLAG_EN_PASSANT)) != 0
+        || move_promotion_piece_code(move_description) != NO_PIECE_CODE
+    {
+        return apply_move(game_state, move_description)
+            .map(|next| is_king_in_check(&next, enemy))
+            .unwrap_or(false);
+    }
+
+    let Some(piece) = piece_kind_from_code(move_moved_piece_code(move_description)) else {
+        return false;
+    };
+    let from = move_from(move_description);
+    let to = move_to(move_description);
+    let king_mask = 1u64 << enemy_king;
+    let occ_after = (game_state.occupancy_all & !(1u64 << from)) | (1u64 << to);
+
+    let direct = match piece {
+        PieceKind::Pawn => pawn_attacks(side, to),
+        PieceKind::Knight => knight_attacks(to),
+        PieceKind::Bishop => bishop_attacks(to, occ_after),
+        PieceKind::Rook => rook_attacks(to, occ_after),
+        PieceKind::Queen => bishop_attacks(to, occ_after) | rook_attacks(to, occ_after),
+        PieceKind::King => 0,
+    };
+    if (direct & king_mask) != 0 {
+        return true;
+    }
+
+    // Discovered check: one of our other sliders now sees the king.
+    let ours = &game_state.pieces[side.index()];
+    let queens = ours[PieceKind::Queen.index()];
+    let diagonal = (ours[PieceKind::Bishop.index()] | queens) & !(1u64 << from);
+    let straight = (ours[PieceKind::Rook.index()] | queens) & !(1u64 << from);
+    (bishop_attacks(enemy_king, occ_after) & diagonal) != 0
+        || (rook_attacks(enemy_king, occ_after) & straight) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        generate_captures, generate_evasions, generate_quiet_checks, generate_quiets, gives_check,
+    };
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_apply::apply_move;
+    use crate::move_generation::legal_move_checks::is_king_in_check;
+    use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+    use crate::move_generation::move_list::MoveList;
+    use crate::moves::move_descriptions::{move_promotion_piece_code, FLAG_CAPTURE, NO_PIECE_CODE};
+
+    const FENS: [&str; 5] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    ];
+
+    #[test]
+    fn captures_and_quiets_partition_legal_moves() {
+        for fen in FENS {
+            let mut game = GameState::from_fen(fen).expect("FEN should parse");
+            let all = generate_legal_move_descriptions_in_place(&mut game).expect("legal moves");
+
+            let mut staged = MoveList::new();
+            generate_captures(&game, &mut staged);
+            let capture_count = staged.len();
+            assert!(staged[..capture_count].iter().all(|mv| {
+                (mv & FLAG_CAPTURE) != 0 || move_promotion_piece_code(*mv) != NO_PIECE_CODE
+            }));
+            generate_quiets(&game, &mut staged);
+
+            let mut expected = all.clone();
+            expected.sort_unstable();
+            staged.sort_unstable();
+            assert_eq!(
+                staged.as_slice(),
+                expected.as_slice(),
+                "partition mismatch for {fen}"
+            );
+        }
+    }
+
+    #[test]
+    fn evasions_only_generated_in_check() {
+        let game = GameState::new_game();
+        let mut out = MoveList::new();
+        generate_evasions(&game, &mut out);
+        assert!(out.is_empty());
+
+        let mut checked =
+            GameState::from_fen("4k3/8/8/8/8/2n5/4r3/4K2R w K - 0 1").expect("FEN should parse");
+        generate_evasions(&checked, &mut out);
+        let all = generate_legal_move_descriptions_in_place(&mut checked).expect("legal moves");
+        assert!(!out.is_empty());
+        assert_eq!(out.as_slice(), all.as_slice());
+    }
+
+    #[test]
+    fn gives_check_matches_applied_position() {
+        for fen in FENS {
+            let mut game = GameState::from_fen(fen).expect("FEN should parse");
+            let all = generate_legal_move_descriptions_in_place(&mut game).expect("legal moves");
+            let mut quiet_checks = MoveList::new();
+            generate_quiet_checks(&game, &mut quiet_checks);
+            for mv in all {
+                let next = apply_move(&game, mv).expect("legal move applies");
+                let expected = is_king_in_check(&next, next.side_to_move);
+                assert_eq!(gives_check(&game, mv), expected, "check mismatch in {fen}");
+                let is_quiet =
+                    (mv & FLAG_CAPTURE) == 0 && move_promotion_piece_code(mv) == NO_PIECE_CODE;
+                assert_eq!(quiet_checks.contains(&mv), is_quiet && expected);
+            }
+        }
+    }
+}
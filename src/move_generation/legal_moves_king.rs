@@ -4,13 +4,16 @@
 //! move, including captures and special rules where applicable.
 
 use crate::game_state::{chess_types::*, game_state::GameState};
-use crate::move_generation::legal_move_apply::build_move;
-use crate::move_generation::legal_move_checks::is_square_attacked;
+use crate::move_generation::legal_move_apply::{back_rank_base, build_move};
+use crate::move_generation::legal_move_checks::{
+    attackers_bitboard, is_square_attacked, squares_between,
+};
 use crate::move_generation::legal_move_shared::enemy_piece_on;
+use crate::move_generation::move_list::MoveList;
 use crate::moves::king_moves::king_attacks;
 use crate::moves::move_descriptions::{FLAG_CAPTURE, FLAG_CASTLING};
 
-pub fn generate_king_moves(game_state: &GameState, out: &mut Vec<u64>) {
+pub fn generate_king_moves(game_state: &GameState, out: &mut MoveList) {
     let side = game_state.side_to_move;
     let own_occ = game_state.occupancy_by_color[side.index()];
     let enemy_occ = game_state.occupancy_by_color[side.opposite().index()];
@@ -19,10 +22,10 @@ pub fn generate_king_moves(game_state: &GameState, out: &mut Vec<u64>) {
         return;
     }
 
-    let from = king_bb.trailing_zeros() as Square;
+    let from = king_bb.trailing_zeros() as SquareIndex;
     let mut attacks = king_attacks(from) & !own_occ;
     while attacks != 0 {
-        let to = attacks.trailing_zeros() as Square;
+        let to = attacks.trailing_zeros() as SquareIndex;
         let to_mask = 1u64 << to;
         let is_capture = (to_mask & enemy_occ) != 0;
         let captured = if is_capture {
@@ -44,69 +47,79 @@ pub fn generate_king_moves(game_state: &GameState, out: &mut Vec<u64>) {
     generate_castling_moves(game_state, out, from);
 }
 
-fn generate_castling_moves(game_state: &GameState, out: &mut Vec<u64>, king_from: Square) {
+/// Emit legal castling moves for the side to move.
+///
+/// Handles arbitrary Chess960 king/rook origins: every square either piece
+/// crosses must be empty (apart from the two castling pieces) and the king
+/// may not start on, cross, or land on an attacked square. Attacks are tested
+/// with the castling rook lifted so x-rays through its origin are seen.
+pub(crate) fn generate_castling_moves(
+    game_state: &GameState,
+    out: &mut MoveList,
+    king_from: SquareIndex,
+) {
     let side = game_state.side_to_move;
     let enemy = side.opposite();
+    let base = back_rank_base(side);
+    if king_from / 8 != base / 8 {
+        return;
+    }
+    if (game_state.castling_rights
+        & (castling_right_flag(side, CASTLE_SIDE_KING)
+            | castling_right_flag(side, CASTLE_SIDE_QUEEN)))
+        == 0
+    {
+        return;
+    }
 
     // Cannot castle out of check.
     if is_square_attacked(game_state, king_from, enemy) {
         return;
     }
 
-    match side {
-        Color::Light => {
-            if king_from == 4 && (game_state.castling_rights & CASTLE_LIGHT_KINGSIDE) != 0 {
-                let empty = (1u64 << 5) | (1u64 << 6);
-                if (game_state.occupancy_all & empty) == 0
-                    && !is_square_attacked(game_state, 5, enemy)
-                    && !is_square_attacked(game_state, 6, enemy)
-                {
-                    out.push(build_move(4, 6, PieceKind::King, None, None, FLAG_CASTLING));
-                }
-            }
-            if king_from == 4 && (game_state.castling_rights & CASTLE_LIGHT_QUEENSIDE) != 0 {
-                let empty = (1u64 << 1) | (1u64 << 2) | (1u64 << 3);
-                if (game_state.occupancy_all & empty) == 0
-                    && !is_square_attacked(game_state, 3, enemy)
-                    && !is_square_attacked(game_state, 2, enemy)
-                {
-                    out.push(build_move(4, 2, PieceKind::King, None, None, FLAG_CASTLING));
-                }
-            }
+    let own_rooks = game_state.pieces[side.index()][PieceKind::Rook.index()];
+    for castle_side in [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN] {
+        if (game_state.castling_rights & castling_right_flag(side, castle_side)) == 0 {
+            continue;
         }
-        Color::Dark => {
-            if king_from == 60 && (game_state.castling_rights & CASTLE_DARK_KINGSIDE) != 0 {
-                let empty = (1u64 << 61) | (1u64 << 62);
-                if (game_state.occupancy_all & empty) == 0
-                    && !is_square_attacked(game_state, 61, enemy)
-                    && !is_square_attacked(game_state, 62, enemy)
-                {
-                    out.push(build_move(
-                        60,
-                        62,
-                        PieceKind::King,
-                        None,
-                        None,
-                        FLAG_CASTLING,
-                    ));
-                }
-            }
-            if king_from == 60 && (game_state.castling_rights & CASTLE_DARK_QUEENSIDE) != 0 {
-                let empty = (1u64 << 57) | (1u64 << 58) | (1u64 << 59);
-                if (game_state.occupancy_all & empty) == 0
-                    && !is_square_attacked(game_state, 59, enemy)
-                    && !is_square_attacked(game_state, 58, enemy)
-                {
-                    out.push(build_move(
-                        60,
-                        58,
-                        PieceKind::King,
-                        None,
-                        None,
-                        FLAG_CASTLING,
-                    ));
-                }
+        let rook_from = base + game_state.castling_rook_files[side.index()][castle_side];
+        if (own_rooks & (1u64 << rook_from)) == 0 {
+            continue;
+        }
+        let (king_to, rook_to) = if castle_side == CASTLE_SIDE_KING {
+            (base + 6, base + 5)
+        } else {
+            (base + 2, base + 3)
+        };
+
+        let castling_pieces = (1u64 << king_from) | (1u64 << rook_from);
+        let king_path = squares_between(king_from, king_to) | (1u64 << king_to);
+        let rook_path = squares_between(rook_from, rook_to) | (1u64 << rook_to);
+        if (game_state.occupancy_all & (king_path | rook_path) & !castling_pieces) != 0 {
+            continue;
+        }
+
+        let occupancy = game_state.occupancy_all & !(1u64 << rook_from);
+        // Includes the destination even when the king does not move.
+        let mut path = king_path;
+        let mut safe = true;
+        while path != 0 {
+            let sq = path.trailing_zeros() as SquareIndex;
+            if attackers_bitboard(game_state, sq, enemy, occupancy) != 0 {
+                safe = false;
+                break;
             }
+            path &= path - 1;
+        }
+        if safe {
+            out.push(build_move(
+                king_from,
+                king_to,
+                PieceKind::King,
+                None,
+                None,
+                FLAG_CASTLING,
+            ));
         }
     }
 }
@@ -6,9 +6,10 @@
 use crate::game_state::{chess_types::*, game_state::GameState};
 use crate::move_generation::legal_move_apply::build_move;
 use crate::move_generation::legal_move_shared::enemy_piece_on;
+use crate::move_generation::move_list::MoveList;
 use crate::moves::move_descriptions::{FLAG_CAPTURE, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT};
 
-pub fn generate_pawn_moves(game_state: &GameState, out: &mut Vec<u64>) {
+pub fn generate_pawn_moves(game_state: &GameState, out: &mut MoveList) {
     let side = game_state.side_to_move;
     let our_pawns = game_state.pieces[side.index()][PieceKind::Pawn.index()];
     let our_occ = game_state.occupancy_by_color[side.index()];
@@ -17,7 +18,7 @@ pub fn generate_pawn_moves(game_state: &GameState, out: &mut Vec<u64>) {
 
     let mut pawns = our_pawns;
     while pawns != 0 {
-        let from = pawns.trailing_zeros() as Square;
+        let from = pawns.trailing_zeros() as SquareIndex;
         let file = from % 8;
         let rank = from / 8;
 
@@ -0,0 +1,284 @@
+//! Single-move legality checks.
+//!
+//! Validates one packed move description against the current position without
+//! generating the full move list, so TT, killer and countermove candidates
+//! (including hash-collision garbage) can be screened cheaply.
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::move_generation::legal_move_apply::build_move;
+use crate::move_generation::legal_move_checks::attackers_bitboard;
+use crate::move_generation::legal_move_shared::enemy_piece_on;
+use crate::move_generation::legal_moves_king::generate_castling_moves;
+use crate::move_generation::move_list::MoveList;
+use crate::moves::bishop_moves::bishop_attacks;
+use crate::moves::king_moves::king_attacks;
+use crate::moves::knight_moves::knight_attacks;
+use crate::moves::move_descriptions::{
+    move_from, move_moved_piece_code, move_promotion_piece_code, move_to, piece_kind_from_code,
+    FLAG_CAPTURE, FLAG_CASTLING, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT,
+};
+use crate::moves::pawn_moves::pawn_attacks;
+use crate::moves::rook_moves::rook_attacks;
+
+/// Bits above the highest flag; generated moves never set them.
+const UNUSED_BITS: u64 = !((FLAG_CASTLING << 1) - 1);
+
+/// Whether the pseudo-legal generators would emit exactly `move_description`.
+///
+/// Every field must match: moved and captured piece codes, promotion piece and
+/// flags. Castling is only accepted when fully legal, as in `generate_king_moves`.
+pub fn is_pseudo_legal(game_state: &GameState, move_description: u64) -> bool {
+    expected_description(game_state, move_description) == Some(move_description)
+}
+
+/// Whether `move_description` is one of the legal moves of the current position.
+pub fn is_legal(game_state: &GameState, move_description: u64) -> bool {
+    is_pseudo_legal(game_state, move_description) && leaves_king_safe(game_state, move_description)
+}
+
+/// Rebuild the move the generators would emit for this from/to/piece/promotion,
+/// or `None` when no such pseudo-legal move exists.
+fn expected_description(game_state: &GameState, move_description: u64) -> Option<u64> {
+    if move_description & UNUSED_BITS != 0 {
+        return None;
+    }
+    let side = game_state.side_to_move;
+    let from = move_from(move_description);
+    let to = move_to(move_description);
+    let piece = piece_kind_from_code(move_moved_piece_code(move_description))?;
+    if game_state.pieces[side.index()][piece.index()] & (1u64 << from) == 0 {
+        return None;
+    }
+
+    if move_description & FLAG_CASTLING != 0 {
+        if piece != PieceKind::King {
+            return None;
+        }
+        let mut castles = MoveList::new();
+        generate_castling_moves(game_state, &mut castles, from);
+        return castles
+            .contains(&move_description)
+            .then_some(move_description);
+    }
+
+    let to_mask = 1u64 << to;
+    if game_state.occupancy_by_color[side.index()] & to_mask != 0 {
+        return None;
+    }
+    let captured = if game_state.occupancy_by_color[side.opposite().index()] & to_mask != 0 {
+        enemy_piece_on(game_state, to)
+    } else {
+        None
+    };
+    let capture_flag = if captured.is_some() { FLAG_CAPTURE } else { 0 };
+
+    let occ = game_state.occupancy_all;
+    let reachable = match piece {
+        PieceKind::Pawn => return expected_pawn_move(game_state, move_description, captured),
+        PieceKind::Knight => knight_attacks(from),
+        PieceKind::Bishop => bishop_attacks(from, occ),
+        PieceKind::Rook => rook_attacks(from, occ),
+        PieceKind::Queen => bishop_attacks(from, occ) | rook_attacks(from, occ),
+        PieceKind::King => king_attacks(from),
+    };
+    (reachable & to_mask != 0).then(|| build_move(from, to, piece, captured, None, capture_flag))
+}
+
+fn expected_pawn_move(
+    game_state: &GameState,
+    move_description: u64,
+    captured: Option<PieceKind>,
+) -> Option<u64> {
+    let side = game_state.side_to_move;
+    let from = move_from(move_description);
+    let to = move_to(move_description);
+    let empty = !game_state.occupancy_all;
+    let (promotion_rank, start_rank, forward) = if side == Color::Light {
+        (7, 1, 8i8)
+    } else {
+        (0, 6, -8i8)
+    };
+
+    let promotion = if to / 8 == promotion_rank {
+        match piece_kind_from_code(move_promotion_piece_code(move_description))? {
+            PieceKind::Pawn | PieceKind::King => return None,
+            promo => Some(promo),
+        }
+    } else {
+        None
+    };
+
+    let one_step = from as i8 + forward;
+    let two_step = one_step + forward;
+    let to_mask = 1u64 << to;
+    if pawn_attacks(side, from) & to_mask != 0 {
+        if captured.is_some() {
+            return Some(build_move(
+                from,
+                to,
+                PieceKind::Pawn,
+                captured,
+                promotion,
+                FLAG_CAPTURE,
+            ));
+        }
+        if game_state.en_passant_square == Some(to) {
+            return Some(build_move(
+                from,
+                to,
+                PieceKind::Pawn,
+                Some(PieceKind::Pawn),
+                None,
+                FLAG_CAPTURE | FLAG_EN_PASSANT,
+            ));
+        }
+        return None;
+    }
+    if captured.is_some() || to_mask & empty == 0 {
+        return None;
+    }
+    if i8::try_from(to).ok() == Some(one_step) {
+        return Some(build_move(from, to, PieceKind::Pawn, None, promotion, 0));
+    }
+    if from / 8 == start_rank
+        && i8::try_from(to).ok() == Some(two_step)
+        && (1u64 << one_step) & empty != 0
+    {
+        return Some(build_move(
+            from,
+            to,
+            PieceKind::Pawn,
+            None,
+            None,
+            FLAG_DOUBLE_PAWN_PUSH,
+        ));
+    }
+    None
+}
+
+/// Own king is not attacked once the (pseudo-legal) move is played.
+fn leaves_king_safe(game_state: &GameState, move_description: u64) -> bool {
+    if move_description & FLAG_CASTLING != 0 {
+        // Castling is only pseudo-legal when already fully legal.
+        return true;
+    }
+    let side = game_state.side_to_move;
+    let enemy = side.opposite();
+    let from = move_from(move_description);
+    let to = move_to(move_description);
+    let king_bb = game_state.pieces[side.index()][PieceKind::King.index()];
+    if king_bb == 0 {
+        return true;
+    }
+
+    // Squares whose enemy occupant is gone after the move.
+    let mut removed = 1u64 << to;
+    if move_description & FLAG_EN_PASSANT != 0 {
+        removed |= if side == Color::Light {
+            1u64 << (to - 8)
+        } else {
+            1u64 << (to + 8)
+        };
+    }
+    let occ_after = (game_state.occupancy_all & !(1u64 << from) & !removed) | (1u64 << to);
+    let king_sq = if king_bb == 1u64 << from {
+        to
+    } else {
+        king_bb.trailing_zeros() as SquareIndex
+    };
+    attackers_bitboard(game_state, king_sq, enemy, occ_after) & !removed == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_legal, is_pseudo_legal};
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_apply::apply_move;
+    use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+    use crate::move_generation::legal_moves_bishop::generate_bishop_moves;
+    use crate::move_generation::legal_moves_king::generate_king_moves;
+    use crate::move_generation::legal_moves_knight::generate_knight_moves;
+    use crate::move_generation::legal_moves_pawn::generate_pawn_moves;
+    use crate::move_generation::legal_moves_queen::generate_queen_moves;
+    use crate::move_generation::legal_moves_rook::generate_rook_moves;
+    use crate::move_generation::move_list::MoveList;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn pseudo_legal_moves(game_state: &GameState) -> MoveList {
+        let mut moves = MoveList::new();
+        generate_pawn_moves(game_state, &mut moves);
+        generate_knight_moves(game_state, &mut moves);
+        generate_bishop_moves(game_state, &mut moves);
+        generate_rook_moves(game_state, &mut moves);
+        generate_queen_moves(game_state, &mut moves);
+        generate_king_moves(game_state, &mut moves);
+        moves
+    }
+
+    #[test]
+    fn accepts_generated_moves_and_rejects_foreign_ones() {
+        let game = GameState::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+        let legal = generate_legal_move_descriptions_in_place(&mut game.clone()).unwrap();
+        assert!(legal.iter().all(|&mv| is_legal(&game, mv)));
+
+        let after = apply_move(&game, legal[0]).unwrap();
+        for mv in generate_legal_move_descriptions_in_place(&mut after.clone()).unwrap() {
+            assert_eq!(is_legal(&game, mv), legal.contains(&mv), "{mv:#x}");
+        }
+        assert!(!is_pseudo_legal(&game, 0));
+        assert!(!is_legal(&game, legal[0] | (1u64 << 40)));
+    }
+
+    #[test]
+    fn randomized_positions_match_full_generation() {
+        let mut rng = StdRng::seed_from_u64(0x5EED_1E6A);
+        let starts = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+            "1r2k1r1/8/8/8/8/8/8/1R2K1R1 w GBgb - 0 1",
+        ];
+        for fen in starts {
+            let mut game = GameState::from_fen(fen).unwrap();
+            // Moves seen in earlier positions make realistic near-miss candidates.
+            let mut foreign = Vec::<u64>::new();
+            for _ in 0..60 {
+                let legal = generate_legal_move_descriptions_in_place(&mut game.clone()).unwrap();
+                let pseudo = pseudo_legal_moves(&game);
+                if legal.is_empty() {
+                    break;
+                }
+
+                let mut candidates = pseudo.to_vec();
+                candidates.extend(foreign.iter().copied());
+                for _ in 0..200 {
+                    candidates.push(rng.random::<u64>() & ((1u64 << 25) - 1));
+                    let base = legal[rng.random_range(0..legal.len())];
+                    candidates.push(base ^ (1u64 << rng.random_range(0..25)));
+                }
+                for mv in candidates {
+                    assert_eq!(
+                        is_pseudo_legal(&game, mv),
+                        pseudo.contains(&mv),
+                        "pseudo {mv:#x} in {}",
+                        game.get_fen()
+                    );
+                    assert_eq!(
+                        is_legal(&game, mv),
+                        legal.contains(&mv),
+                        "legal {mv:#x} in {}",
+                        game.get_fen()
+                    );
+                }
+
+                foreign.extend(legal.iter().copied());
+                let mv = legal[rng.random_range(0..legal.len())];
+                game = apply_move(&game, mv).unwrap();
+            }
+        }
+    }
+}
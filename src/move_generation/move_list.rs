@@ -0,0 +1,297 @@
+//! Fixed-capacity, stack-allocated move list.
+//!
+//! Move generation writes into `MoveList` instead of a heap `Vec<u64>`, so
+//! search and perft nodes generate moves without allocating. Each slot also
+//! carries an ordering score for pick-best move ordering.
+
+use std::ops::{Deref, DerefMut};
+
+/// Inline capacity; the maximum legal move count of any chess position is 218.
+pub const MAX_MOVES: usize = 256;
+
+#[derive(Clone)]
+pub struct MoveList {
+    moves: [u64; MAX_MOVES],
+    scores: [i32; MAX_MOVES],
+    len: usize,
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl MoveList {
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            moves: [0; MAX_MOVES],
+            scores: [0; MAX_MOVES],
+            len: 0,
+        }
+    }
+
+    /// Append a move with a zero ordering score.
+    #[inline]
+    pub fn push(&mut self, move_description: u64) {
+        self.push_scored(move_description, 0);
+    }
+
+    /// Append a move together with its ordering score.
+    ///
+    /// Legal chess never exceeds `MAX_MOVES`, but the lenient FEN parser
+    /// accepts illegal material (e.g. ten queens), so moves past capacity are
+    /// dropped instead of indexing out of bounds. Debug builds assert.
+    #[inline]
+    pub fn push_scored(&mut self, move_description: u64, score: i32) {
+        debug_assert!(
+            self.len < MAX_MOVES,
+            "MoveList overflow past {MAX_MOVES} moves"
+        );
+        if self.len == MAX_MOVES {
+            return;
+        }
+        self.moves[self.len] = move_description;
+        self.scores[self.len] = score;
+        self.len += 1;
+    }
+
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[u64] {
+        &self.moves[..self.len]
+    }
+
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [u64] {
+        &mut self.moves[..self.len]
+    }
+
+    /// Ordering scores, parallel to `as_slice()`.
+    #[inline]
+    pub fn scores(&self) -> &[i32] {
+        &self.scores[..self.len]
+    }
+
+    #[inline]
+    pub fn score(&self, index: usize) -> i32 {
+        self.scores()[index]
+    }
+
+    #[inline]
+    pub fn set_score(&mut self, index: usize, score: i32) {
+        assert!(index < self.len, "score index out of bounds");
+        self.scores[index] = score;
+    }
+
+    /// Swap two entries together with their scores.
+    #[inline]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.as_mut_slice().swap(a, b);
+        self.scores[..self.len].swap(a, b);
+    }
+
+    /// Keep only moves for which `keep` returns true, preserving order.
+    pub fn retain(&mut self, mut keep: impl FnMut(u64) -> bool) {
+        let mut write = 0usize;
+        for read in 0..self.len {
+            if keep(self.moves[read]) {
+                self.moves[write] = self.moves[read];
+                self.scores[write] = self.scores[read];
+                write += 1;
+            }
+        }
+        self.len = write;
+    }
+
+    /// Selection-sort step: move the highest-scored entry at or after `start`
+    /// into `start` and return it.
+    pub fn pick_best(&mut self, start: usize) -> Option<u64> {
+        if start >= self.len {
+            return None;
+        }
+        let mut best = start;
+        for i in (start + 1)..self.len {
+            if self.scores[i] > self.scores[best] {
+                best = i;
+            }
+        }
+        self.swap(start, best);
+        Some(self.moves[start])
+    }
+
+    /// Stable sort by descending score.
+    pub fn sort_by_score_desc(&mut self) {
+        let mut pairs = [(0u64, 0i32); MAX_MOVES];
+        for (i, pair) in pairs[..self.len].iter_mut().enumerate() {
+            *pair = (self.moves[i], self.scores[i]);
+        }
+        pairs[..self.len].sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        for (i, (mv, score)) in pairs[..self.len].iter().enumerate() {
+            self.moves[i] = *mv;
+            self.scores[i] = *score;
+        }
+    }
+
+    /// Copy the moves into a heap `Vec` for APIs that still return vectors.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u64> {
+        self.as_slice().to_vec()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [u64];
+
+    #[inline]
+    fn deref(&self) -> &[u64] {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for MoveList {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [u64] {
+        self.as_mut_slice()
+    }
+}
+
+impl From<MoveList> for Vec<u64> {
+    fn from(list: MoveList) -> Self {
+        list.to_vec()
+    }
+}
+
+impl From<&[u64]> for MoveList {
+    fn from(moves: &[u64]) -> Self {
+        moves.iter().copied().collect()
+    }
+}
+
+impl Extend<u64> for MoveList {
+    fn extend<T: IntoIterator<Item = u64>>(&mut self, iter: T) {
+        for mv in iter {
+            self.push(mv);
+        }
+    }
+}
+
+impl FromIterator<u64> for MoveList {
+    fn from_iter<T: IntoIterator<Item = u64>>(iter: T) -> Self {
+        let mut list = MoveList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a u64;
+    type IntoIter = std::slice::Iter<'a, u64>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+/// Owning iterator over the moves of a `MoveList`.
+pub struct MoveListIntoIter {
+    list: MoveList,
+    index: usize,
+}
+
+impl Iterator for MoveListIntoIter {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        let mv = self.list.as_slice().get(self.index).copied()?;
+        self.index += 1;
+        Some(mv)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for MoveListIntoIter {}
+
+impl IntoIterator for MoveList {
+    type Item = u64;
+    type IntoIter = MoveListIntoIter;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        MoveListIntoIter {
+            list: self,
+            index: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MoveList, MAX_MOVES};
+
+    #[test]
+    fn push_retain_and_iterate_keep_scores_aligned() {
+        let mut list = MoveList::new();
+        for mv in 1..=6u64 {
+            list.push_scored(mv, (mv as i32) * 10);
+        }
+        list.retain(|mv| mv % 2 == 0);
+        assert_eq!(list.as_slice(), &[2, 4, 6]);
+        assert_eq!(list.scores(), &[20, 40, 60]);
+        assert_eq!(list.iter().copied().sum::<u64>(), 12);
+        assert_eq!(list.clone().into_iter().collect::<Vec<_>>(), vec![2, 4, 6]);
+        assert_eq!(Vec::from(list), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn pick_best_and_sort_order_by_descending_score() {
+        let mut list = MoveList::new();
+        list.push_scored(7, 5);
+        list.push_scored(8, 50);
+        list.push_scored(9, 20);
+        list.push_scored(10, 50);
+
+        let mut picked = list.clone();
+        let order = (0..picked.len())
+            .map(|i| picked.pick_best(i).expect("entry"))
+            .collect::<Vec<_>>();
+        assert_eq!(order, vec![8, 10, 9, 7]);
+        assert_eq!(picked.pick_best(4), None);
+
+        list.sort_by_score_desc();
+        assert_eq!(list.as_slice(), &[8, 10, 9, 7]);
+        assert_eq!(list.scores(), &[50, 50, 20, 5]);
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "MoveList overflow"))]
+    fn push_past_capacity_drops_the_overflow() {
+        let mut list = (0..MAX_MOVES as u64).collect::<MoveList>();
+        list.push(u64::MAX);
+        assert_eq!(list.len(), MAX_MOVES);
+        assert_eq!(list.last().copied(), Some(MAX_MOVES as u64 - 1));
+    }
+}
@@ -1,19 +1,24 @@
 //! Perft validation and benchmarking counters.
 //!
 //! Recursively explores legal move trees to verify correctness and collect
-//! tactical event counts (captures, checks, promotions, etc.).
+//! tactical event counts (captures, checks, promotions, etc.). Node-only runs
+//! count leaves in bulk at depth 1 and can share a `PerftTable`.
 
 use std::sync::Arc;
 use std::thread;
 
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
-use crate::move_generation::legal_move_generator::LegalMoveGenerator;
+use crate::move_generation::legal_move_apply::{make_move_in_place, unmake_move_in_place};
+use crate::move_generation::legal_move_generator::{generate_legal_move_list, LegalMoveGenerator};
 use crate::move_generation::move_generator::{
     GeneratedMove, MoveGenResult, MoveGenerationError, MoveGenerator,
 };
+use crate::move_generation::perft_table::PerftTable;
 use crate::moves::move_descriptions::{
     move_promotion_piece_code, FLAG_CAPTURE, FLAG_CASTLING, FLAG_EN_PASSANT, NO_PIECE_CODE,
 };
+use crate::utils::long_algebraic::move_description_to_long_algebraic;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct PerftCounts {
@@ -55,6 +60,125 @@ pub fn perft_legal(game_state: &GameState, depth: u8) -> MoveGenResult<PerftCoun
     perft(&generator, game_state, depth)
 }
 
+/// Node-only perft using make/unmake and stack-allocated move lists.
+///
+/// Skips the per-move state clones and annotations of `perft`, so it measures
+/// raw generation plus make/unmake throughput.
+pub fn perft_nodes(game_state: &mut GameState, depth: u8) -> MoveGenResult<usize> {
+    if depth == 0 {
+        return Ok(1);
+    }
+
+    let moves = generate_legal_move_list(game_state);
+    if depth == 1 {
+        return Ok(moves.len());
+    }
+
+    let mut nodes = 0usize;
+    for mv in &moves {
+        make_move_in_place(game_state, *mv).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
+        })?;
+        let child = perft_nodes(game_state, depth - 1);
+        unmake_move_in_place(game_state).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
+        })?;
+        nodes += child?;
+    }
+    Ok(nodes)
+}
+
+/// `perft_nodes` with subtree counts cached in `table` by key and depth.
+///
+/// Depth-1 nodes are counted in bulk and never stored; the table only pays
+/// off for subtrees of depth 2 and more.
+pub fn perft_nodes_hashed(
+    game_state: &mut GameState,
+    depth: u8,
+    table: &PerftTable,
+) -> MoveGenResult<usize> {
+    if depth <= 1 {
+        return perft_nodes(game_state, depth);
+    }
+    if let Some(nodes) = table.probe(game_state.zobrist_key, depth) {
+        return Ok(nodes);
+    }
+
+    let moves = generate_legal_move_list(game_state);
+    let mut nodes = 0usize;
+    for mv in &moves {
+        make_move_in_place(game_state, *mv).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
+        })?;
+        let child = perft_nodes_hashed(game_state, depth - 1, table);
+        unmake_move_in_place(game_state).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
+        })?;
+        nodes += child?;
+    }
+    table.store(game_state.zobrist_key, depth, nodes);
+    Ok(nodes)
+}
+
+/// How `perft_multi_threaded_with` counts below each root move.
+#[derive(Debug, Clone, Default)]
+pub enum PerftMode {
+    /// Every `PerftCounts` field, via `MoveGenerator` child states.
+    #[default]
+    Detailed,
+    /// Nodes only (other counters stay zero): make/unmake with bulk leaf
+    /// counting, plus a table shared by all workers when given.
+    Bulk { table: Option<Arc<PerftTable>> },
+}
+
+/// Leaf count below one root move, as printed by `perft_divide` tooling.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftDivideEntry {
+    pub mv: Move,
+    /// Root move in UCI long algebraic notation.
+    pub uci: String,
+    pub nodes: usize,
+}
+
+/// Per-root-move node counts at `depth`, in generation order.
+pub fn perft_divide(game_state: &GameState, depth: u8) -> MoveGenResult<Vec<PerftDivideEntry>> {
+    if depth == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut work = game_state.clone();
+    let root_moves = generate_legal_move_list(&work);
+    let mut entries = Vec::with_capacity(root_moves.len());
+    for mv in root_moves {
+        let uci = move_description_to_long_algebraic(mv, &work)
+            .map_err(MoveGenerationError::InvalidState)?;
+        make_move_in_place(&mut work, mv).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
+        })?;
+        let nodes = perft_nodes(&mut work, depth - 1);
+        unmake_move_in_place(&mut work).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
+        })?;
+        entries.push(PerftDivideEntry {
+            mv: Move::from_raw(mv),
+            uci,
+            nodes: nodes?,
+        });
+    }
+    Ok(entries)
+}
+
+/// Stockfish-style divide listing: `e2e4: 20` lines then `Nodes searched: N`.
+pub fn format_perft_divide(entries: &[PerftDivideEntry]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        out.push_str(&format!("{}: {}\n", entry.uci, entry.nodes));
+    }
+    let total = entries.iter().map(|e| e.nodes).sum::<usize>();
+    out.push_str(&format!("\nNodes searched: {}\n", total));
+    out
+}
+
 pub fn perft_single_thread<G: MoveGenerator>(
     generator: &G,
     game_state: &GameState,
@@ -81,6 +205,16 @@ pub fn perft_multi_threaded(
     generator: Arc<dyn MoveGenerator>,
     game_state: &GameState,
     depth: u8,
+) -> MoveGenResult<PerftCounts> {
+    perft_multi_threaded_with(generator, game_state, depth, &PerftMode::Detailed)
+}
+
+/// One worker thread per root move, counting as selected by `mode`.
+pub fn perft_multi_threaded_with(
+    generator: Arc<dyn MoveGenerator>,
+    game_state: &GameState,
+    depth: u8,
+    mode: &PerftMode,
 ) -> MoveGenResult<PerftCounts> {
     if depth == 0 {
         return Ok(PerftCounts {
@@ -94,9 +228,22 @@ pub fn perft_multi_threaded(
 
     for mv in root_moves {
         let generator_ref = Arc::clone(&generator);
+        let mode = mode.clone();
         handles.push(thread::spawn(move || {
             let mut local = PerftCounts::default();
-            let result = perft_recurse(generator_ref.as_ref(), &mv, depth, 1, &mut local);
+            let result = match mode {
+                PerftMode::Detailed => {
+                    perft_recurse(generator_ref.as_ref(), &mv, depth, 1, &mut local)
+                }
+                PerftMode::Bulk { table } => {
+                    let mut child = mv.game_after_move;
+                    let nodes = match table {
+                        Some(table) => perft_nodes_hashed(&mut child, depth - 1, &table),
+                        None => perft_nodes(&mut child, depth - 1),
+                    };
+                    nodes.map(|nodes| local.nodes = nodes)
+                }
+            };
             (result, local)
         }));
     }
@@ -164,6 +311,9 @@ fn perft_recurse(
 mod tests {
     use crate::game_state::chess_types::{Color, PieceKind};
     use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_pin_mask::{
+        FastPinMaskMoveGenerator, PinMaskMoveGenerator,
+    };
     use crate::move_generation::move_generator::MoveAnnotations;
     use crate::moves::move_descriptions::{
         pack_move_description, FLAG_CAPTURE, FLAG_CASTLING, FLAG_DOUBLE_PAWN_PUSH, FLAG_EN_PASSANT,
@@ -171,6 +321,14 @@ mod tests {
 
     use super::*;
 
+    /// Run the filtered reference and the pin-mask generator; both must agree.
+    fn perft_with_both_generators(game: &GameState, depth: u8) -> PerftCounts {
+        let reference = perft_legal(game, depth).expect("perft should run");
+        let pin_mask = perft(&PinMaskMoveGenerator, game, depth).expect("perft should run");
+        assert_eq!(pin_mask, reference, "pin-mask mismatch at depth {depth}");
+        reference
+    }
+
     struct MockMoveGenerator;
 
     impl MoveGenerator for MockMoveGenerator {
@@ -344,6 +502,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn perft_nodes_matches_reference_counts() {
+        let cases: [(&str, [usize; 3]); 3] = [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                [20, 400, 8902],
+            ),
+            (
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+                [48, 2039, 97862],
+            ),
+            ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", [14, 191, 2812]),
+        ];
+        for (fen, expected) in cases {
+            let mut game = GameState::from_fen(fen).expect("FEN should parse");
+            let before = game.get_fen();
+            for (idx, nodes) in expected.iter().enumerate() {
+                let depth = (idx + 1) as u8;
+                assert_eq!(perft_nodes(&mut game, depth).expect("perft"), *nodes);
+            }
+            assert_eq!(game.get_fen(), before);
+        }
+    }
+
+    #[test]
+    fn perft_divide_sums_to_perft_and_names_moves_in_uci() {
+        let game = GameState::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .expect("FEN should parse");
+        let entries = perft_divide(&game, 2).expect("divide should run");
+        assert_eq!(entries.len(), 48);
+        assert_eq!(entries.iter().map(|e| e.nodes).sum::<usize>(), 2039);
+        let castle = entries
+            .iter()
+            .find(|e| e.uci == "e1g1")
+            .expect("castling root move");
+        assert_eq!(castle.nodes, 43);
+        assert!(castle.mv.is_castle());
+
+        let listing = format_perft_divide(&entries);
+        assert!(listing.contains("e1g1: 43\n"));
+        assert!(listing.ends_with("\nNodes searched: 2039\n"));
+    }
+
+    #[test]
+    fn perft_chess960_reference_positions() {
+        let positions: [(&str, [usize; 4]); 6] = [
+            (
+                "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+                [21, 528, 12189, 326672],
+            ),
+            (
+                "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+                [20, 479, 10471, 273318],
+            ),
+            (
+                "qbbnnrkr/2pp2pp/p7/1p2pp2/8/P3PP2/1PPP1KPP/QBBNNR1R w hf - 0 9",
+                [22, 593, 13440, 382958],
+            ),
+            (
+                "1nbbnrkr/p1p1ppp1/3p4/1p3P1p/3Pq2P/8/PPP1P1P1/QNBBNRKR w HFhf - 0 9",
+                [28, 1120, 31058, 1171749],
+            ),
+            (
+                "q1bnrkr1/ppppp2p/2n2p2/4b1p1/2NP4/8/PPP1PPPP/QNB1RRKB w ge - 1 9",
+                [30, 860, 24566, 732757],
+            ),
+            (
+                "qbn1brkr/ppp1p1p1/2n4p/3p1p2/P7/6PP/QPPPPP2/1BNNBRKR w HFhf - 0 9",
+                [25, 635, 17054, 465806],
+            ),
+        ];
+
+        for (fen, expected) in positions {
+            let game = GameState::from_fen(fen).expect("Chess960 FEN should parse");
+            assert!(game.chess960, "{fen} should be detected as Chess960");
+            for depth in 1..=3u8 {
+                let counts = perft_with_both_generators(&game, depth);
+                assert_eq!(
+                    counts.nodes,
+                    expected[depth as usize - 1],
+                    "{fen} depth {depth}"
+                );
+            }
+        }
+
+        let game = GameState::from_fen(positions[0].0).expect("Chess960 FEN should parse");
+        let counts = perft(&FastPinMaskMoveGenerator, &game, 4).expect("perft should run");
+        assert_eq!(counts.nodes, positions[0].1[3]);
+    }
+
     #[test]
     fn perft_start_position_nodes_depth_1_to_3() {
         let game = GameState::new_game();
@@ -411,7 +661,7 @@ mod tests {
 
         for (idx, target) in results.iter().enumerate() {
             let depth = (idx + 1) as u8;
-            let count = perft_legal(&game, depth).expect("perft should run");
+            let count = perft_with_both_generators(&game, depth);
             assert_eq!(count, *target, "mismatch at depth {depth}");
         }
     }
@@ -460,7 +710,7 @@ mod tests {
 
         for (idx, target) in results.iter().enumerate() {
             let depth = (idx + 1) as u8;
-            let count = perft_legal(&game, depth).expect("perft should run");
+            let count = perft_with_both_generators(&game, depth);
             assert_eq!(count, *target, "mismatch at depth {depth}");
         }
     }
@@ -529,7 +779,7 @@ mod tests {
 
         for (idx, target) in results.iter().enumerate() {
             let depth = (idx + 1) as u8;
-            let count = perft_legal(&game, depth).expect("perft should run");
+            let count = perft_with_both_generators(&game, depth);
             assert_eq!(count, *target, "mismatch at depth {depth}");
         }
     }
@@ -588,7 +838,7 @@ mod tests {
 
         for (idx, target) in results.iter().enumerate() {
             let depth = (idx + 1) as u8;
-            let count = perft_legal(&game, depth).expect("perft should run");
+            let count = perft_with_both_generators(&game, depth);
             assert_eq!(count, *target, "mismatch at depth {depth}");
         }
     }
@@ -601,7 +851,7 @@ mod tests {
 
         for (idx, target_nodes) in results.iter().enumerate() {
             let depth = (idx + 1) as u8;
-            let count = perft_legal(&game, depth).expect("perft should run");
+            let count = perft_with_both_generators(&game, depth);
             assert_eq!(count.nodes, *target_nodes, "node mismatch at depth {depth}");
         }
     }
@@ -616,8 +866,55 @@ mod tests {
 
         for (idx, target_nodes) in results.iter().enumerate() {
             let depth = (idx + 1) as u8;
-            let count = perft_legal(&game, depth).expect("perft should run");
+            let count = perft_with_both_generators(&game, depth);
             assert_eq!(count.nodes, *target_nodes, "node mismatch at depth {depth}");
         }
     }
+
+    #[test]
+    fn bulk_and_hashed_modes_match_reference_node_counts() {
+        let table = Arc::new(PerftTable::new_with_mb(4));
+        for (fen, depth, expected) in [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                4u8,
+                197_281usize,
+            ),
+            (
+                "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 0",
+                3,
+                97_862,
+            ),
+        ] {
+            let game = GameState::from_fen(fen).expect("FEN should parse");
+            let hashed = perft_nodes_hashed(&mut game.clone(), depth, &table).expect("perft");
+            assert_eq!(hashed, expected, "hashed {fen}");
+            // A warm table must give the same answer.
+            let warm = perft_nodes_hashed(&mut game.clone(), depth, &table).expect("perft");
+            assert_eq!(warm, expected, "warm {fen}");
+
+            for mode in [
+                PerftMode::Bulk { table: None },
+                PerftMode::Bulk {
+                    table: Some(Arc::clone(&table)),
+                },
+            ] {
+                let counts = perft_multi_threaded_with(
+                    Arc::new(FastPinMaskMoveGenerator),
+                    &game,
+                    depth,
+                    &mode,
+                )
+                .expect("perft should run");
+                assert_eq!(
+                    counts,
+                    PerftCounts {
+                        nodes: expected,
+                        ..PerftCounts::default()
+                    },
+                    "{mode:?} {fen}"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,149 @@
+//! EPD perft-suite loading and checking.
+//!
+//! Reads `perftsuite.epd`-style lines (`<fen> ;D1 20 ;D2 400 ...`) and runs
+//! each expected depth through `perft_nodes`, reporting any mismatch.
+
+use std::fs;
+
+use crate::game_state::game_state::GameState;
+use crate::move_generation::perft::perft_nodes;
+
+/// One EPD line: a position and its expected leaf counts by depth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftSuiteEntry {
+    pub fen: String,
+    /// `(depth, expected_nodes)` in file order.
+    pub expected: Vec<(u8, usize)>,
+}
+
+/// Outcome of one depth of one suite entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PerftSuiteCheck {
+    pub depth: u8,
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl PerftSuiteCheck {
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// Parse perft-suite text; blank lines and `#` comments are skipped.
+///
+/// Four-field EPD positions get default `0 1` clocks.
+pub fn parse_perft_suite(text: &str) -> Result<Vec<PerftSuiteEntry>, String> {
+    let mut entries = Vec::new();
+    for (line_idx, raw_line) in text.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        entries.push(
+            parse_perft_suite_line(line)
+                .map_err(|e| format!("perft suite line {}: {e}", line_idx + 1))?,
+        );
+    }
+    Ok(entries)
+}
+
+/// Read and parse a perft-suite file.
+pub fn load_perft_suite(path: &str) -> Result<Vec<PerftSuiteEntry>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+    parse_perft_suite(&text)
+}
+
+fn parse_perft_suite_line(line: &str) -> Result<PerftSuiteEntry, String> {
+    let mut fields = line.split(';');
+    let position = fields.next().unwrap_or_default().trim();
+    let fen = match position.split_whitespace().count() {
+        4 => format!("{position} 0 1"),
+        6 => position.to_owned(),
+        n => return Err(format!("expected 4 or 6 FEN fields, found {n}")),
+    };
+
+    let mut expected = Vec::new();
+    for field in fields {
+        let field = field.trim();
+        if field.is_empty() {
+            continue;
+        }
+        let (tag, count) = field
+            .split_once(char::is_whitespace)
+            .ok_or_else(|| format!("malformed depth field '{field}'"))?;
+        let depth = tag
+            .strip_prefix('D')
+            .and_then(|d| d.parse::<u8>().ok())
+            .ok_or_else(|| format!("malformed depth tag '{tag}'"))?;
+        let nodes = count
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("malformed node count '{count}'"))?;
+        expected.push((depth, nodes));
+    }
+    if expected.is_empty() {
+        return Err("no ;D<n> expectations".to_owned());
+    }
+
+    Ok(PerftSuiteEntry { fen, expected })
+}
+
+impl PerftSuiteEntry {
+    /// Run every expected depth up to `max_depth` and report each result.
+    pub fn check(&self, max_depth: u8) -> Result<Vec<PerftSuiteCheck>, String> {
+        let mut game_state = GameState::from_fen(&self.fen)?;
+        let mut checks = Vec::new();
+        for &(depth, expected) in self.expected.iter().filter(|(d, _)| *d <= max_depth) {
+            let actual = perft_nodes(&mut game_state, depth).map_err(|e| e.to_string())?;
+            checks.push(PerftSuiteCheck {
+                depth,
+                expected,
+                actual,
+            });
+        }
+        Ok(checks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_perft_suite;
+
+    const SUITE: &str = "\
+# standard opening checks
+rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - ;D1 20 ;D2 400 ;D3 8902
+8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1 ;D1 14 ;D2 191 ;D3 2812
+
+4k3/8/8/8/8/8/8/4K2R w K - 0 1 ;D1 15 ;D2 66
+";
+
+    #[test]
+    fn parses_epd_lines_and_default_clocks() {
+        let entries = parse_perft_suite(SUITE).expect("suite should parse");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0].fen,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(entries[0].expected, vec![(1, 20), (2, 400), (3, 8902)]);
+        assert!(parse_perft_suite("8/8/8/8 w ;D1 1").is_err());
+        assert!(parse_perft_suite("4k3/8/8/8/8/8/8/4K3 w - - 0 1 ;X1 5").is_err());
+    }
+
+    #[test]
+    fn check_reports_matches_and_mismatches() {
+        let entries = parse_perft_suite(SUITE).expect("suite should parse");
+        for entry in &entries {
+            let checks = entry.check(3).expect("perft should run");
+            assert!(checks.iter().all(|c| c.passed()), "{checks:?}");
+        }
+
+        let wrong = parse_perft_suite("4k3/8/8/8/8/8/8/4K2R w K - 0 1 ;D1 16 ;D4 1")
+            .expect("suite should parse");
+        let checks = wrong[0].check(2).expect("perft should run");
+        assert_eq!(checks.len(), 1);
+        assert!(!checks[0].passed());
+        assert_eq!(checks[0].actual, 15);
+    }
+}
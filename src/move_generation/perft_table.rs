@@ -0,0 +1,96 @@
+//! Perft transposition table.
+//!
+//! Caches subtree node counts by `(zobrist_key, depth)` so transposed
+//! positions in deep perft runs are counted once. Slots are two atomics with
+//! the key folded into the payload (XOR trick), so one table can be shared by
+//! `perft_multi_threaded_with` workers without locks; a torn slot simply fails
+//! the key check.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+const DEPTH_BITS: u32 = 8;
+const DEPTH_MASK: u64 = (1 << DEPTH_BITS) - 1;
+
+#[derive(Debug, Default)]
+struct PerftSlot {
+    check: AtomicU64,
+    data: AtomicU64,
+}
+
+#[derive(Debug)]
+pub struct PerftTable {
+    slots: Vec<PerftSlot>,
+    mask: usize,
+}
+
+impl PerftTable {
+    /// Largest power-of-two slot count that fits in `size_mb` megabytes.
+    pub fn new_with_mb(size_mb: usize) -> Self {
+        let bytes = size_mb.max(1) * 1024 * 1024;
+        let raw = (bytes / std::mem::size_of::<PerftSlot>()).max(1);
+        let count = 1usize << (usize::BITS - 1 - raw.leading_zeros());
+        Self {
+            slots: (0..count).map(|_| PerftSlot::default()).collect(),
+            mask: count - 1,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn clear(&self) {
+        for slot in &self.slots {
+            slot.check.store(0, Ordering::Relaxed);
+            slot.data.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Stored node count for `key` searched to exactly `depth`.
+    #[inline]
+    pub fn probe(&self, key: u64, depth: u8) -> Option<usize> {
+        let slot = &self.slots[self.index(key, depth)];
+        let data = slot.data.load(Ordering::Relaxed);
+        let check = slot.check.load(Ordering::Relaxed);
+        (data != 0 && check ^ data == key && data & DEPTH_MASK == u64::from(depth))
+            .then_some((data >> DEPTH_BITS) as usize)
+    }
+
+    /// Record `nodes` for `key` at `depth`, always replacing the slot.
+    #[inline]
+    pub fn store(&self, key: u64, depth: u8, nodes: usize) {
+        let data = ((nodes as u64) << DEPTH_BITS) | u64::from(depth);
+        let slot = &self.slots[self.index(key, depth)];
+        slot.check.store(key ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    /// Depth is mixed in so one position's counts at several depths coexist.
+    #[inline]
+    fn index(&self, key: u64, depth: u8) -> usize {
+        ((key ^ u64::from(depth).wrapping_mul(0x9E37_79B9_7F4A_7C15)) as usize) & self.mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PerftTable;
+
+    #[test]
+    fn probes_only_the_stored_key_and_depth() {
+        let table = PerftTable::new_with_mb(1);
+        assert!(table.len().is_power_of_two());
+        table.store(0xDEAD_BEEF, 4, 197_281);
+        assert_eq!(table.probe(0xDEAD_BEEF, 4), Some(197_281));
+        assert_eq!(table.probe(0xDEAD_BEEF, 3), None);
+        assert_eq!(table.probe(0xDEAD_BEEE, 4), None);
+        table.clear();
+        assert_eq!(table.probe(0xDEAD_BEEF, 4), None);
+    }
+}
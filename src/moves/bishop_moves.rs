@@ -4,10 +4,18 @@
 //! generation and tactical evaluation. These routines are performance-critical
 //! building blocks for both perft and search.
 
+use crate::moves::magic_bitboards::bishop_attacks_magic;
+
 pub const BISHOP_RAYS: [u64; 64] = generate_bishop_rays();
 
+/// Bishop attacks for `square` under `occupancy` (magic-bitboard lookup).
 #[inline]
 pub fn bishop_attacks(square: u8, occupancy: u64) -> u64 {
+    bishop_attacks_magic(square, occupancy)
+}
+
+/// Reference ray-walking implementation used to build and verify magic tables.
+pub fn bishop_attacks_ray_traced(square: u8, occupancy: u64) -> u64 {
     let sq = square as i32;
     let mut attacks = 0u64;
 
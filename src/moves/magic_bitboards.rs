@@ -0,0 +1,238 @@
+//! Magic-bitboard attack tables for sliding pieces.
+//!
+//! Magic multipliers are searched once from a fixed seed and the resulting
+//! per-square attack tables are cached, so bishop/rook lookups become a mask,
+//! multiply, shift and single table read instead of per-call ray walking.
+
+use std::sync::OnceLock;
+
+use crate::moves::bishop_moves::bishop_attacks_ray_traced;
+use crate::moves::rook_moves::rook_attacks_ray_traced;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct MagicEntry {
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    offset: usize,
+}
+
+impl MagicEntry {
+    #[inline]
+    fn index(&self, occupancy: u64) -> usize {
+        self.offset + (((occupancy & self.mask).wrapping_mul(self.magic)) >> self.shift) as usize
+    }
+}
+
+#[derive(Debug)]
+struct MagicTables {
+    bishop: [MagicEntry; 64],
+    rook: [MagicEntry; 64],
+    attacks: Vec<u64>,
+}
+
+static TABLES: OnceLock<MagicTables> = OnceLock::new();
+
+#[inline]
+fn tables() -> &'static MagicTables {
+    TABLES.get_or_init(build_tables)
+}
+
+/// Force magic table construction.
+///
+/// Lookups initialize lazily on first use; calling this at process start keeps
+/// the one-time build cost out of the first search or perft call.
+pub fn init_magic_tables() {
+    let _ = tables();
+}
+
+/// Bishop attacks for `square` under `occupancy` via magic lookup.
+#[inline]
+pub fn bishop_attacks_magic(square: u8, occupancy: u64) -> u64 {
+    let t = tables();
+    t.attacks[t.bishop[square as usize].index(occupancy)]
+}
+
+/// Rook attacks for `square` under `occupancy` via magic lookup.
+#[inline]
+pub fn rook_attacks_magic(square: u8, occupancy: u64) -> u64 {
+    let t = tables();
+    t.attacks[t.rook[square as usize].index(occupancy)]
+}
+
+const BISHOP_DIRECTIONS: [(i32, i32); 4] = [(1, 1), (-1, 1), (1, -1), (-1, -1)];
+const ROOK_DIRECTIONS: [(i32, i32); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+
+fn build_tables() -> MagicTables {
+    let mut seed: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut attacks = Vec::<u64>::new();
+    let mut bishop = [MagicEntry::default(); 64];
+    let mut rook = [MagicEntry::default(); 64];
+
+    for sq in 0..64u8 {
+        bishop[sq as usize] = find_magic(
+            sq,
+            relevant_mask(sq, &BISHOP_DIRECTIONS),
+            bishop_attacks_ray_traced,
+            &mut attacks,
+            &mut seed,
+        );
+        rook[sq as usize] = find_magic(
+            sq,
+            relevant_mask(sq, &ROOK_DIRECTIONS),
+            rook_attacks_ray_traced,
+            &mut attacks,
+            &mut seed,
+        );
+    }
+
+    MagicTables {
+        bishop,
+        rook,
+        attacks,
+    }
+}
+
+/// Ray squares whose occupancy can change the attack set (board edges excluded).
+fn relevant_mask(square: u8, directions: &[(i32, i32)]) -> u64 {
+    let sq = square as i32;
+    let mut mask = 0u64;
+    for &(file_step, rank_step) in directions {
+        let mut file = (sq % 8) + file_step;
+        let mut rank = (sq / 8) + rank_step;
+        while (0..8).contains(&(file + file_step)) && (0..8).contains(&(rank + rank_step)) {
+            mask |= 1u64 << (rank * 8 + file);
+            file += file_step;
+            rank += rank_step;
+        }
+    }
+    mask
+}
+
+fn find_magic(
+    square: u8,
+    mask: u64,
+    reference: fn(u8, u64) -> u64,
+    attacks: &mut Vec<u64>,
+    seed: &mut u64,
+) -> MagicEntry {
+    let bits = mask.count_ones();
+    let size = 1usize << bits;
+
+    // Enumerate all blocker subsets of the mask (Carry-Rippler).
+    let mut occupancies = Vec::with_capacity(size);
+    let mut reference_attacks = Vec::with_capacity(size);
+    let mut subset = 0u64;
+    loop {
+        occupancies.push(subset);
+        reference_attacks.push(reference(square, subset));
+        subset = subset.wrapping_sub(mask) & mask;
+        if subset == 0 {
+            break;
+        }
+    }
+
+    let shift = 64 - bits;
+    let mut table = vec![0u64; size];
+    let mut epoch = vec![0u32; size];
+    let mut attempt = 0u32;
+
+    loop {
+        // Sparse candidates converge far faster than uniform random values.
+        let magic = next_random_u64(seed) & next_random_u64(seed) & next_random_u64(seed);
+        if (mask.wrapping_mul(magic) & 0xFF00_0000_0000_0000).count_ones() < 6 {
+            continue;
+        }
+
+        attempt += 1;
+        let mut ok = true;
+        for (occ, attack) in occupancies.iter().zip(&reference_attacks) {
+            let idx = (occ.wrapping_mul(magic) >> shift) as usize;
+            if epoch[idx] != attempt {
+                epoch[idx] = attempt;
+                table[idx] = *attack;
+            } else if table[idx] != *attack {
+                ok = false;
+                break;
+            }
+        }
+
+        if ok {
+            let offset = attacks.len();
+            attacks.extend_from_slice(&table);
+            return MagicEntry {
+                mask,
+                magic,
+                shift,
+                offset,
+            };
+        }
+    }
+}
+
+#[inline]
+fn next_random_u64(state: &mut u64) -> u64 {
+    // splitmix64
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bishop_attacks_magic, next_random_u64, relevant_mask, rook_attacks_magic};
+    use super::{BISHOP_DIRECTIONS, ROOK_DIRECTIONS};
+    use crate::moves::bishop_moves::bishop_attacks_ray_traced;
+    use crate::moves::rook_moves::rook_attacks_ray_traced;
+
+    #[test]
+    fn relevant_masks_have_expected_bit_counts() {
+        // a1 rook: 6 + 6 relevant squares, d4 bishop: 9 relevant squares.
+        assert_eq!(relevant_mask(0, &ROOK_DIRECTIONS).count_ones(), 12);
+        assert_eq!(relevant_mask(27, &BISHOP_DIRECTIONS).count_ones(), 9);
+    }
+
+    #[test]
+    fn magic_attacks_match_ray_tracing_for_random_occupancies() {
+        let mut seed = 0xDEAD_BEEF_1234_5678u64;
+        for sq in 0..64u8 {
+            assert_eq!(
+                bishop_attacks_magic(sq, 0),
+                bishop_attacks_ray_traced(sq, 0)
+            );
+            assert_eq!(rook_attacks_magic(sq, 0), rook_attacks_ray_traced(sq, 0));
+            assert_eq!(
+                bishop_attacks_magic(sq, !0),
+                bishop_attacks_ray_traced(sq, !0)
+            );
+            assert_eq!(rook_attacks_magic(sq, !0), rook_attacks_ray_traced(sq, !0));
+
+            for _ in 0..1000 {
+                // Mix dense and sparse boards.
+                let occ = next_random_u64(&mut seed) & next_random_u64(&mut seed);
+                assert_eq!(
+                    bishop_attacks_magic(sq, occ),
+                    bishop_attacks_ray_traced(sq, occ),
+                    "bishop mismatch on square {sq} occupancy {occ:#018x}"
+                );
+                assert_eq!(
+                    rook_attacks_magic(sq, occ),
+                    rook_attacks_ray_traced(sq, occ),
+                    "rook mismatch on square {sq} occupancy {occ:#018x}"
+                );
+
+                let dense = next_random_u64(&mut seed) | next_random_u64(&mut seed);
+                assert_eq!(
+                    bishop_attacks_magic(sq, dense),
+                    bishop_attacks_ray_traced(sq, dense)
+                );
+                assert_eq!(
+                    rook_attacks_magic(sq, dense),
+                    rook_attacks_ray_traced(sq, dense)
+                );
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@
 //! This module defines the bit layout for the engine move format and provides
 //! utilities for packing, unpacking, and interpreting move metadata flags.
 
-use crate::game_state::chess_types::{PieceKind, Square};
+use crate::game_state::chess_types::{PieceKind, SquareIndex};
 
 const FROM_SHIFT: u64 = 0;
 const TO_SHIFT: u64 = 6;
@@ -20,10 +20,17 @@ pub const FLAG_DOUBLE_PAWN_PUSH: u64 = 1u64 << 22;
 pub const FLAG_EN_PASSANT: u64 = 1u64 << 23;
 pub const FLAG_CASTLING: u64 = 1u64 << 24;
 
+/// Sentinel for a null (passing) move. Chess960 castles can encode
+/// `from == to`, so the squares alone do not rule it out; it stays
+/// unambiguous because every real move sets a piece code (the promotion field
+/// is `NO_PIECE_CODE` or a knight..queen code, never zero) and castles also
+/// carry the king code and `FLAG_CASTLING`.
+pub const NULL_MOVE: u64 = 0;
+
 #[inline]
 pub fn pack_move_description(
-    from: Square,
-    to: Square,
+    from: SquareIndex,
+    to: SquareIndex,
     moved_piece: PieceKind,
     captured_piece: Option<PieceKind>,
     promotion_piece: Option<PieceKind>,
@@ -46,13 +53,13 @@ pub fn pack_move_description(
 }
 
 #[inline]
-pub fn move_from(move_description: u64) -> Square {
-    ((move_description >> FROM_SHIFT) & SQUARE_MASK) as Square
+pub fn move_from(move_description: u64) -> SquareIndex {
+    ((move_description >> FROM_SHIFT) & SQUARE_MASK) as SquareIndex
 }
 
 #[inline]
-pub fn move_to(move_description: u64) -> Square {
-    ((move_description >> TO_SHIFT) & SQUARE_MASK) as Square
+pub fn move_to(move_description: u64) -> SquareIndex {
+    ((move_description >> TO_SHIFT) & SQUARE_MASK) as SquareIndex
 }
 
 #[inline]
@@ -36,25 +36,7 @@ impl MaterialScorer {
 
     #[inline]
     fn material_balance_white_minus_black(game_state: &GameState) -> i32 {
-        let mut score = 0i32;
-
-        for piece in [
-            PieceKind::Pawn,
-            PieceKind::Knight,
-            PieceKind::Bishop,
-            PieceKind::Rook,
-            PieceKind::Queen,
-            PieceKind::King,
-        ] {
-            let value = Self::piece_value(piece);
-            let white_count =
-                game_state.pieces[Color::Light.index()][piece.index()].count_ones() as i32;
-            let black_count =
-                game_state.pieces[Color::Dark.index()][piece.index()].count_ones() as i32;
-            score += (white_count - black_count) * value;
-        }
-
-        score
+        game_state.eval.material
     }
 }
 
@@ -86,25 +68,7 @@ impl AlphaZeroMetric {
 
     #[inline]
     fn material_balance_white_minus_black(game_state: &GameState) -> i32 {
-        let mut score = 0i32;
-
-        for piece in [
-            PieceKind::Pawn,
-            PieceKind::Knight,
-            PieceKind::Bishop,
-            PieceKind::Rook,
-            PieceKind::Queen,
-            PieceKind::King,
-        ] {
-            let value = Self::piece_value(piece);
-            let white_count =
-                game_state.pieces[Color::Light.index()][piece.index()].count_ones() as i32;
-            let black_count =
-                game_state.pieces[Color::Dark.index()][piece.index()].count_ones() as i32;
-            score += (white_count - black_count) * value;
-        }
-
-        score
+        game_state.eval.material_alpha_zero
     }
 }
 
@@ -243,27 +207,10 @@ pub struct StandardScorer;
 impl StandardScorer {
     const MOBILITY_WEIGHT: i32 = 2;
 
+    /// Untapered midgame piece-square sum, maintained incrementally on `GameState`.
+    #[inline]
     fn positional_term(game_state: &GameState) -> i32 {
-        let mut score = 0i32;
-        for color in [Color::Light, Color::Dark] {
-            let sign = if color == Color::Light { 1 } else { -1 };
-            for piece in [
-                PieceKind::Pawn,
-                PieceKind::Knight,
-                PieceKind::Bishop,
-                PieceKind::Rook,
-                PieceKind::Queen,
-                PieceKind::King,
-            ] {
-                let mut bb = game_state.pieces[color.index()][piece.index()];
-                while bb != 0 {
-                    let sq = bb.trailing_zeros() as u8;
-                    score += sign * piece_square_bonus(piece, color, sq);
-                    bb &= bb - 1;
-                }
-            }
-        }
-        score
+        game_state.eval.pst_mg
     }
 
     fn mobility_term(game_state: &GameState) -> i32 {
@@ -344,45 +291,10 @@ fn mobility_for_color(game_state: &GameState, color: Color, occ: u64, own_occ: u
     m
 }
 
-fn piece_square_bonus(piece: PieceKind, color: Color, sq: u8) -> i32 {
-    let rank = (sq / 8) as i32;
-    let file = (sq % 8) as i32;
-    let r = if color == Color::Light {
-        rank
-    } else {
-        7 - rank
-    };
-    let dist_center = (file - 3).abs() + (r - 3).abs();
-    let center_bonus = 4 - dist_center;
-
-    match piece {
-        PieceKind::Pawn => r * 8 - (file - 3).abs() * 2,
-        PieceKind::Knight => center_bonus * 6,
-        PieceKind::Bishop => center_bonus * 4 + r,
-        PieceKind::Rook => r * 2,
-        PieceKind::Queen => center_bonus * 2,
-        PieceKind::King => {
-            // Mild opening preference for castled/edge king.
-            if r <= 1 {
-                8 - (file - 4).abs() * 2
-            } else {
-                -center_bonus * 4
-            }
-        }
-    }
-}
-
+#[inline]
 fn endgame_weight(game_state: &GameState) -> f64 {
-    // Phase based on non-pawn material. 24 = full opening phase.
-    let mut phase = 0i32;
-    for color in [Color::Light, Color::Dark] {
-        phase += game_state.pieces[color.index()][PieceKind::Queen.index()].count_ones() as i32 * 4;
-        phase += game_state.pieces[color.index()][PieceKind::Rook.index()].count_ones() as i32 * 2;
-        phase += game_state.pieces[color.index()][PieceKind::Bishop.index()].count_ones() as i32;
-        phase += game_state.pieces[color.index()][PieceKind::Knight.index()].count_ones() as i32;
-    }
-    let capped = phase.clamp(0, 24) as f64;
-    1.0 - (capped / 24.0)
+    // Phase based on non-pawn material, tracked incrementally in `GameState`.
+    game_state.eval.endgame_weight()
 }
 
 fn endgame_king_activity_white_minus_black(game_state: &GameState) -> i32 {
@@ -0,0 +1,210 @@
+//! Incrementally maintained evaluation terms.
+//!
+//! `GameState` carries an `EvalAccumulator` with material, piece-square and
+//! game-phase sums. Move application updates it per piece placed or removed,
+//! so scorers read these terms in O(1) instead of rescanning bitboards.
+
+use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::search::board_scoring::{AlphaZeroMetric, MaterialScorer};
+
+/// Phase units at the opening: 4 minors, 4 rooks (2 each), 2 queens (4 each).
+pub const FULL_GAME_PHASE: i32 = 24;
+
+/// Running evaluation sums; every term except `phase` is white minus black.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvalAccumulator {
+    /// Material using `MaterialScorer` piece values.
+    pub material: i32,
+    /// Material using `AlphaZeroMetric` piece values.
+    pub material_alpha_zero: i32,
+    /// Midgame piece-square bonus sum.
+    pub pst_mg: i32,
+    /// Endgame piece-square bonus sum.
+    pub pst_eg: i32,
+    /// Non-pawn phase units on the board (`FULL_GAME_PHASE` at the start).
+    pub phase: i32,
+}
+
+impl EvalAccumulator {
+    #[inline]
+    pub fn add_piece(&mut self, color: Color, piece: PieceKind, square: SquareIndex) {
+        self.apply(color, piece, square, 1);
+    }
+
+    #[inline]
+    pub fn remove_piece(&mut self, color: Color, piece: PieceKind, square: SquareIndex) {
+        self.apply(color, piece, square, -1);
+    }
+
+    #[inline]
+    fn apply(&mut self, color: Color, piece: PieceKind, square: SquareIndex, delta: i32) {
+        let signed = if color == Color::Light { delta } else { -delta };
+        let sq = usize::from(square);
+        self.material += signed * MaterialScorer::piece_value(piece);
+        self.material_alpha_zero += signed * AlphaZeroMetric::piece_value(piece);
+        self.pst_mg += signed * PST_MG[color.index()][piece.index()][sq];
+        self.pst_eg += signed * PST_EG[color.index()][piece.index()][sq];
+        self.phase += delta * phase_weight(piece);
+    }
+
+    /// Piece-square sum tapered by the current game phase.
+    #[inline]
+    pub fn tapered_pst(&self) -> i32 {
+        let phase = self.phase.clamp(0, FULL_GAME_PHASE);
+        (self.pst_mg * phase + self.pst_eg * (FULL_GAME_PHASE - phase)) / FULL_GAME_PHASE
+    }
+
+    /// Fraction of the way into the endgame, 0.0 (opening) to 1.0 (bare kings/pawns).
+    #[inline]
+    pub fn endgame_weight(&self) -> f64 {
+        let phase = self.phase.clamp(0, FULL_GAME_PHASE) as f64;
+        1.0 - (phase / FULL_GAME_PHASE as f64)
+    }
+}
+
+/// Game-phase units contributed by one piece.
+#[inline]
+pub const fn phase_weight(piece: PieceKind) -> i32 {
+    match piece {
+        PieceKind::Knight | PieceKind::Bishop => 1,
+        PieceKind::Rook => 2,
+        PieceKind::Queen => 4,
+        PieceKind::Pawn | PieceKind::King => 0,
+    }
+}
+
+/// Midgame piece-square bonus: centralisation and advancement, king sheltered.
+#[inline]
+pub fn piece_square_bonus_mg(piece: PieceKind, color: Color, sq: SquareIndex) -> i32 {
+    PST_MG[color.index()][piece.index()][usize::from(sq)]
+}
+
+/// Endgame piece-square bonus: as midgame, but the king heads for the centre.
+#[inline]
+pub fn piece_square_bonus_eg(piece: PieceKind, color: Color, sq: SquareIndex) -> i32 {
+    PST_EG[color.index()][piece.index()][usize::from(sq)]
+}
+
+type PieceSquareTable = [[[i32; 64]; 6]; 2];
+
+const PST_MG: PieceSquareTable = build_table(false);
+const PST_EG: PieceSquareTable = build_table(true);
+
+const fn build_table(endgame: bool) -> PieceSquareTable {
+    let mut table = [[[0i32; 64]; 6]; 2];
+    let mut color = 0usize;
+    while color < 2 {
+        let mut piece = 0usize;
+        while piece < 6 {
+            let mut sq = 0usize;
+            while sq < 64 {
+                table[color][piece][sq] = bonus(piece, color == 0, sq as i32, endgame);
+                sq += 1;
+            }
+            piece += 1;
+        }
+        color += 1;
+    }
+    table
+}
+
+/// Table entry for piece index `piece` (pawn = 0 .. king = 5).
+const fn bonus(piece: usize, light: bool, sq: i32, endgame: bool) -> i32 {
+    let rank = sq / 8;
+    let file = sq % 8;
+    let r = if light { rank } else { 7 - rank };
+    let dist_center = (file - 3).abs() + (r - 3).abs();
+    let center_bonus = 4 - dist_center;
+
+    match piece {
+        0 => r * 8 - (file - 3).abs() * 2,
+        1 => center_bonus * 6,
+        2 => center_bonus * 4 + r,
+        3 => r * 2,
+        4 => center_bonus * 2,
+        _ => {
+            if endgame {
+                center_bonus * 4
+            } else if r <= 1 {
+                // Mild opening preference for castled/edge king.
+                8 - (file - 4).abs() * 2
+            } else {
+                -center_bonus * 4
+            }
+        }
+    }
+}
+
+/// Full recompute from the piece bitboards.
+pub fn compute_eval_accumulator(game_state: &GameState) -> EvalAccumulator {
+    let mut acc = EvalAccumulator::default();
+    for color in [Color::Light, Color::Dark] {
+        for piece in [
+            PieceKind::Pawn,
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Rook,
+            PieceKind::Queen,
+            PieceKind::King,
+        ] {
+            let mut bb = game_state.pieces[color.index()][piece.index()];
+            while bb != 0 {
+                acc.add_piece(color, piece, bb.trailing_zeros() as SquareIndex);
+                bb &= bb - 1;
+            }
+        }
+    }
+    acc
+}
+
+/// Recompute and store the accumulator on the provided state.
+#[inline]
+pub fn refresh_eval_accumulator(game_state: &mut GameState) {
+    game_state.eval = compute_eval_accumulator(game_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute_eval_accumulator, FULL_GAME_PHASE};
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_apply::{
+        make_move_in_place, make_null_move_in_place, unmake_move_in_place,
+    };
+    use crate::move_generation::legal_move_generator::generate_legal_move_list;
+
+    #[test]
+    fn start_position_is_balanced_at_full_phase() {
+        let game = GameState::new_game();
+        assert_eq!(game.eval.material, 0);
+        assert_eq!(game.eval.pst_mg, 0);
+        assert_eq!(game.eval.pst_eg, 0);
+        assert_eq!(game.eval.phase, FULL_GAME_PHASE);
+        assert_eq!(game.eval, compute_eval_accumulator(&game));
+    }
+
+    #[test]
+    fn make_unmake_keeps_accumulator_in_sync_with_recompute() {
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ] {
+            let mut game = GameState::from_fen(fen).expect("FEN should parse");
+            let root = game.eval;
+            for mv in generate_legal_move_list(&game) {
+                make_move_in_place(&mut game, mv).expect("make move");
+                assert_eq!(game.eval, compute_eval_accumulator(&game), "{fen}");
+                for reply in generate_legal_move_list(&game) {
+                    make_move_in_place(&mut game, reply).expect("make reply");
+                    assert_eq!(game.eval, compute_eval_accumulator(&game), "{fen}");
+                    unmake_move_in_place(&mut game).expect("unmake reply");
+                }
+                unmake_move_in_place(&mut game).expect("unmake move");
+                assert_eq!(game.eval, root);
+            }
+            make_null_move_in_place(&mut game);
+            assert_eq!(game.eval, root);
+            unmake_move_in_place(&mut game).expect("unmake null move");
+        }
+    }
+}
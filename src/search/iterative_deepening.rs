@@ -1,28 +1,63 @@
 //! Iterative deepening search with negamax alpha-beta pruning.
 //!
 //! Implements depth-progressive search that repeatedly refines best-move
-//! output and supports configurable search depth limits.
+//! output and supports configurable search depth limits. Every heuristic is
+//! switched by `SearchConfig::features`; the `SearchFeatures` presets replay
+//! the v3-v15 search profiles on this shared core (staged move picker,
+//! bucketed TT, 10-ply quiescence cap). The v15 preset matches the legacy
+//! search exactly; `iterative_deepening_v3`..`v14` stay as the reference for
+//! the rest, pinned by fixtures in the tests below.
 
 use crate::game_state::game_state::GameState;
-use crate::move_generation::legal_move_apply::{make_move_in_place, unmake_move_in_place};
+use crate::move_generation::legal_move_apply::{
+    make_move_in_place, make_null_move_in_place, unmake_move_in_place, unmake_null_move_in_place,
+};
 use crate::move_generation::legal_move_checks::is_king_in_check;
 use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+use crate::move_generation::legal_move_staged::{
+    generate_captures, generate_evasions, generate_quiet_checks, has_legal_move,
+};
 use crate::move_generation::move_generator::{MoveGenResult, MoveGenerationError, MoveGenerator};
+use crate::move_generation::move_list::MoveList;
 use crate::moves::move_descriptions::{
-    move_captured_piece_code, move_promotion_piece_code, piece_kind_from_code, FLAG_CAPTURE,
-    FLAG_EN_PASSANT, NO_PIECE_CODE,
+    move_captured_piece_code, move_moved_piece_code, move_promotion_piece_code, move_to,
+    piece_kind_from_code, FLAG_CAPTURE, FLAG_EN_PASSANT, NO_PIECE_CODE,
 };
 use crate::search::board_scoring::BoardScorer;
-use crate::search::transposition_table::{Bound, TTEntry, TTStats, TranspositionTable};
+use crate::search::move_picker::{static_exchange_evaluation, MovePicker};
+use crate::search::search_features::{
+    DrawScore, EndgameExtensions, InternalIterativeReduction, LateMovePruning, LateMoveReductions,
+    NullMovePruning, ProbCut, SearchFeatures, SeePruning, SingularExtensions,
+};
+use crate::search::search_observer::{SearchInfo, SearchObserver, PROGRESS_NODE_INTERVAL};
+use crate::search::threading::{SharedSearchState, SharedTableHandle, SharedTranspositionTable};
+use crate::search::transposition_table_v11::{
+    Bound, SearchTable, TTEntry, TTStats, TranspositionTable,
+};
 use crate::utils::long_algebraic::move_description_to_long_algebraic;
+use std::cell::Cell;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
 use std::time::{Duration, Instant};
 
 const MATE_SCORE: i32 = 30000;
-
-#[derive(Debug, Clone, Copy)]
+const MAX_PLY: usize = 128;
+const QUIESCENCE_DELTA_MARGIN: i32 = 120;
+const SEE_BAD_CAPTURE_THRESHOLD: i32 = -120;
+const QUIESCENCE_MAX_PLY: u8 = 10;
+const QUIESCENCE_CHECK_PLY: u8 = 2;
+const MATE_TT_THRESHOLD: i32 = MATE_SCORE - 1000;
+
+#[derive(Debug, Clone)]
 pub struct SearchConfig {
     pub max_depth: u8,
     pub movetime_ms: Option<u64>,
+    pub max_nodes: Option<u64>,
+    pub stop_flag: Option<Arc<std::sync::atomic::AtomicBool>>,
+    pub features: SearchFeatures,
+    pub observer: Option<Arc<dyn SearchObserver>>,
 }
 
 impl Default for SearchConfig {
@@ -30,19 +65,30 @@ impl Default for SearchConfig {
         Self {
             max_depth: 4,
             movetime_ms: None,
+            max_nodes: None,
+            stop_flag: None,
+            features: SearchFeatures::default(),
+            observer: None,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct SearchResult {
     pub best_move: Option<u64>,
     pub best_score: i32,
+    /// `best_move` followed by the TT continuation; empty without a best move.
+    pub pv: Vec<u64>,
     pub reached_depth: u8,
+    /// Deepest ply reached in the last completed iteration, quiescence
+    /// included.
+    pub seldepth: u8,
     pub nodes: u64,
     pub elapsed_ms: u64,
     pub nps: u64,
     pub tt_stats: TTStats,
+    /// Copy of `tt_stats.hashfull`.
+    pub hashfull: u16,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -60,52 +106,200 @@ pub fn iterative_deepening_search<G: MoveGenerator, S: BoardScorer>(
     iterative_deepening_search_with_tt(game_state, generator, scorer, config, &mut local_tt)
 }
 
-pub fn iterative_deepening_search_with_tt<G: MoveGenerator, S: BoardScorer>(
+pub fn iterative_deepening_search_with_tt<G: MoveGenerator, S: BoardScorer, T: SearchTable>(
     game_state: &GameState,
     _generator: &G,
     scorer: &S,
     config: SearchConfig,
-    tt: &mut TranspositionTable,
+    tt: &mut T,
+) -> MoveGenResult<SearchResult> {
+    search_thread(game_state, scorer, &config, tt, 0, None)
+}
+
+/// Lazy SMP: the calling thread and `helper_threads` helpers each run the full
+/// iterative deepening on the same root against one shared table. Helpers
+/// skip staggered depths and rotate their root move order so they fill the
+/// table with different subtrees; stop and node budget go through one
+/// `SharedSearchState`, and the first thread to finish `max_depth` stops the
+/// rest. Returns the deepest completed result (the main thread on ties) and
+/// the id of the thread it came from.
+pub fn lazy_smp_search<G: MoveGenerator, S: BoardScorer>(
+    game_state: &GameState,
+    _generator: &G,
+    scorer: &S,
+    config: SearchConfig,
+    tt: &SharedTranspositionTable,
+    helper_threads: usize,
+) -> MoveGenResult<(SearchResult, usize)> {
+    let started_at = Instant::now();
+    let shared = SharedSearchState::new();
+    shared.reset_accounting();
+    shared.set_node_budget(config.max_nodes.filter(|n| *n > 0));
+
+    let (main, helpers) = std::thread::scope(|scope| {
+        let handles = (1..=helper_threads)
+            .map(|id| {
+                let helper_config = SearchConfig {
+                    observer: None,
+                    ..config.clone()
+                };
+                let shared = shared.as_ref();
+                scope.spawn(move || {
+                    let mut table = SharedTableHandle::new(tt);
+                    search_thread(
+                        game_state,
+                        scorer,
+                        &helper_config,
+                        &mut table,
+                        id,
+                        Some(shared),
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut table = SharedTableHandle::new(tt);
+        let main = search_thread(game_state, scorer, &config, &mut table, 0, Some(&shared));
+        shared.request_stop();
+        let helpers = handles
+            .into_iter()
+            .map(|handle| handle.join().ok().and_then(Result::ok))
+            .collect::<Vec<_>>();
+        (main, helpers)
+    });
+
+    let mut best = main?;
+    let mut best_thread = 0;
+    for (idx, helper) in helpers.into_iter().enumerate() {
+        let Some(helper) = helper else {
+            continue;
+        };
+        if helper.best_move.is_some() && helper.reached_depth > best.reached_depth {
+            best = helper;
+            best_thread = idx + 1;
+        }
+    }
+    best.nodes = shared.nodes_visited.load(Ordering::Relaxed).max(best.nodes);
+    best.elapsed_ms = started_at.elapsed().as_millis() as u64;
+    best.nps = best
+        .nodes
+        .saturating_mul(1000)
+        .checked_div(best.elapsed_ms)
+        .unwrap_or(0);
+    best.tt_stats = TTStats {
+        hashfull: tt.hashfull(),
+        ..shared.tt_counts()
+    };
+    best.hashfull = best.tt_stats.hashfull;
+    Ok((best, best_thread))
+}
+
+/// One thread's iterative deepening. Thread 0 is the main thread (the only
+/// one outside Lazy SMP); it alone ages the table and reports to the observer.
+fn search_thread<S: BoardScorer, T: SearchTable>(
+    game_state: &GameState,
+    scorer: &S,
+    config: &SearchConfig,
+    tt: &mut T,
+    thread_id: usize,
+    shared: Option<&SharedSearchState>,
 ) -> MoveGenResult<SearchResult> {
     let started_at = Instant::now();
+    let mut heuristics = SearchHeuristics {
+        root_order_offset: thread_id,
+        ..SearchHeuristics::default()
+    };
+    let smp_link = shared.map(SmpLink::new);
+    let stop_flag = StopSignal {
+        flag: config.stop_flag.as_ref(),
+        smp: smp_link.as_ref(),
+    };
+    let features = &config.features;
+    let max_nodes = config.max_nodes.filter(|n| *n > 0);
     let deadline = config
         .movetime_ms
         .map(|ms| started_at + Duration::from_millis(ms.max(1)));
 
     if config.max_depth == 0 {
         let elapsed_ms = started_at.elapsed().as_millis() as u64;
+        let tt_stats = tt.stats();
         return Ok(SearchResult {
             best_move: None,
             best_score: scorer.score(game_state),
+            pv: Vec::new(),
             reached_depth: 0,
+            seldepth: 0,
             nodes: 1,
             elapsed_ms,
             nps: 0,
-            tt_stats: tt.stats(),
+            tt_stats,
+            hashfull: tt_stats.hashfull,
         });
     }
 
     let mut result = SearchResult::default();
+    let mut total_nodes = 0u64;
+    let mut reporter = SearchReporter::new(config.observer.as_deref(), started_at);
 
+    let mut prev_iter_score = 0i32;
     for depth in 1..=config.max_depth {
-        if let Some(limit) = deadline {
-            if Instant::now() >= limit {
-                break;
-            }
+        // The node cap is checked against `total_nodes` just below.
+        if should_abort(deadline, stop_flag, 0, None) {
+            break;
+        }
+        let node_cap = max_nodes.map(|cap| cap.saturating_sub(total_nodes));
+        if node_cap == Some(0) {
+            break;
+        }
+        if helper_skips_depth(thread_id, depth, config.max_depth) {
+            continue;
         }
 
+        if thread_id == 0 {
+            tt.new_generation();
+        }
         let mut nodes = 0u64;
         let mut root_state = game_state.clone();
-        let Some((best_move, best_score)) =
-            negamax_root(&mut root_state, scorer, depth, &mut nodes, deadline, tt)?
-        else {
+        heuristics.reset_iteration(depth);
+        reporter.start_iteration(depth, total_nodes);
+        let searched = search_root_with_aspiration(
+            &mut root_state,
+            scorer,
+            depth,
+            prev_iter_score,
+            &mut nodes,
+            deadline,
+            node_cap,
+            stop_flag,
+            tt,
+            &mut heuristics,
+            features,
+            &mut reporter,
+        )?;
+        stop_flag.finish_iteration(nodes, || tt.stats());
+        let Some((best_move, best_score)) = searched else {
             break;
         };
 
+        total_nodes = total_nodes.saturating_add(nodes);
         result.best_move = best_move;
         result.best_score = best_score;
         result.reached_depth = depth;
-        result.nodes = nodes;
+        result.seldepth = heuristics.seldepth.max(depth);
+        result.nodes = total_nodes;
+        prev_iter_score = best_score;
+        reporter.iteration_complete(
+            game_state,
+            best_move,
+            best_score,
+            total_nodes,
+            &heuristics,
+            tt,
+        );
+        if depth == config.max_depth {
+            if let Some(shared) = shared {
+                shared.request_stop();
+            }
+        }
     }
 
     result.elapsed_ms = started_at.elapsed().as_millis() as u64;
@@ -115,17 +309,134 @@ pub fn iterative_deepening_search_with_tt<G: MoveGenerator, S: BoardScorer>(
         result.nodes.saturating_mul(1000) / result.elapsed_ms
     };
     result.tt_stats = tt.stats();
+    result.hashfull = result.tt_stats.hashfull;
+    if let Some(mv) = result.best_move {
+        result.pv = root_move_pv(game_state, mv, tt, result.reached_depth);
+    }
 
     Ok(result)
 }
 
-fn negamax_root<S: BoardScorer>(
+/// Lazy SMP depth staggering (the skip pattern popularised by Stockfish):
+/// each helper sits out a different subset of depths so the threads spread
+/// over neighbouring iterations. Depth 1 and the final depth always run.
+fn helper_skips_depth(thread_id: usize, depth: u8, max_depth: u8) -> bool {
+    const SKIP_SIZE: [usize; 20] = [1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4];
+    const SKIP_PHASE: [usize; 20] = [0, 1, 0, 1, 2, 3, 0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5, 6, 7];
+    if thread_id == 0 || depth <= 1 || depth >= max_depth {
+        return false;
+    }
+    let idx = (thread_id - 1) % SKIP_SIZE.len();
+    !((usize::from(depth) + SKIP_PHASE[idx]) / SKIP_SIZE[idx]).is_multiple_of(2)
+}
+
+/// Iteration nodes a Lazy SMP thread counts before adding them to the
+/// shared total, keeping the shared counter off the per-node path.
+const SHARED_NODE_BATCH: u64 = 1024;
+
+/// A Lazy SMP thread's view of the search-wide `SharedSearchState`.
+struct SmpLink<'a> {
+    state: &'a SharedSearchState,
+    /// Nodes of the current iteration already added to `state`.
+    flushed: Cell<u64>,
+    /// This thread's TT counts already added to `state`.
+    flushed_tt: Cell<TTStats>,
+}
+
+impl<'a> SmpLink<'a> {
+    fn new(state: &'a SharedSearchState) -> Self {
+        Self {
+            state,
+            flushed: Cell::new(0),
+            flushed_tt: Cell::new(TTStats::default()),
+        }
+    }
+
+    #[inline]
+    fn should_stop(&self, iteration_nodes: u64) -> bool {
+        let pending = iteration_nodes.saturating_sub(self.flushed.get());
+        if pending >= SHARED_NODE_BATCH {
+            self.flushed.set(iteration_nodes);
+            if self.state.bump_nodes_and_check_budget(pending) {
+                self.state.request_stop();
+            }
+        }
+        self.state.should_stop()
+    }
+
+    fn finish_iteration(&self, iteration_nodes: u64, tt_stats: TTStats) {
+        let pending = iteration_nodes.saturating_sub(self.flushed.get());
+        if self.state.bump_nodes_and_check_budget(pending) {
+            self.state.request_stop();
+        }
+        self.flushed.set(0);
+        let flushed_tt = self.flushed_tt.replace(tt_stats);
+        self.state.add_tt_counts(
+            tt_stats.probes - flushed_tt.probes,
+            tt_stats.hits - flushed_tt.hits,
+            tt_stats.stores - flushed_tt.stores,
+        );
+    }
+}
+
+/// Stop sources polled at every node: the caller's flag and, under Lazy SMP,
+/// the state shared with the other threads of the same search.
+#[derive(Clone, Copy)]
+struct StopSignal<'a> {
+    flag: Option<&'a Arc<AtomicBool>>,
+    smp: Option<&'a SmpLink<'a>>,
+}
+
+impl StopSignal<'_> {
+    #[inline]
+    fn is_set(self, iteration_nodes: u64) -> bool {
+        self.flag.is_some_and(|flag| flag.load(Ordering::Relaxed))
+            || self
+                .smp
+                .is_some_and(|link| link.should_stop(iteration_nodes))
+    }
+
+    fn finish_iteration(self, iteration_nodes: u64, tt_stats: impl FnOnce() -> TTStats) {
+        if let Some(link) = self.smp {
+            link.finish_iteration(iteration_nodes, tt_stats());
+        }
+    }
+}
+
+#[inline]
+fn should_abort(
+    deadline: Option<Instant>,
+    stop_flag: StopSignal<'_>,
+    nodes: u64,
+    max_nodes: Option<u64>,
+) -> bool {
+    if let Some(cap) = max_nodes {
+        if nodes >= cap {
+            return true;
+        }
+    }
+    if let Some(limit) = deadline {
+        if Instant::now() >= limit {
+            return true;
+        }
+    }
+    stop_flag.is_set(nodes)
+}
+
+fn negamax_root<S: BoardScorer, T: SearchTable>(
     game_state: &mut GameState,
     scorer: &S,
     depth: u8,
+    mut alpha: i32,
+    beta: i32,
     nodes: &mut u64,
     deadline: Option<Instant>,
-    tt: &mut TranspositionTable,
+    node_cap: Option<u64>,
+    stop_flag: StopSignal<'_>,
+    tt: &mut T,
+    heuristics: &mut SearchHeuristics,
+    features: &SearchFeatures,
+    reporter: &mut SearchReporter,
 ) -> MoveGenResult<Option<(Option<u64>, i32)>> {
     let mut moves = generate_legal_move_descriptions_in_place(game_state)?;
     if moves.is_empty() {
@@ -135,19 +446,27 @@ fn negamax_root<S: BoardScorer>(
     }
 
     let tt_move = tt.probe(game_state.zobrist_key).and_then(|e| e.best_move);
-    order_moves(&mut moves, tt_move);
+    order_moves(
+        &mut moves,
+        tt_move,
+        None,
+        heuristics.killers_at(0),
+        heuristics,
+        game_state.side_to_move,
+    );
+    if heuristics.root_order_offset > 0 && moves.len() > 2 {
+        let shift = heuristics.root_order_offset % (moves.len() - 1);
+        moves[1..].rotate_left(shift);
+    }
 
-    let mut alpha = -MATE_SCORE;
-    let beta = MATE_SCORE;
     let mut best_move = None;
     let mut best_score = -MATE_SCORE;
 
-    for mv in moves {
-        if let Some(limit) = deadline {
-            if Instant::now() >= limit {
-                return Ok(None);
-            }
+    for (idx, mv) in moves.into_iter().enumerate() {
+        if should_abort(deadline, stop_flag, *nodes, node_cap) {
+            return Ok(None);
         }
+        reporter.progress(*nodes, mv, idx + 1, heuristics, tt);
 
         make_move_in_place(game_state, mv).map_err(|x| {
             MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
@@ -156,13 +475,22 @@ fn negamax_root<S: BoardScorer>(
         let score_opt = negamax(
             game_state,
             scorer,
-            depth.saturating_sub(1),
-            -beta,
-            -alpha,
-            1,
+            NodeParams {
+                depth: depth.saturating_sub(1),
+                alpha: -beta,
+                beta: -alpha,
+                ply: 1,
+                allow_check_extension: true,
+                allow_null_pruning: true,
+                prev_move: Some(mv),
+            },
             nodes,
             deadline,
+            node_cap,
+            stop_flag,
             tt,
+            heuristics,
+            features,
         )?;
 
         unmake_move_in_place(game_state).map_err(|x| {
@@ -180,95 +508,633 @@ fn negamax_root<S: BoardScorer>(
         }
         if score > alpha {
             alpha = score;
+            reporter.new_best_move(game_state, mv, score, *nodes, heuristics, tt);
+        }
+        if alpha >= beta {
+            break;
         }
     }
 
     Ok(Some((best_move, best_score)))
 }
 
-fn negamax<S: BoardScorer>(
+fn search_root_with_aspiration<S: BoardScorer, T: SearchTable>(
     game_state: &mut GameState,
     scorer: &S,
     depth: u8,
-    mut alpha: i32,
+    prev_score: i32,
+    nodes: &mut u64,
+    deadline: Option<Instant>,
+    node_cap: Option<u64>,
+    stop_flag: StopSignal<'_>,
+    tt: &mut T,
+    heuristics: &mut SearchHeuristics,
+    features: &SearchFeatures,
+    reporter: &mut SearchReporter,
+) -> MoveGenResult<Option<(Option<u64>, i32)>> {
+    if depth <= 1 || !features.aspiration {
+        return negamax_root(
+            game_state,
+            scorer,
+            depth,
+            -MATE_SCORE,
+            MATE_SCORE,
+            nodes,
+            deadline,
+            node_cap,
+            stop_flag,
+            tt,
+            heuristics,
+            features,
+            reporter,
+        );
+    }
+
+    let mut window = aspiration_initial_window(depth);
+    let mut attempts = 0u8;
+    let mut alpha = (prev_score - window).max(-MATE_SCORE);
+    let mut beta = (prev_score + window).min(MATE_SCORE);
+
+    loop {
+        attempts = attempts.saturating_add(1);
+        let Some((best_move, score)) = negamax_root(
+            game_state, scorer, depth, alpha, beta, nodes, deadline, node_cap, stop_flag, tt,
+            heuristics, features, reporter,
+        )?
+        else {
+            return Ok(None);
+        };
+
+        // If we've expanded to the full legal score window, accept the result.
+        // This avoids pathological loops when mate scores sit on the bounds.
+        if alpha <= -MATE_SCORE && beta >= MATE_SCORE {
+            return Ok(Some((best_move, score)));
+        }
+
+        if score <= alpha {
+            window = (window * 2).min(MATE_SCORE / 2);
+            alpha = (score - window).saturating_sub(1).max(-MATE_SCORE);
+            beta = (score + window).min(MATE_SCORE);
+            if attempts >= 8 {
+                alpha = -MATE_SCORE;
+                beta = MATE_SCORE;
+            }
+            continue;
+        }
+
+        if score >= beta {
+            window = (window * 2).min(MATE_SCORE / 2);
+            alpha = (score - window).max(-MATE_SCORE);
+            beta = (score + window).saturating_add(1).min(MATE_SCORE);
+            if attempts >= 8 {
+                alpha = -MATE_SCORE;
+                beta = MATE_SCORE;
+            }
+            continue;
+        }
+
+        return Ok(Some((best_move, score)));
+    }
+}
+
+#[inline]
+fn aspiration_initial_window(depth: u8) -> i32 {
+    25 + (i32::from(depth) * 10)
+}
+
+/// Inputs that change from node to node; everything else `negamax` takes is
+/// shared by the whole tree.
+#[derive(Debug, Clone, Copy)]
+struct NodeParams {
+    depth: u8,
+    alpha: i32,
     beta: i32,
     ply: u8,
+    allow_check_extension: bool,
+    allow_null_pruning: bool,
+    /// Move that led here, for countermove and continuation history.
+    prev_move: Option<u64>,
+}
+
+fn negamax<S: BoardScorer, T: SearchTable>(
+    game_state: &mut GameState,
+    scorer: &S,
+    node: NodeParams,
     nodes: &mut u64,
     deadline: Option<Instant>,
-    tt: &mut TranspositionTable,
+    node_cap: Option<u64>,
+    stop_flag: StopSignal<'_>,
+    tt: &mut T,
+    heuristics: &mut SearchHeuristics,
+    features: &SearchFeatures,
 ) -> MoveGenResult<Option<i32>> {
-    if let Some(limit) = deadline {
-        if Instant::now() >= limit {
-            return Ok(None);
-        }
+    let NodeParams {
+        depth,
+        mut alpha,
+        beta,
+        ply,
+        allow_check_extension,
+        allow_null_pruning,
+        prev_move,
+    } = node;
+    if should_abort(deadline, stop_flag, *nodes, node_cap) {
+        return Ok(None);
     }
 
     if is_draw_state(game_state) {
-        return Ok(Some(0));
+        return Ok(Some(draw_score(
+            features.draw_score,
+            scorer.score(game_state),
+        )));
     }
 
     let alpha_orig = alpha;
-
-    if let Some(entry) = tt.probe(game_state.zobrist_key) {
+    let ply_idx = usize::from(ply).min(MAX_PLY - 1);
+    // Set while verifying a singular extension: the TT entry describes the
+    // full node, so it may neither cut nor be overwritten here.
+    let excluded_move = Some(heuristics.excluded[ply_idx]).filter(|&mv| mv != 0);
+
+    let tt_entry = tt.probe(game_state.zobrist_key);
+    if let Some(entry) = tt_entry.filter(|_| excluded_move.is_none()) {
+        let tt_score = if features.mate_distance_tt {
+            tt_score_from_storage(entry.score, ply)
+        } else {
+            entry.score
+        };
         if entry.depth >= depth {
             match entry.bound {
-                Bound::Exact => return Ok(Some(entry.score)),
-                Bound::Lower if entry.score >= beta => return Ok(Some(entry.score)),
-                Bound::Upper if entry.score <= alpha => return Ok(Some(entry.score)),
+                Bound::Exact => return Ok(Some(tt_score)),
+                Bound::Lower if tt_score >= beta => return Ok(Some(tt_score)),
+                Bound::Upper if tt_score <= alpha => return Ok(Some(tt_score)),
                 _ => {}
             }
         }
     }
 
     *nodes += 1;
+    heuristics.record_seldepth(ply);
 
     if depth == 0 {
-        return quiescence(game_state, scorer, alpha, beta, nodes, deadline);
+        return quiescence(
+            game_state, scorer, alpha, beta, 0, ply, nodes, deadline, node_cap, stop_flag,
+            heuristics, features,
+        );
     }
 
-    let mut moves = generate_legal_move_descriptions_in_place(game_state)?;
-    if moves.is_empty() {
-        return Ok(Some(terminal_score(game_state, ply)));
+    let in_check = is_king_in_check(game_state, game_state.side_to_move);
+    // Conservative reverse futility pruning:
+    // at shallow depth in quiet, non-PV-like nodes, skip hopelessly high evals.
+    let static_eval = scorer.score(game_state);
+    if features.reverse_futility
+        && excluded_move.is_none()
+        && should_reverse_futility_prune(depth, in_check, alpha, beta, static_eval, game_state)
+    {
+        return Ok(Some(beta));
+    }
+
+    let null_verify_depth = match features.null_move {
+        NullMovePruning::On { verify_depth, .. } => verify_depth,
+        NullMovePruning::Off => None,
+    };
+    if allow_null_pruning
+        && should_try_null_move(features.null_move, depth, in_check, beta, game_state)
+    {
+        make_null_move_in_place(game_state);
+        let reduction = if depth >= 6 { 3 } else { 2 };
+        let score_opt = negamax(
+            game_state,
+            scorer,
+            NodeParams {
+                depth: depth.saturating_sub(1 + reduction),
+                alpha: -beta,
+                beta: -beta + 1,
+                ply: ply.saturating_add(1),
+                allow_check_extension: false,
+                allow_null_pruning: null_verify_depth.is_none(),
+                prev_move: None,
+            },
+            nodes,
+            deadline,
+            node_cap,
+            stop_flag,
+            tt,
+            heuristics,
+            features,
+        )?;
+        unmake_null_move_in_place(game_state).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("unmake_null_move_in_place failed: {x}"))
+        })?;
+
+        let Some(score) = score_opt else {
+            return Ok(None);
+        };
+        let score = -score;
+        if score >= beta {
+            if should_verify_null_cutoff(null_verify_depth, depth, in_check) {
+                let verify_opt = negamax(
+                    game_state,
+                    scorer,
+                    NodeParams {
+                        depth: depth.saturating_sub(1),
+                        alpha: beta.saturating_sub(1),
+                        allow_null_pruning: false,
+                        ..node
+                    },
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    tt,
+                    heuristics,
+                    features,
+                )?;
+                let Some(verify_score) = verify_opt else {
+                    return Ok(None);
+                };
+                if verify_score >= beta {
+                    return Ok(Some(cutoff_score(features, verify_score, beta)));
+                }
+            } else {
+                return Ok(Some(cutoff_score(features, score, beta)));
+            }
+        }
+    }
+
+    let pv_node = beta.saturating_sub(alpha) > 1;
+    let has_tt_move = tt_entry.is_some_and(|entry| entry.best_move.is_some());
+    // Internal iterative reduction: move ordering is weakest without a TT
+    // move, so spend less here and let the next iteration fill the TT.
+    let depth = match features.internal_iterative_reduction {
+        InternalIterativeReduction::On {
+            min_depth,
+            reduction,
+        } if depth >= min_depth
+            && !has_tt_move
+            && excluded_move.is_none()
+            && (pv_node || static_eval >= beta) =>
+        {
+            depth.saturating_sub(reduction).max(1)
+        }
+        _ => depth,
+    };
+
+    // ProbCut: a good capture that still beats beta by `margin` in a much
+    // shallower search makes a fail high here very likely.
+    if let ProbCut::On { min_depth, margin } = features.probcut {
+        let probcut_beta = beta.saturating_add(margin);
+        let tt_says_no = tt_entry.is_some_and(|entry| {
+            u16::from(entry.depth) + 3 >= u16::from(depth) && entry.score < probcut_beta
+        });
+        if !pv_node
+            && !in_check
+            && excluded_move.is_none()
+            && depth >= min_depth
+            && beta.abs() < MATE_TT_THRESHOLD
+            && !tt_says_no
+        {
+            let mut captures = MoveList::new();
+            generate_captures(game_state, &mut captures);
+            order_moves_basic(&mut captures, None);
+            for mv in captures {
+                if static_exchange_evaluation(game_state, mv) < probcut_beta - static_eval {
+                    continue;
+                }
+                make_move_in_place(game_state, mv).map_err(|x| {
+                    MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
+                })?;
+                let mut score_opt = quiescence(
+                    game_state,
+                    scorer,
+                    -probcut_beta,
+                    -probcut_beta + 1,
+                    0,
+                    ply.saturating_add(1),
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    heuristics,
+                    features,
+                )?;
+                if score_opt.is_some_and(|score| -score >= probcut_beta) {
+                    score_opt = negamax(
+                        game_state,
+                        scorer,
+                        NodeParams {
+                            depth: depth.saturating_sub(4),
+                            alpha: -probcut_beta,
+                            beta: -probcut_beta + 1,
+                            ply: ply.saturating_add(1),
+                            allow_check_extension,
+                            allow_null_pruning,
+                            prev_move: Some(mv),
+                        },
+                        nodes,
+                        deadline,
+                        node_cap,
+                        stop_flag,
+                        tt,
+                        heuristics,
+                        features,
+                    )?;
+                }
+                unmake_move_in_place(game_state).map_err(|x| {
+                    MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
+                })?;
+                let Some(score) = score_opt else {
+                    return Ok(None);
+                };
+                let score = -score;
+                if score >= probcut_beta {
+                    tt.store(TTEntry {
+                        key: game_state.zobrist_key,
+                        depth: depth.saturating_sub(3),
+                        score: if features.mate_distance_tt {
+                            tt_score_for_storage(score, ply)
+                        } else {
+                            score
+                        },
+                        bound: Bound::Lower,
+                        best_move: Some(mv),
+                    });
+                    return Ok(Some(cutoff_score(features, score, beta)));
+                }
+            }
+        }
     }
 
-    let tt_move = tt.probe(game_state.zobrist_key).and_then(|entry| {
+    let tt_move = tt_entry.and_then(|entry| {
         if entry.depth >= depth {
             entry.best_move
         } else {
             None
         }
     });
-    order_moves(&mut moves, tt_move);
+
+    // Singular extension: search the node without the TT move at half depth
+    // against a window just below the TT score. The extension (or reduction)
+    // applies to the TT move only.
+    let mut tt_move_extension: Option<(u64, i8)> = None;
+    if let SingularExtensions::On {
+        min_depth,
+        double_margin,
+        max_double,
+    } = features.singular_extensions
+    {
+        if let Some((singular_move, tt_score)) = singular_candidate(
+            tt_entry,
+            depth,
+            min_depth,
+            ply,
+            heuristics.root_depth,
+            excluded_move,
+        ) {
+            let singular_beta = tt_score - 2 * i32::from(depth);
+            heuristics.excluded[ply_idx] = singular_move;
+            let verified = negamax(
+                game_state,
+                scorer,
+                NodeParams {
+                    depth: (depth - 1) / 2,
+                    alpha: singular_beta - 1,
+                    beta: singular_beta,
+                    allow_null_pruning: false,
+                    ..node
+                },
+                nodes,
+                deadline,
+                node_cap,
+                stop_flag,
+                tt,
+                heuristics,
+                features,
+            )?;
+            heuristics.excluded[ply_idx] = 0;
+            let Some(score) = verified else {
+                return Ok(None);
+            };
+            if score < singular_beta {
+                let double = score < singular_beta - double_margin
+                    && heuristics.double_extensions < max_double;
+                tt_move_extension = Some((singular_move, if double { 2 } else { 1 }));
+            } else if singular_beta >= beta {
+                // Multi-cut: another move beats beta even without the TT move.
+                return Ok(Some(singular_beta));
+            } else if tt_score >= beta {
+                tt_move_extension = Some((singular_move, -1));
+            }
+        }
+    }
+
+    let side_to_move = game_state.side_to_move;
+    // Staged picker: quiet moves are only generated if no tactical cutoff occurs.
+    let mut picker = MovePicker::new(game_state, tt_move, heuristics.killers_at(ply_idx));
 
     let mut best = -MATE_SCORE;
     let mut best_move: Option<u64> = None;
+    let mut moves_tried = 0usize;
 
-    for mv in moves {
-        if let Some(limit) = deadline {
-            if Instant::now() >= limit {
-                return Ok(None);
-            }
+    while let Some(mv) = picker.next_move(game_state, &|m| {
+        quiet_move_order_score(m, prev_move, heuristics, side_to_move)
+    }) {
+        if excluded_move == Some(mv) {
+            continue;
+        }
+        let move_index = moves_tried;
+        moves_tried += 1;
+        if should_abort(deadline, stop_flag, *nodes, node_cap) {
+            return Ok(None);
         }
 
         make_move_in_place(game_state, mv).map_err(|x| {
             MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
         })?;
 
-        let score_opt = negamax(
-            game_state,
-            scorer,
-            depth.saturating_sub(1),
-            -beta,
-            -alpha,
-            ply.saturating_add(1),
-            nodes,
-            deadline,
-            tt,
-        )?;
+        let extensions = features.endgame_extensions;
+        let child = child_depth(extensions, depth, game_state, allow_check_extension, mv);
+        let (child, double_extended) = match tt_move_extension {
+            Some((ext_move, ext)) if ext_move == mv => (child.saturating_add_signed(ext), ext == 2),
+            _ => (child, false),
+        };
+        let child_allow_check_ext =
+            child_allows_check_extension(extensions, depth, game_state, allow_check_extension, mv);
+        let child_node = |depth, alpha, beta| NodeParams {
+            depth,
+            alpha,
+            beta,
+            ply: ply.saturating_add(1),
+            allow_check_extension: child_allow_check_ext,
+            allow_null_pruning,
+            prev_move: Some(mv),
+        };
+        let is_quiet = is_quiet_move(mv);
+        if is_quiet
+            && !in_check
+            && should_lmp_prune(
+                features.late_move_pruning,
+                depth,
+                move_index,
+                alpha,
+                best,
+                game_state,
+            )
+        {
+            unmake_move_in_place(game_state).map_err(|x| {
+                MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
+            })?;
+            continue;
+        }
+        if double_extended {
+            heuristics.double_extensions += 1;
+            debug_assert!(matches!(
+                features.singular_extensions,
+                SingularExtensions::On { max_double, .. }
+                    if heuristics.double_extensions <= max_double
+            ));
+        }
+        let lmr_reduction = lmr_reduction(
+            features.late_move_reductions,
+            depth,
+            move_index,
+            is_quiet,
+            in_check,
+        );
+        let use_pvs = features.pvs && should_use_pvs(depth, move_index, alpha, in_check);
+        let score_opt = if !use_pvs {
+            // Fallback to classic full-window search (v5 behavior).
+            if lmr_reduction > 0 {
+                let reduced_child = child.saturating_sub(lmr_reduction);
+                let reduced = negamax(
+                    game_state,
+                    scorer,
+                    child_node(reduced_child, -alpha - 1, -alpha),
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    tt,
+                    heuristics,
+                    features,
+                )?;
+
+                let Some(reduced_score) = reduced else {
+                    unmake_move_in_place(game_state).map_err(|x| {
+                        MoveGenerationError::InvalidState(format!(
+                            "unmake_move_in_place failed: {x}"
+                        ))
+                    })?;
+                    return Ok(None);
+                };
+                let reduced_score = -reduced_score;
+
+                if reduced_score > alpha {
+                    negamax(
+                        game_state,
+                        scorer,
+                        child_node(child, -beta, -alpha),
+                        nodes,
+                        deadline,
+                        node_cap,
+                        stop_flag,
+                        tt,
+                        heuristics,
+                        features,
+                    )?
+                } else {
+                    Some(-reduced_score)
+                }
+            } else {
+                negamax(
+                    game_state,
+                    scorer,
+                    child_node(child, -beta, -alpha),
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    tt,
+                    heuristics,
+                    features,
+                )?
+            }
+        } else if move_index == 0 {
+            // PV move: full-window search.
+            negamax(
+                game_state,
+                scorer,
+                child_node(child, -beta, -alpha),
+                nodes,
+                deadline,
+                node_cap,
+                stop_flag,
+                tt,
+                heuristics,
+                features,
+            )?
+        } else {
+            // Non-PV move: PVS zero-window probe first.
+            let zero_window_opp_score = if lmr_reduction > 0 {
+                let reduced_child = child.saturating_sub(lmr_reduction);
+                negamax(
+                    game_state,
+                    scorer,
+                    child_node(reduced_child, -alpha - 1, -alpha),
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    tt,
+                    heuristics,
+                    features,
+                )?
+            } else {
+                negamax(
+                    game_state,
+                    scorer,
+                    child_node(child, -alpha - 1, -alpha),
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    tt,
+                    heuristics,
+                    features,
+                )?
+            };
+
+            let Some(pvs_score) = zero_window_opp_score else {
+                unmake_move_in_place(game_state).map_err(|x| {
+                    MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
+                })?;
+                return Ok(None);
+            };
+            let pvs_score_us = -pvs_score;
+
+            if pvs_score_us > alpha {
+                // Likely improves PV: confirm with full window.
+                negamax(
+                    game_state,
+                    scorer,
+                    child_node(child, -beta, -alpha),
+                    nodes,
+                    deadline,
+                    node_cap,
+                    stop_flag,
+                    tt,
+                    heuristics,
+                    features,
+                )?
+            } else {
+                Some(pvs_score)
+            }
+        };
 
         unmake_move_in_place(game_state).map_err(|x| {
             MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
         })?;
+        if double_extended {
+            heuristics.double_extensions -= 1;
+        }
 
         let Some(score) = score_opt else {
             return Ok(None);
@@ -283,10 +1149,29 @@ fn negamax<S: BoardScorer>(
             alpha = score;
         }
         if alpha >= beta {
+            if is_quiet && features.killers_and_history {
+                heuristics.record_killer(ply_idx, mv);
+                heuristics.record_history(game_state.side_to_move, mv, depth);
+            }
+            if is_quiet && features.countermoves {
+                heuristics.record_countermove(prev_move, mv);
+                heuristics.record_continuation(game_state.side_to_move, prev_move, mv, depth);
+            }
             break;
         }
     }
 
+    if moves_tried == 0 {
+        // Only the excluded TT move was legal: it is singular.
+        if excluded_move.is_some() {
+            return Ok(Some(alpha));
+        }
+        return Ok(Some(terminal_score(game_state, ply)));
+    }
+    if excluded_move.is_some() {
+        return Ok(Some(best));
+    }
+
     let bound = if best <= alpha_orig {
         Bound::Upper
     } else if best >= beta {
@@ -298,7 +1183,11 @@ fn negamax<S: BoardScorer>(
     tt.store(TTEntry {
         key: game_state.zobrist_key,
         depth,
-        score: best,
+        score: if features.mate_distance_tt {
+            tt_score_for_storage(best, ply)
+        } else {
+            best
+        },
         bound,
         best_move,
     });
@@ -306,6 +1195,41 @@ fn negamax<S: BoardScorer>(
     Ok(Some(best))
 }
 
+/// TT move and score when this node may try a singular extension: a lower or
+/// exact non-mate entry at most three plies shallower than `depth`, outside a
+/// verification search, and within twice the iteration depth.
+fn singular_candidate(
+    tt_entry: Option<TTEntry>,
+    depth: u8,
+    min_depth: u8,
+    ply: u8,
+    root_depth: u8,
+    excluded_move: Option<u64>,
+) -> Option<(u64, i32)> {
+    let entry = tt_entry?;
+    let tt_move = entry.best_move?;
+    if excluded_move.is_some()
+        || depth < min_depth
+        || u16::from(ply) >= 2 * u16::from(root_depth)
+        || entry.bound == Bound::Upper
+        || u16::from(entry.depth) + 3 < u16::from(depth)
+        || entry.score.abs() >= MATE_TT_THRESHOLD
+    {
+        return None;
+    }
+    Some((tt_move, entry.score))
+}
+
+#[inline]
+fn should_use_pvs(depth: u8, move_index: usize, alpha: i32, in_check: bool) -> bool {
+    if in_check || depth < 3 || move_index == 0 {
+        return false;
+    }
+    // Avoid PVS when alpha is still near the initial floor; in those nodes it
+    // often causes extra full re-searches with little cutoff benefit.
+    alpha > (-MATE_SCORE + 2000)
+}
+
 fn terminal_score(game_state: &GameState, ply: u8) -> i32 {
     if is_king_in_check(game_state, game_state.side_to_move) {
         -MATE_SCORE + i32::from(ply)
@@ -314,33 +1238,65 @@ fn terminal_score(game_state: &GameState, ply: u8) -> i32 {
     }
 }
 
+#[inline]
+fn tt_score_for_storage(score: i32, ply: u8) -> i32 {
+    if score >= MATE_TT_THRESHOLD {
+        score.saturating_add(i32::from(ply))
+    } else if score <= -MATE_TT_THRESHOLD {
+        score.saturating_sub(i32::from(ply))
+    } else {
+        score
+    }
+}
+
+#[inline]
+fn tt_score_from_storage(score: i32, ply: u8) -> i32 {
+    if score >= MATE_TT_THRESHOLD {
+        score.saturating_sub(i32::from(ply))
+    } else if score <= -MATE_TT_THRESHOLD {
+        score.saturating_add(i32::from(ply))
+    } else {
+        score
+    }
+}
+
 fn quiescence<S: BoardScorer>(
     game_state: &mut GameState,
     scorer: &S,
     mut alpha: i32,
     beta: i32,
+    qply: u8,
+    ply: u8,
     nodes: &mut u64,
     deadline: Option<Instant>,
+    node_cap: Option<u64>,
+    stop_flag: StopSignal<'_>,
+    heuristics: &mut SearchHeuristics,
+    features: &SearchFeatures,
 ) -> MoveGenResult<Option<i32>> {
-    if let Some(limit) = deadline {
-        if Instant::now() >= limit {
-            return Ok(None);
-        }
+    if should_abort(deadline, stop_flag, *nodes, node_cap) {
+        return Ok(None);
     }
 
     if is_draw_state(game_state) {
-        return Ok(Some(0));
+        return Ok(Some(draw_score(
+            features.draw_score,
+            scorer.score(game_state),
+        )));
     }
 
     *nodes += 1;
+    heuristics.record_seldepth(ply);
+    let in_check = is_king_in_check(game_state, game_state.side_to_move);
 
     // If side-to-move is in check, stand-pat is invalid.
-    if is_king_in_check(game_state, game_state.side_to_move) {
-        let mut moves = generate_legal_move_descriptions_in_place(game_state)?;
+    if in_check {
+        let mut moves = MoveList::new();
+        generate_evasions(game_state, &mut moves);
         if moves.is_empty() {
-            return Ok(Some(terminal_score(game_state, 0)));
+            return Ok(Some(terminal_score(game_state, qply)));
         }
-        order_moves(&mut moves, None);
+        order_moves_basic(&mut moves, None);
 
         let mut local_alpha = alpha;
         for mv in moves {
@@ -348,7 +1304,20 @@ fn quiescence<S: BoardScorer>(
                 MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
             })?;
 
-            let score_opt = quiescence(game_state, scorer, -beta, -local_alpha, nodes, deadline)?;
+            let score_opt = quiescence(
+                game_state,
+                scorer,
+                -beta,
+                -local_alpha,
+                qply.saturating_add(1),
+                ply.saturating_add(1),
+                nodes,
+                deadline,
+                node_cap,
+                stop_flag,
+                heuristics,
+                features,
+            )?;
 
             unmake_move_in_place(game_state).map_err(|x| {
                 MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
@@ -360,7 +1329,7 @@ fn quiescence<S: BoardScorer>(
             let score = -score;
 
             if score >= beta {
-                return Ok(Some(beta));
+                return Ok(Some(cutoff_score(features, score, beta)));
             }
             if score > local_alpha {
                 local_alpha = score;
@@ -371,32 +1340,54 @@ fn quiescence<S: BoardScorer>(
 
     let stand_pat = scorer.score(game_state);
     if stand_pat >= beta {
-        return Ok(Some(beta));
+        return Ok(Some(cutoff_score(features, stand_pat, beta)));
     }
     if stand_pat > alpha {
         alpha = stand_pat;
     }
+    if qply >= QUIESCENCE_MAX_PLY {
+        return Ok(Some(alpha));
+    }
 
-    let mut moves = generate_legal_move_descriptions_in_place(game_state)?;
-    if moves.is_empty() {
-        return Ok(Some(terminal_score(game_state, 0)));
+    let mut moves = MoveList::new();
+    generate_captures(game_state, &mut moves);
+    if moves.is_empty() && !has_legal_move(game_state) {
+        return Ok(Some(terminal_score(game_state, qply)));
     }
 
-    moves.retain(|m| is_tactical_move(*m));
-    order_moves(&mut moves, None);
+    if features.see_pruning != SeePruning::Off {
+        moves.retain(|m| {
+            passes_quiescence_pruning(features.see_pruning, m, stand_pat, alpha, qply, game_state)
+        });
+    }
+    if features.quiet_checks && qply > 0 && qply < QUIESCENCE_CHECK_PLY {
+        append_quiescence_check_moves(game_state, &mut moves);
+    }
+    order_moves_basic(&mut moves, None);
 
     for mv in moves {
-        if let Some(limit) = deadline {
-            if Instant::now() >= limit {
-                return Ok(None);
-            }
+        if should_abort(deadline, stop_flag, *nodes, node_cap) {
+            return Ok(None);
         }
 
         make_move_in_place(game_state, mv).map_err(|x| {
             MoveGenerationError::InvalidState(format!("make_move_in_place failed: {x}"))
         })?;
 
-        let score_opt = quiescence(game_state, scorer, -beta, -alpha, nodes, deadline)?;
+        let score_opt = quiescence(
+            game_state,
+            scorer,
+            -beta,
+            -alpha,
+            qply.saturating_add(1),
+            ply.saturating_add(1),
+            nodes,
+            deadline,
+            node_cap,
+            stop_flag,
+            heuristics,
+            features,
+        )?;
 
         unmake_move_in_place(game_state).map_err(|x| {
             MoveGenerationError::InvalidState(format!("unmake_move_in_place failed: {x}"))
@@ -408,7 +1399,7 @@ fn quiescence<S: BoardScorer>(
         let score = -score;
 
         if score >= beta {
-            return Ok(Some(beta));
+            return Ok(Some(cutoff_score(features, score, beta)));
         }
         if score > alpha {
             alpha = score;
@@ -420,45 +1411,802 @@ fn quiescence<S: BoardScorer>(
 
 #[inline]
 fn is_draw_state(game_state: &GameState) -> bool {
-    if game_state.halfmove_clock >= 100 {
+    game_state.is_draw_by_rule()
+}
+
+/// Return the bound itself on a cutoff unless fail-soft is enabled.
+#[inline]
+fn cutoff_score(features: &SearchFeatures, score: i32, beta: i32) -> i32 {
+    if features.fail_soft {
+        score
+    } else {
+        beta
+    }
+}
+
+#[inline]
+fn draw_score(policy: DrawScore, static_eval_side_to_move: i32) -> i32 {
+    match policy {
+        DrawScore::Zero => 0,
+        DrawScore::WinningPenalty => winning_penalty_draw_score(static_eval_side_to_move),
+        DrawScore::TightWinningPenalty => {
+            tight_winning_penalty_draw_score(static_eval_side_to_move)
+        }
+        DrawScore::Contempt => contempt_draw_score(static_eval_side_to_move),
+    }
+}
+
+#[inline]
+fn winning_penalty_draw_score(static_eval_side_to_move: i32) -> i32 {
+    const DRAW_PENALTY_BASE: i32 = 40;
+    const DRAW_PENALTY_CAP: i32 = 220;
+    const WINNING_MARGIN: i32 = 80;
+
+    if static_eval_side_to_move > WINNING_MARGIN {
+        let penalty = DRAW_PENALTY_BASE + (static_eval_side_to_move / 4).min(DRAW_PENALTY_CAP);
+        -penalty
+    } else if static_eval_side_to_move < -WINNING_MARGIN {
+        DRAW_PENALTY_BASE + ((-static_eval_side_to_move) / 4).min(DRAW_PENALTY_CAP)
+    } else {
+        0
+    }
+}
+
+#[inline]
+fn tight_winning_penalty_draw_score(static_eval_side_to_move: i32) -> i32 {
+    // Strongly discourage repetition draws when winning; the symmetric policy
+    // above is too draw-tolerant in clearly won positions.
+    const WINNING_MARGIN: i32 = 40;
+    const DRAW_PENALTY_BASE: i32 = 120;
+    const DRAW_PENALTY_CAP: i32 = 1000;
+    const DRAW_BONUS_BASE: i32 = 35;
+    const DRAW_BONUS_CAP: i32 = 220;
+
+    if static_eval_side_to_move > WINNING_MARGIN {
+        let mut penalty = DRAW_PENALTY_BASE + (static_eval_side_to_move / 2).min(DRAW_PENALTY_CAP);
+        if static_eval_side_to_move > 600 {
+            penalty += 150;
+        }
+        -penalty
+    } else if static_eval_side_to_move < -WINNING_MARGIN {
+        DRAW_BONUS_BASE + ((-static_eval_side_to_move) / 6).min(DRAW_BONUS_CAP)
+    } else {
+        0
+    }
+}
+
+#[inline]
+fn contempt_draw_score(static_eval_side_to_move: i32) -> i32 {
+    // - Non-zero contempt in near-equal positions to avoid passive draw lines.
+    // - Much stronger draw penalties when clearly winning.
+    // - Mild draw-seeking when clearly worse.
+    const CLEAR_WIN_MARGIN: i32 = 180;
+    const CLEAR_LOSS_MARGIN: i32 = -180;
+    const CONTEMPT_CP: i32 = 18;
+    const DRAW_PENALTY_BASE: i32 = 160;
+    const DRAW_PENALTY_SCALE_DIV: i32 = 2;
+    const DRAW_PENALTY_CAP: i32 = 1200;
+    const DRAW_BONUS_BASE: i32 = 45;
+    const DRAW_BONUS_SCALE_DIV: i32 = 8;
+    const DRAW_BONUS_CAP: i32 = 240;
+
+    if static_eval_side_to_move >= CLEAR_WIN_MARGIN {
+        let mut penalty = DRAW_PENALTY_BASE
+            + (static_eval_side_to_move / DRAW_PENALTY_SCALE_DIV).min(DRAW_PENALTY_CAP);
+        if static_eval_side_to_move > 900 {
+            penalty += 200;
+        }
+        return -penalty;
+    }
+
+    if static_eval_side_to_move <= CLEAR_LOSS_MARGIN {
+        let bonus = DRAW_BONUS_BASE
+            + ((-static_eval_side_to_move) / DRAW_BONUS_SCALE_DIV).min(DRAW_BONUS_CAP);
+        return bonus;
+    }
+
+    if static_eval_side_to_move >= 0 {
+        -CONTEMPT_CP
+    } else {
+        CONTEMPT_CP / 2
+    }
+}
+
+#[inline]
+fn child_depth(
+    extensions: EndgameExtensions,
+    depth: u8,
+    game_state: &GameState,
+    allow_check_extension: bool,
+    last_move: u64,
+) -> u8 {
+    let base = depth.saturating_sub(1);
+    if should_extend_endgame_move(
+        extensions,
+        base,
+        game_state,
+        allow_check_extension,
+        last_move,
+    ) {
+        base.saturating_add(1)
+    } else {
+        base
+    }
+}
+
+#[inline]
+fn child_allows_check_extension(
+    extensions: EndgameExtensions,
+    depth: u8,
+    game_state: &GameState,
+    allow_check_extension: bool,
+    last_move: u64,
+) -> bool {
+    allow_check_extension
+        && !should_extend_endgame_move(
+            extensions,
+            depth.saturating_sub(1),
+            game_state,
+            allow_check_extension,
+            last_move,
+        )
+}
+
+#[inline]
+fn should_extend_endgame_move(
+    extensions: EndgameExtensions,
+    base_child_depth: u8,
+    game_state: &GameState,
+    allow_check_extension: bool,
+    last_move: u64,
+) -> bool {
+    if extensions == EndgameExtensions::Off || !allow_check_extension {
+        return false;
+    }
+    if base_child_depth > 1 {
+        return false;
+    }
+    if !is_late_endgame(game_state) {
+        return false;
+    }
+    // After make_move_in_place(), side_to_move has flipped. If that side is in check,
+    // the move that was just made is checking.
+    if is_king_in_check(game_state, game_state.side_to_move) {
+        return true;
+    }
+    if extensions == EndgameExtensions::Checks {
+        return false;
+    }
+
+    let moved_piece = piece_kind_from_code(move_moved_piece_code(last_move));
+    let moved_color = game_state.side_to_move.opposite();
+    let to_sq = move_to(last_move);
+
+    if moved_piece == Some(crate::game_state::chess_types::PieceKind::Pawn)
+        && is_advanced_pawn_square(moved_color, to_sq)
+        && is_passed_pawn_on_square(game_state, moved_color, to_sq)
+    {
         return true;
     }
-    let current = game_state.zobrist_key;
-    let count = game_state
-        .repetition_history
-        .iter()
-        .filter(|h| **h == current)
-        .count();
-    count >= 3
+
+    if is_king_pawn_only_endgame(game_state)
+        && matches!(
+            moved_piece,
+            Some(crate::game_state::chess_types::PieceKind::King)
+                | Some(crate::game_state::chess_types::PieceKind::Pawn)
+        )
+        && king_pawn_race_like_position(game_state, moved_color, to_sq)
+    {
+        return true;
+    }
+
+    false
 }
 
 #[inline]
-fn is_tactical_move(move_description: u64) -> bool {
-    (move_description & (FLAG_CAPTURE | FLAG_EN_PASSANT)) != 0
-        || move_promotion_piece_code(move_description) != NO_PIECE_CODE
+fn is_late_endgame(game_state: &GameState) -> bool {
+    let minor_phase = 1i32;
+    let rook_phase = 2i32;
+    let queen_phase = 4i32;
+    let mut phase = 0i32;
+
+    for color in [
+        crate::game_state::chess_types::Color::Light,
+        crate::game_state::chess_types::Color::Dark,
+    ] {
+        phase += (game_state.pieces[color.index()]
+            [crate::game_state::chess_types::PieceKind::Knight.index()]
+        .count_ones() as i32)
+            * minor_phase;
+        phase += (game_state.pieces[color.index()]
+            [crate::game_state::chess_types::PieceKind::Bishop.index()]
+        .count_ones() as i32)
+            * minor_phase;
+        phase += (game_state.pieces[color.index()]
+            [crate::game_state::chess_types::PieceKind::Rook.index()]
+        .count_ones() as i32)
+            * rook_phase;
+        phase += (game_state.pieces[color.index()]
+            [crate::game_state::chess_types::PieceKind::Queen.index()]
+        .count_ones() as i32)
+            * queen_phase;
+    }
+
+    // Maximum phase here is 24. Treat <= 8 as late endgame.
+    phase <= 8
 }
 
-fn order_moves(moves: &mut [u64], tt_move: Option<u64>) {
-    moves.sort_by_key(|m| -move_order_score(*m, tt_move));
+#[inline]
+fn is_advanced_pawn_square(color: crate::game_state::chess_types::Color, sq: u8) -> bool {
+    let rank = sq / 8;
+    match color {
+        crate::game_state::chess_types::Color::Light => rank >= 5,
+        crate::game_state::chess_types::Color::Dark => rank <= 2,
+    }
 }
 
-fn move_order_score(move_description: u64, tt_move: Option<u64>) -> i32 {
+fn is_passed_pawn_on_square(
+    game_state: &GameState,
+    color: crate::game_state::chess_types::Color,
+    sq: u8,
+) -> bool {
+    let enemy_pawns = game_state.pieces[color.opposite().index()]
+        [crate::game_state::chess_types::PieceKind::Pawn.index()];
+    let file = (sq % 8) as i8;
+    let rank = (sq / 8) as i8;
+
+    for f in [file - 1, file, file + 1] {
+        if !(0..=7).contains(&f) {
+            continue;
+        }
+        match color {
+            crate::game_state::chess_types::Color::Light => {
+                let mut r = rank + 1;
+                while r <= 7 {
+                    let target = (r as u8) * 8 + (f as u8);
+                    if (enemy_pawns & (1u64 << target)) != 0 {
+                        return false;
+                    }
+                    r += 1;
+                }
+            }
+            crate::game_state::chess_types::Color::Dark => {
+                let mut r = rank - 1;
+                while r >= 0 {
+                    let target = (r as u8) * 8 + (f as u8);
+                    if (enemy_pawns & (1u64 << target)) != 0 {
+                        return false;
+                    }
+                    r -= 1;
+                }
+            }
+        }
+    }
+    true
+}
+
+#[inline]
+fn is_king_pawn_only_endgame(game_state: &GameState) -> bool {
+    for color in [
+        crate::game_state::chess_types::Color::Light,
+        crate::game_state::chess_types::Color::Dark,
+    ] {
+        let idx = color.index();
+        if game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Queen.index()] != 0
+            || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Rook.index()] != 0
+            || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Bishop.index()]
+                != 0
+            || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Knight.index()]
+                != 0
+        {
+            return false;
+        }
+    }
+    true
+}
+
+fn king_pawn_race_like_position(
+    game_state: &GameState,
+    moved_color: crate::game_state::chess_types::Color,
+    moved_to_sq: u8,
+) -> bool {
+    let opp = moved_color.opposite();
+    let own_king_bb = game_state.pieces[moved_color.index()]
+        [crate::game_state::chess_types::PieceKind::King.index()];
+    let opp_king_bb =
+        game_state.pieces[opp.index()][crate::game_state::chess_types::PieceKind::King.index()];
+    if own_king_bb == 0 || opp_king_bb == 0 {
+        return false;
+    }
+    let own_king_sq = own_king_bb.trailing_zeros() as u8;
+    let opp_king_sq = opp_king_bb.trailing_zeros() as u8;
+
+    let kings_close = chebyshev_distance(own_king_sq, opp_king_sq) <= 2;
+    let move_near_promo = is_advanced_pawn_square(moved_color, moved_to_sq);
+    kings_close || move_near_promo
+}
+
+#[inline]
+fn chebyshev_distance(a: u8, b: u8) -> i32 {
+    let af = i32::from(a % 8);
+    let ar = i32::from(a / 8);
+    let bf = i32::from(b % 8);
+    let br = i32::from(b / 8);
+    (af - bf).abs().max((ar - br).abs())
+}
+
+fn order_moves(
+    moves: &mut [u64],
+    tt_move: Option<u64>,
+    prev_move: Option<u64>,
+    killers: [u64; 2],
+    heuristics: &SearchHeuristics,
+    side_to_move: crate::game_state::chess_types::Color,
+) {
+    if moves.len() < 2 {
+        return;
+    }
+    moves.sort_unstable_by_key(|m| {
+        -move_order_score(*m, tt_move, prev_move, killers, heuristics, side_to_move)
+    });
+}
+
+fn order_moves_basic(moves: &mut [u64], tt_move: Option<u64>) {
+    if moves.len() < 2 {
+        return;
+    }
+    moves.sort_unstable_by_key(|m| -move_order_score_basic(*m, tt_move));
+}
+
+fn move_order_score(
+    move_description: u64,
+    tt_move: Option<u64>,
+    prev_move: Option<u64>,
+    killers: [u64; 2],
+    heuristics: &SearchHeuristics,
+    side_to_move: crate::game_state::chess_types::Color,
+) -> i32 {
+    let mut score = move_order_score_basic(move_description, tt_move);
+    if is_quiet_move(move_description) {
+        if move_description == killers[0] {
+            score += 80_000;
+        } else if move_description == killers[1] {
+            score += 70_000;
+        }
+
+        if let Some(piece) = piece_kind_from_code(move_moved_piece_code(move_description)) {
+            let to = move_to_square(move_description);
+            score += heuristics.history[side_to_move.index()][piece.index()][to] / 2;
+            score += heuristics.continuation_bonus(side_to_move, prev_move, piece, to) / 2;
+        }
+
+        if heuristics.is_countermove(prev_move, move_description) {
+            score += 60_000;
+        }
+    }
+    score
+}
+
+/// Quiet-move ranking for the staged picker (killers have their own stage).
+fn quiet_move_order_score(
+    move_description: u64,
+    prev_move: Option<u64>,
+    heuristics: &SearchHeuristics,
+    side_to_move: crate::game_state::chess_types::Color,
+) -> i32 {
+    let mut score = 0i32;
+    if let Some(piece) = piece_kind_from_code(move_moved_piece_code(move_description)) {
+        let to = move_to_square(move_description);
+        score += heuristics.history[side_to_move.index()][piece.index()][to] / 2;
+        score += heuristics.continuation_bonus(side_to_move, prev_move, piece, to) / 2;
+    }
+    if heuristics.is_countermove(prev_move, move_description) {
+        score += 60_000;
+    }
+    score
+}
+
+fn move_order_score_basic(move_description: u64, tt_move: Option<u64>) -> i32 {
     if Some(move_description) == tt_move {
         return 1_000_000;
     }
     let mut score = 0i32;
     if (move_description & (FLAG_CAPTURE | FLAG_EN_PASSANT)) != 0 {
-        let victim = piece_kind_from_code(move_captured_piece_code(move_description))
+        let victim = capture_value(move_description);
+        let aggressor = piece_kind_from_code(move_moved_piece_code(move_description))
             .map(piece_value)
             .unwrap_or(100);
-        score += 100_000 + victim;
+        let see = static_exchange_estimate(move_description);
+        // Tactical ordering blend:
+        // - MVV/LVA preference (high victim, low aggressor)
+        // - SEE bonus to push likely winning captures earlier
+        score += 100_000 + (victim * 16) - aggressor + (see * 4);
     }
     if move_promotion_piece_code(move_description) != NO_PIECE_CODE {
-        score += 90_000;
+        let promo_value = piece_kind_from_code(move_promotion_piece_code(move_description))
+            .map(piece_value)
+            .unwrap_or(0);
+        score += 90_000 + promo_value;
     }
     score
 }
 
+#[inline]
+fn passes_quiescence_pruning(
+    mode: SeePruning,
+    move_description: u64,
+    stand_pat: i32,
+    alpha: i32,
+    qply: u8,
+    game_state: &GameState,
+) -> bool {
+    if move_promotion_piece_code(move_description) != NO_PIECE_CODE {
+        return true;
+    }
+
+    let margin = quiescence_delta_margin(mode, qply, game_state);
+    let max_gain = capture_value(move_description) + promotion_gain(move_description);
+    if stand_pat + max_gain + margin < alpha {
+        return false;
+    }
+
+    if (move_description & (FLAG_CAPTURE | FLAG_EN_PASSANT)) != 0
+        && static_exchange_estimate(move_description)
+            < see_bad_capture_threshold(mode, qply, game_state)
+    {
+        return false;
+    }
+
+    true
+}
+
+#[inline]
+fn see_bad_capture_threshold(mode: SeePruning, qply: u8, game_state: &GameState) -> i32 {
+    if mode == SeePruning::Fixed {
+        return SEE_BAD_CAPTURE_THRESHOLD;
+    }
+    let mut threshold = SEE_BAD_CAPTURE_THRESHOLD + (i32::from(qply) * 20);
+    if mode != SeePruning::EndgameAware {
+        return threshold;
+    }
+    if is_critical_endgame(game_state) {
+        // Endgames are tactical and tempo-sensitive: allow more speculative captures.
+        threshold -= 90;
+    } else if is_late_endgame(game_state) {
+        threshold -= 40;
+    }
+    threshold
+}
+
+#[inline]
+fn quiescence_delta_margin(mode: SeePruning, qply: u8, game_state: &GameState) -> i32 {
+    if mode == SeePruning::Fixed {
+        return QUIESCENCE_DELTA_MARGIN;
+    }
+    let mut margin = QUIESCENCE_DELTA_MARGIN.saturating_sub(i32::from(qply) * 10);
+    if mode != SeePruning::EndgameAware {
+        return margin;
+    }
+    if is_critical_endgame(game_state) {
+        // Increase margin to reduce over-pruning in king/pawn races.
+        margin += 100;
+    } else if is_late_endgame(game_state) {
+        margin += 50;
+    }
+    margin
+}
+
+fn append_quiescence_check_moves(game_state: &GameState, moves: &mut MoveList) {
+    // Captures and quiet checks are disjoint, so no de-duplication is needed.
+    generate_quiet_checks(game_state, moves);
+}
+
+#[inline]
+fn capture_value(move_description: u64) -> i32 {
+    piece_kind_from_code(move_captured_piece_code(move_description))
+        .map(piece_value)
+        .unwrap_or(0)
+}
+
+#[inline]
+fn promotion_gain(move_description: u64) -> i32 {
+    piece_kind_from_code(move_promotion_piece_code(move_description))
+        .map(|p| piece_value(p) - piece_value(crate::game_state::chess_types::PieceKind::Pawn))
+        .unwrap_or(0)
+}
+
+#[inline]
+fn static_exchange_estimate(move_description: u64) -> i32 {
+    let victim = capture_value(move_description);
+    let attacker = piece_kind_from_code(move_moved_piece_code(move_description))
+        .map(piece_value)
+        .unwrap_or(100);
+    victim + promotion_gain(move_description) - attacker
+}
+
+#[inline]
+fn move_to_square(move_description: u64) -> usize {
+    crate::moves::move_descriptions::move_to(move_description) as usize
+}
+
+#[inline]
+fn is_quiet_move(move_description: u64) -> bool {
+    (move_description & (FLAG_CAPTURE | FLAG_EN_PASSANT)) == 0
+        && move_promotion_piece_code(move_description) == NO_PIECE_CODE
+}
+
+#[inline]
+fn lmr_reduction(
+    schedule: LateMoveReductions,
+    depth: u8,
+    move_index: usize,
+    is_quiet: bool,
+    in_check: bool,
+) -> u8 {
+    if schedule == LateMoveReductions::Off || !is_quiet || in_check || depth < 3 || move_index < 3 {
+        return 0;
+    }
+    if schedule == LateMoveReductions::Deep {
+        if depth >= 9 && move_index >= 12 {
+            return 3;
+        } else if depth >= 7 && move_index >= 7 {
+            return 2;
+        } else if depth >= 5 && move_index >= 5 {
+            return 1;
+        }
+    }
+    if depth >= 6 && move_index >= 8 {
+        2
+    } else {
+        1
+    }
+}
+
+#[inline]
+fn should_lmp_prune(
+    schedule: LateMovePruning,
+    depth: u8,
+    move_index: usize,
+    alpha: i32,
+    best: i32,
+    game_state: &GameState,
+) -> bool {
+    if schedule == LateMovePruning::Off {
+        return false;
+    }
+    if schedule == LateMovePruning::Tight && is_critical_endgame(game_state) {
+        // Avoid pruning quiet king/pawn moves in critical endgames.
+        return false;
+    }
+    if depth > 3 {
+        return false;
+    }
+    // Only prune once we have some evidence a good move already exists.
+    if best <= -MATE_SCORE + 2000 || alpha <= -MATE_SCORE + 2000 {
+        return false;
+    }
+
+    let threshold = match (schedule, depth) {
+        (LateMovePruning::Tight, 0 | 1) => 3,
+        (LateMovePruning::Tight, 2) => 6,
+        (LateMovePruning::Tight, 3) => 10,
+        (_, 0 | 1) => 4,
+        (_, 2) => 8,
+        (_, 3) => 12,
+        _ => usize::MAX,
+    };
+
+    move_index >= threshold
+}
+
+#[inline]
+fn should_reverse_futility_prune(
+    depth: u8,
+    in_check: bool,
+    alpha: i32,
+    beta: i32,
+    static_eval: i32,
+    game_state: &GameState,
+) -> bool {
+    if in_check || depth > 2 {
+        return false;
+    }
+    // Restrict to non-PV-like narrow windows.
+    if beta.saturating_sub(alpha) > 1 {
+        return false;
+    }
+    // Avoid tactical instability near mate bounds.
+    if beta > (MATE_SCORE - 2000) || alpha < (-MATE_SCORE + 2000) {
+        return false;
+    }
+    // Be conservative in sensitive endgames.
+    if is_critical_endgame(game_state) {
+        return false;
+    }
+    let margin = match depth {
+        0 => 0,
+        1 => 110,
+        _ => 210,
+    };
+    static_eval - margin >= beta
+}
+
+#[inline]
+fn is_critical_endgame(game_state: &GameState) -> bool {
+    is_king_pawn_only_endgame(game_state) || is_late_endgame(game_state)
+}
+
+#[inline]
+fn should_try_null_move(
+    null_move: NullMovePruning,
+    depth: u8,
+    in_check: bool,
+    beta: i32,
+    game_state: &GameState,
+) -> bool {
+    let NullMovePruning::On { min_depth, .. } = null_move else {
+        return false;
+    };
+    if in_check || depth < min_depth {
+        return false;
+    }
+    if beta > (MATE_SCORE - 1000) {
+        return false;
+    }
+    if is_late_endgame(game_state) {
+        return false;
+    }
+    has_non_pawn_material(game_state, game_state.side_to_move)
+}
+
+#[inline]
+fn should_verify_null_cutoff(verify_depth: Option<u8>, depth: u8, in_check: bool) -> bool {
+    verify_depth.is_some_and(|min| !in_check && depth >= min)
+}
+
+#[inline]
+fn has_non_pawn_material(
+    game_state: &GameState,
+    color: crate::game_state::chess_types::Color,
+) -> bool {
+    let idx = color.index();
+    game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Knight.index()] != 0
+        || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Bishop.index()] != 0
+        || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Rook.index()] != 0
+        || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Queen.index()] != 0
+}
+
+type HistoryTable = [[[i32; 64]; 6]; 2];
+type CounterMoveTable = [[u64; 64]; 6];
+type ContinuationHistoryTable = [[[[[i32; 64]; 6]; 64]; 6]; 2];
+
+#[derive(Debug, Clone)]
+struct SearchHeuristics {
+    killers: [[u64; 2]; MAX_PLY],
+    history: HistoryTable,
+    countermove: CounterMoveTable,
+    continuation_history: Box<ContinuationHistoryTable>,
+    seldepth: u8,
+    /// TT move left out of the node at each ply during a singular-extension
+    /// verification search; zero when none.
+    excluded: [u64; MAX_PLY],
+    /// Double singular extensions on the line being searched.
+    double_extensions: u8,
+    /// Depth of the current iteration; singular extensions stop at twice it.
+    root_depth: u8,
+    /// Lazy SMP helper id; rotates root moves after the first so helpers
+    /// explore the root in different orders. Zero keeps the ordered list.
+    root_order_offset: usize,
+}
+
+impl Default for SearchHeuristics {
+    fn default() -> Self {
+        Self {
+            killers: [[0; 2]; MAX_PLY],
+            history: [[[0; 64]; 6]; 2],
+            countermove: [[0; 64]; 6],
+            continuation_history: Box::new([[[[[0; 64]; 6]; 64]; 6]; 2]),
+            seldepth: 0,
+            excluded: [0; MAX_PLY],
+            double_extensions: 0,
+            root_depth: 0,
+            root_order_offset: 0,
+        }
+    }
+}
+
+impl SearchHeuristics {
+    fn reset_iteration(&mut self, root_depth: u8) {
+        self.killers.fill([0; 2]);
+        self.seldepth = 0;
+        self.excluded.fill(0);
+        self.double_extensions = 0;
+        self.root_depth = root_depth;
+    }
+
+    #[inline]
+    fn record_seldepth(&mut self, ply: u8) {
+        self.seldepth = self.seldepth.max(ply);
+    }
+
+    fn killers_at(&self, ply: usize) -> [u64; 2] {
+        self.killers[ply]
+    }
+
+    fn record_killer(&mut self, ply: usize, mv: u64) {
+        if self.killers[ply][0] == mv {
+            return;
+        }
+        self.killers[ply][1] = self.killers[ply][0];
+        self.killers[ply][0] = mv;
+    }
+
+    fn record_history(&mut self, side: crate::game_state::chess_types::Color, mv: u64, depth: u8) {
+        let Some(piece) = piece_kind_from_code(move_moved_piece_code(mv)) else {
+            return;
+        };
+        let to = move_to_square(mv);
+        let bonus = i32::from(depth) * i32::from(depth);
+        let entry = &mut self.history[side.index()][piece.index()][to];
+        *entry = (*entry + bonus).min(50_000);
+    }
+
+    fn record_countermove(&mut self, prev_move: Option<u64>, mv: u64) {
+        let Some((prev_piece, prev_to)) = move_meta(prev_move) else {
+            return;
+        };
+        self.countermove[prev_piece.index()][prev_to] = mv;
+    }
+
+    fn is_countermove(&self, prev_move: Option<u64>, mv: u64) -> bool {
+        let Some((prev_piece, prev_to)) = move_meta(prev_move) else {
+            return false;
+        };
+        self.countermove[prev_piece.index()][prev_to] == mv
+    }
+
+    fn record_continuation(
+        &mut self,
+        side: crate::game_state::chess_types::Color,
+        prev_move: Option<u64>,
+        mv: u64,
+        depth: u8,
+    ) {
+        let Some((prev_piece, prev_to)) = move_meta(prev_move) else {
+            return;
+        };
+        let Some((piece, to)) = move_meta(Some(mv)) else {
+            return;
+        };
+        let bonus = i32::from(depth) * i32::from(depth);
+        let entry = &mut self.continuation_history[side.index()][prev_piece.index()][prev_to]
+            [piece.index()][to];
+        *entry = (*entry + bonus).min(50_000);
+    }
+
+    fn continuation_bonus(
+        &self,
+        side: crate::game_state::chess_types::Color,
+        prev_move: Option<u64>,
+        piece: crate::game_state::chess_types::PieceKind,
+        to: usize,
+    ) -> i32 {
+        let Some((prev_piece, prev_to)) = move_meta(prev_move) else {
+            return 0;
+        };
+        self.continuation_history[side.index()][prev_piece.index()][prev_to][piece.index()][to]
+    }
+}
+
+#[inline]
+fn move_meta(
+    move_description: Option<u64>,
+) -> Option<(crate::game_state::chess_types::PieceKind, usize)> {
+    let mv = move_description?;
+    let piece = piece_kind_from_code(move_moved_piece_code(mv))?;
+    Some((piece, move_to_square(mv)))
+}
+
 #[inline]
 fn piece_value(piece: crate::game_state::chess_types::PieceKind) -> i32 {
     match piece {
@@ -471,9 +2219,140 @@ fn piece_value(piece: crate::game_state::chess_types::PieceKind) -> i32 {
     }
 }
 
+/// Turns root-level search milestones into `SearchObserver` reports.
+/// Every method is a no-op when no observer is configured.
+struct SearchReporter<'a> {
+    observer: Option<&'a dyn SearchObserver>,
+    started_at: Instant,
+    depth: u8,
+    /// Nodes from completed iterations; root node counters restart per depth.
+    base_nodes: u64,
+    next_progress: u64,
+    reported_best: Option<u64>,
+}
+
+impl<'a> SearchReporter<'a> {
+    fn new(observer: Option<&'a dyn SearchObserver>, started_at: Instant) -> Self {
+        Self {
+            observer,
+            started_at,
+            depth: 0,
+            base_nodes: 0,
+            next_progress: PROGRESS_NODE_INTERVAL,
+            reported_best: None,
+        }
+    }
+
+    fn start_iteration(&mut self, depth: u8, base_nodes: u64) {
+        self.depth = depth;
+        self.base_nodes = base_nodes;
+    }
+
+    fn progress(
+        &mut self,
+        iteration_nodes: u64,
+        mv: u64,
+        move_number: usize,
+        heuristics: &SearchHeuristics,
+        tt: &impl SearchTable,
+    ) {
+        let Some(observer) = self.observer else {
+            return;
+        };
+        let nodes = self.base_nodes.saturating_add(iteration_nodes);
+        if nodes < self.next_progress {
+            return;
+        }
+        self.next_progress = nodes.saturating_add(PROGRESS_NODE_INTERVAL);
+        let mut info = self.snapshot(nodes, 0, heuristics, tt);
+        info.currmove = Some(mv);
+        info.currmovenumber = Some(move_number);
+        observer.on_progress(&info);
+    }
+
+    fn new_best_move(
+        &mut self,
+        root: &GameState,
+        mv: u64,
+        score: i32,
+        iteration_nodes: u64,
+        heuristics: &SearchHeuristics,
+        tt: &mut impl SearchTable,
+    ) {
+        let Some(observer) = self.observer else {
+            return;
+        };
+        if self.reported_best == Some(mv) {
+            return;
+        }
+        self.reported_best = Some(mv);
+        let nodes = self.base_nodes.saturating_add(iteration_nodes);
+        let mut info = self.snapshot(nodes, score, heuristics, tt);
+        info.pv = root_move_pv(root, mv, tt, self.depth);
+        observer.on_best_move(&info);
+    }
+
+    fn iteration_complete(
+        &mut self,
+        root: &GameState,
+        best_move: Option<u64>,
+        score: i32,
+        nodes: u64,
+        heuristics: &SearchHeuristics,
+        tt: &mut impl SearchTable,
+    ) {
+        let Some(observer) = self.observer else {
+            return;
+        };
+        self.reported_best = best_move;
+        let mut info = self.snapshot(nodes, score, heuristics, tt);
+        if let Some(mv) = best_move {
+            info.pv = root_move_pv(root, mv, tt, self.depth);
+        }
+        observer.on_iteration(&info);
+    }
+
+    fn snapshot(
+        &self,
+        nodes: u64,
+        score: i32,
+        heuristics: &SearchHeuristics,
+        tt: &impl SearchTable,
+    ) -> SearchInfo {
+        let elapsed_ms = self.started_at.elapsed().as_millis() as u64;
+        SearchInfo {
+            depth: self.depth,
+            seldepth: heuristics.seldepth.max(self.depth),
+            score,
+            pv: Vec::new(),
+            nodes,
+            elapsed_ms,
+            nps: nodes
+                .saturating_mul(1000)
+                .checked_div(elapsed_ms)
+                .unwrap_or(0),
+            hashfull: tt.stats().hashfull,
+            currmove: None,
+            currmovenumber: None,
+        }
+    }
+}
+
+/// `mv` followed by the TT line of the resulting position. The root itself is
+/// never stored, so the continuation is read from the child.
+fn root_move_pv(root: &GameState, mv: u64, tt: &mut impl SearchTable, depth: u8) -> Vec<u64> {
+    let mut child = root.clone();
+    if make_move_in_place(&mut child, mv).is_err() {
+        return vec![mv];
+    }
+    let mut pv = vec![mv];
+    pv.extend(principal_variation_from_tt(&child, tt, depth.saturating_sub(1)).moves);
+    pv
+}
+
 pub fn principal_variation_from_tt(
     game_state: &GameState,
-    tt: &mut TranspositionTable,
+    tt: &mut impl SearchTable,
     max_depth: u8,
 ) -> PrincipalVariation {
     let mut pv = PrincipalVariation::default();
@@ -493,11 +2372,9 @@ pub fn principal_variation_from_tt(
             break;
         }
         pv.moves.push(best_move);
-        let Ok(next) = crate::move_generation::legal_move_apply::apply_move(&state, best_move)
-        else {
+        if make_move_in_place(&mut state, best_move).is_err() {
             break;
-        };
-        state = next;
+        }
     }
 
     pv
@@ -513,8 +2390,21 @@ mod tests {
     use crate::search::board_scoring::MaterialScorer;
     use crate::utils::long_algebraic::move_description_to_long_algebraic;
 
-    use super::{iterative_deepening_search, SearchConfig};
+    use super::{
+        draw_score, helper_skips_depth, iterative_deepening_search, lazy_smp_search,
+        make_move_in_place, negamax, singular_candidate, tt_score_for_storage,
+        tt_score_from_storage, NodeParams, SearchConfig, SearchHeuristics, StopSignal, MATE_SCORE,
+    };
     use crate::game_state::game_state::GameState;
+    use crate::search::search_features::{
+        DrawScore, ProbCut, SearchFeatures, SingularExtensions, EXACT_PRESET_VERSIONS,
+        PRESET_VERSIONS,
+    };
+    use crate::search::search_observer::SearchEvent;
+    use crate::search::threading::SharedTranspositionTable;
+    use crate::search::transposition_table_v11::{Bound, TTEntry, TranspositionTable};
+    use crate::utils::long_algebraic::long_algebraic_to_move_description;
+    use std::sync::{mpsc, Arc};
 
     #[test]
     fn search_depth_zero_returns_eval_only() {
@@ -538,6 +2428,71 @@ mod tests {
         assert_eq!(result.reached_depth, 0);
     }
 
+    #[test]
+    fn observer_receives_each_completed_iteration_with_pv() {
+        let game = GameState::new_game();
+        let (tx, rx) = mpsc::channel::<SearchEvent>();
+        let result = iterative_deepening_search(
+            &game,
+            &LegalMoveGenerator,
+            &MaterialScorer,
+            SearchConfig {
+                max_depth: 4,
+                observer: Some(Arc::new(tx)),
+                ..SearchConfig::default()
+            },
+        )
+        .expect("search should run");
+
+        let iterations: Vec<_> = rx
+            .try_iter()
+            .filter_map(|event| match event {
+                SearchEvent::Iteration(info) => Some(info),
+                SearchEvent::BestMove(info) => {
+                    assert!(!info.pv.is_empty());
+                    None
+                }
+                SearchEvent::Progress(info) => {
+                    assert!(info.currmove.is_some());
+                    assert!(matches!(info.currmovenumber, Some(1..=20)));
+                    None
+                }
+            })
+            .collect();
+        let depths: Vec<u8> = iterations.iter().map(|info| info.depth).collect();
+        assert_eq!(depths, vec![1, 2, 3, 4]);
+        assert!(iterations.windows(2).all(|w| w[0].nodes < w[1].nodes));
+
+        let last = iterations.last().expect("depth 4 report");
+        assert_eq!(last.pv.first().copied(), result.best_move);
+        assert_eq!(last.score, result.best_score);
+        assert_eq!(last.nodes, result.nodes);
+        assert!(last.seldepth >= 4);
+        assert!(last.hashfull <= 1000);
+    }
+
+    #[test]
+    fn seldepth_includes_quiescence_plies() {
+        let game = GameState::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .expect("FEN should parse");
+        let result = iterative_deepening_search(
+            &game,
+            &LegalMoveGenerator,
+            &MaterialScorer,
+            SearchConfig {
+                max_depth: 2,
+                ..SearchConfig::default()
+            },
+        )
+        .expect("search should run");
+
+        assert_eq!(result.reached_depth, 2);
+        assert!(result.seldepth > result.reached_depth);
+        assert_eq!(result.hashfull, result.tt_stats.hashfull);
+    }
+
     #[test]
     fn search_prefers_winning_capture_in_simple_position() {
         let game =
@@ -563,6 +2518,200 @@ mod tests {
         assert_eq!(lan, "f1e2");
     }
 
+    #[test]
+    fn singular_candidate_needs_deep_lower_bound_tt_move() {
+        let entry = TTEntry {
+            key: 1,
+            depth: 5,
+            score: 40,
+            bound: Bound::Lower,
+            best_move: Some(99),
+        };
+        let candidate = |entry: TTEntry, depth: u8, ply: u8, excluded: Option<u64>| {
+            singular_candidate(Some(entry), depth, 4, ply, 10, excluded)
+        };
+        assert_eq!(candidate(entry, 8, 3, None), Some((99, 40)));
+        assert_eq!(candidate(entry, 9, 3, None), None);
+        assert_eq!(candidate(entry, 3, 3, None), None);
+        assert_eq!(candidate(entry, 8, 20, None), None);
+        assert_eq!(candidate(entry, 8, 3, Some(7)), None);
+        let upper = TTEntry {
+            bound: Bound::Upper,
+            ..entry
+        };
+        assert_eq!(candidate(upper, 8, 3, None), None);
+        let mate = TTEntry {
+            score: MATE_SCORE - 5,
+            ..entry
+        };
+        assert_eq!(candidate(mate, 8, 3, None), None);
+        let no_move = TTEntry {
+            best_move: None,
+            ..entry
+        };
+        assert_eq!(candidate(no_move, 8, 3, None), None);
+    }
+
+    /// One `negamax` node at depth 4 with a depth-3 lower-bound TT entry for
+    /// `tt_lan` and only singular extensions (plus fail-soft) switched on.
+    /// Returns the node score, the depth the TT move's child was stored at
+    /// (3 without an extension) and the double extension count afterwards.
+    fn singular_node(
+        fen: &str,
+        tt_lan: &str,
+        tt_score: i32,
+        window: (i32, i32),
+        max_double: u8,
+        double_extensions: u8,
+    ) -> (Option<i32>, Option<u8>, u8) {
+        let mut game = GameState::from_fen(fen).expect("FEN should parse");
+        let tt_move = long_algebraic_to_move_description(tt_lan, &game).expect("legal move");
+        let mut tt = TranspositionTable::new_with_mb(1);
+        tt.store(TTEntry {
+            key: game.zobrist_key,
+            depth: 3,
+            score: tt_score,
+            bound: Bound::Lower,
+            best_move: Some(tt_move),
+        });
+        let mut heuristics = SearchHeuristics {
+            double_extensions,
+            root_depth: 4,
+            ..SearchHeuristics::default()
+        };
+        let features = SearchFeatures {
+            singular_extensions: SingularExtensions::On {
+                min_depth: 4,
+                double_margin: 24,
+                max_double,
+            },
+            // Fail-hard verification returns singular_beta - 1 at best, which
+            // never clears the double-extension margin.
+            fail_soft: true,
+            ..SearchFeatures::baseline()
+        };
+        let mut nodes = 0;
+        let score = negamax(
+            &mut game,
+            &MaterialScorer,
+            NodeParams {
+                depth: 4,
+                alpha: window.0,
+                beta: window.1,
+                ply: 1,
+                allow_check_extension: true,
+                allow_null_pruning: true,
+                prev_move: None,
+            },
+            &mut nodes,
+            None,
+            None,
+            StopSignal {
+                flag: None,
+                smp: None,
+            },
+            &mut tt,
+            &mut heuristics,
+            &features,
+        )
+        .expect("search should run");
+
+        make_move_in_place(&mut game, tt_move).expect("make TT move");
+        let child_depth = tt.probe(game.zobrist_key).map(|entry| entry.depth);
+        (score, child_depth, heuristics.double_extensions)
+    }
+
+    #[test]
+    fn singular_tt_move_is_extended_within_the_double_extension_cap() {
+        // Rxd5+ is the only move that does not lose the rook for nothing.
+        let fen = "3k4/8/8/3q4/8/8/8/3RK3 w - - 0 1";
+        let full = (-MATE_SCORE, MATE_SCORE);
+
+        // Alternatives fall far below singular_beta: double extension.
+        let (_, child_depth, doubles) = singular_node(fen, "d1d5", 500, full, 4, 0);
+        assert_eq!(child_depth, Some(5));
+        assert_eq!(doubles, 0);
+
+        // The line already used its double extensions: one ply only.
+        let (_, child_depth, doubles) = singular_node(fen, "d1d5", 500, full, 1, 1);
+        assert_eq!(child_depth, Some(4));
+        assert_eq!(doubles, 1);
+    }
+
+    #[test]
+    fn multi_cut_returns_singular_beta_when_alternatives_fail_high() {
+        // Bxd5 and Rxd5+ both win the queen, so excluding the TT move still
+        // beats singular_beta = 830 - 2 * 4, which is above beta.
+        let fen = "3k4/8/8/3q4/8/8/6B1/3RK3 w - - 0 1";
+        let (score, _, _) = singular_node(fen, "g2d5", 830, (499, 500), 4, 0);
+        assert_eq!(score, Some(830 - 2 * 4));
+    }
+
+    #[test]
+    fn singular_extensions_reshape_tree_but_keep_forced_win() {
+        let search = |fen: &str, singular_extensions: SingularExtensions| {
+            let game = GameState::from_fen(fen).expect("FEN should parse");
+            let result = iterative_deepening_search(
+                &game,
+                &LegalMoveGenerator,
+                &MaterialScorer,
+                SearchConfig {
+                    max_depth: 6,
+                    features: SearchFeatures {
+                        singular_extensions,
+                        ..SearchFeatures::v15()
+                    },
+                    ..SearchConfig::default()
+                },
+            )
+            .expect("search should run");
+            let lan = result
+                .best_move
+                .and_then(|mv| move_description_to_long_algebraic(mv, &game).ok());
+            (lan, result.nodes)
+        };
+
+        let kiwipete = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let (_, nodes_off) = search(kiwipete, SingularExtensions::Off);
+        let (_, nodes_on) = search(kiwipete, SingularExtensions::DEFAULT_ON);
+        assert_ne!(nodes_on, nodes_off);
+
+        let hanging_queen = "4k3/8/8/8/8/8/4q3/4KQ2 w - - 0 1";
+        let (best, _) = search(hanging_queen, SingularExtensions::DEFAULT_ON);
+        assert_eq!(best.as_deref(), Some("f1e2"));
+    }
+
+    #[test]
+    fn probcut_below_four_plies_does_not_underflow() {
+        let fens = [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/8/8/8/8/8/4q3/4KQ2 w - - 0 1",
+        ];
+        for fen in fens {
+            let game = GameState::from_fen(fen).expect("FEN should parse");
+            let result = iterative_deepening_search(
+                &game,
+                &LegalMoveGenerator,
+                &MaterialScorer,
+                SearchConfig {
+                    max_depth: 5,
+                    features: SearchFeatures {
+                        probcut: ProbCut::On {
+                            min_depth: 2,
+                            margin: 0,
+                        },
+                        ..SearchFeatures::v15()
+                    },
+                    ..SearchConfig::default()
+                },
+            )
+            .expect("search should run");
+            let best = result.best_move.expect("position has legal moves");
+            assert!(move_description_to_long_algebraic(best, &game).is_ok());
+        }
+    }
+
     #[test]
     fn search_finds_mate_in_one_at_depth_one() {
         use crate::move_generation::legal_move_apply::apply_move;
@@ -597,4 +2746,343 @@ mod tests {
             result.best_score
         );
     }
+
+    #[test]
+    fn search_respects_node_cap() {
+        let game = GameState::new_game();
+        let gen = LegalMoveGenerator;
+        let scorer = MaterialScorer;
+
+        let result = iterative_deepening_search(
+            &game,
+            &gen,
+            &scorer,
+            SearchConfig {
+                max_depth: 8,
+                max_nodes: Some(200),
+                ..SearchConfig::default()
+            },
+        )
+        .expect("search should run");
+
+        assert!(result.nodes <= 200, "nodes exceeded cap: {}", result.nodes);
+    }
+
+    #[test]
+    fn lazy_smp_reaches_requested_depth_with_legal_move() {
+        let game = GameState::from_fen(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        )
+        .expect("FEN should parse");
+        let tt = SharedTranspositionTable::new_with_mb(8);
+        let (result, thread) = lazy_smp_search(
+            &game,
+            &LegalMoveGenerator,
+            &MaterialScorer,
+            SearchConfig {
+                max_depth: 4,
+                ..SearchConfig::default()
+            },
+            &tt,
+            3,
+        )
+        .expect("lazy smp should run");
+
+        assert_eq!(result.reached_depth, 4);
+        assert!(thread <= 3);
+        let best = result.best_move.expect("best move");
+        let mut probe = game.clone();
+        let legal =
+            crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place(
+                &mut probe,
+            )
+            .expect("legal moves");
+        assert!(legal.contains(&best));
+        assert_eq!(result.pv.first(), Some(&best));
+    }
+
+    #[test]
+    fn lazy_smp_shares_node_budget_across_threads() {
+        let game = GameState::new_game();
+        let tt = SharedTranspositionTable::new_with_mb(4);
+        let (result, _) = lazy_smp_search(
+            &game,
+            &LegalMoveGenerator,
+            &MaterialScorer,
+            SearchConfig {
+                max_depth: 32,
+                max_nodes: Some(20_000),
+                ..SearchConfig::default()
+            },
+            &tt,
+            3,
+        )
+        .expect("lazy smp should run");
+
+        // Each thread may overshoot by one flush batch before it sees the stop.
+        assert!(result.nodes <= 20_000 + 4 * 1024, "nodes {}", result.nodes);
+        assert!(result.reached_depth < 32);
+    }
+
+    #[test]
+    fn lazy_smp_sums_tt_counts_over_threads() {
+        let game = GameState::new_game();
+        let tt = SharedTranspositionTable::new_with_mb(4);
+        let config = SearchConfig {
+            max_depth: 5,
+            ..SearchConfig::default()
+        };
+        let (single, _) = lazy_smp_search(
+            &game,
+            &LegalMoveGenerator,
+            &MaterialScorer,
+            config.clone(),
+            &tt,
+            0,
+        )
+        .expect("lazy smp should run");
+        let stats = single.tt_stats;
+        assert!(stats.probes > 0 && stats.stores > 0);
+        assert!(stats.hits > 0 && stats.hits <= stats.probes);
+
+        tt.clear();
+        let (smp, _) = lazy_smp_search(&game, &LegalMoveGenerator, &MaterialScorer, config, &tt, 2)
+            .expect("lazy smp should run");
+        assert!(smp.tt_stats.probes > 0);
+        assert!(smp.tt_stats.hits <= smp.tt_stats.probes);
+        assert_eq!(smp.hashfull, smp.tt_stats.hashfull);
+    }
+
+    #[test]
+    fn helper_depth_skips_are_staggered() {
+        for depth in 1..=12 {
+            assert!(!helper_skips_depth(0, depth, 12));
+        }
+        assert!(!helper_skips_depth(1, 1, 12));
+        assert!(!helper_skips_depth(1, 12, 12));
+        let pattern = |thread| {
+            (2..12)
+                .map(|depth| helper_skips_depth(thread, depth, 12))
+                .collect::<Vec<_>>()
+        };
+        assert_ne!(pattern(1), pattern(2));
+        assert!(pattern(1).contains(&true) && pattern(1).contains(&false));
+    }
+
+    const FIXTURE_FENS: [&str; 4] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    ];
+    const FIXTURE_DEPTH: u8 = 4;
+
+    /// (version, `FIXTURE_FENS` index, best move, score, nodes) from each
+    /// legacy `iterative_deepening_vN` search with `MaterialScorer` at
+    /// `FIXTURE_DEPTH`, captured before the forks were folded into this core.
+    const LEGACY_FIXTURES: [(u8, usize, &str, i32, u64); 48] = [
+        (3, 0, "a2a3", 0, 2289),
+        (3, 1, "e2a6", 10, 63017),
+        (3, 2, "b2b3", 0, 221484),
+        (3, 3, "b4f4", 100, 2141),
+        (4, 0, "b2b3", 0, 1539),
+        (4, 1, "e2a6", 10, 55081),
+        (4, 2, "c3d5", 0, 83496),
+        (4, 3, "b4f4", 100, 1869),
+        (5, 0, "b2b3", 0, 309),
+        (5, 1, "e2a6", 10, 201654),
+        (5, 2, "c3d5", 0, 20871),
+        (5, 3, "b4f4", 100, 1699),
+        (6, 0, "b2b3", 0, 309),
+        (6, 1, "e2a6", 10, 201654),
+        (6, 2, "c3d5", 0, 20871),
+        (6, 3, "b4f4", 100, 2861),
+        (7, 0, "b2b3", 0, 309),
+        (7, 1, "e2a6", 10, 201654),
+        (7, 2, "c3d5", 0, 16988),
+        (7, 3, "b4f4", 100, 2306),
+        (8, 0, "b2b3", 0, 302),
+        (8, 1, "e2a6", 10, 6569),
+        (8, 2, "c3d5", 0, 6977),
+        (8, 3, "b4f4", 0, 3529),
+        (9, 0, "b2b3", 0, 138),
+        (9, 1, "e2a6", 10, 2910),
+        (9, 2, "c3d5", 250, 1103),
+        (9, 3, "b4f4", 0, 1811),
+        (10, 0, "b2b3", 0, 138),
+        (10, 1, "e2a6", 10, 2910),
+        (10, 2, "c3d5", 250, 1103),
+        (10, 3, "b4f4", 0, 1811),
+        (11, 0, "b2b3", 0, 138),
+        (11, 1, "e2a6", 10, 2910),
+        (11, 2, "c3d5", 250, 1103),
+        (11, 3, "b4f4", 0, 1811),
+        (12, 0, "b2b3", 0, 467),
+        (12, 1, "e2a6", 10, 4543),
+        (12, 2, "c3d5", 250, 2111),
+        (12, 3, "b4f4", 0, 2130),
+        (14, 0, "b2b3", 0, 467),
+        (14, 1, "e2a6", 10, 4543),
+        (14, 2, "c3d5", 250, 2111),
+        (14, 3, "b4f4", 0, 2130),
+        (15, 0, "b2b3", 0, 717),
+        (15, 1, "e2a6", 10, 4229),
+        (15, 2, "c3d5", 250, 3977),
+        (15, 3, "b4f4", 0, 3322),
+    ];
+
+    macro_rules! legacy_search {
+        ($module:ident, $game:expr) => {{
+            use crate::search::$module::{iterative_deepening_search, SearchConfig};
+            let result = iterative_deepening_search(
+                $game,
+                &LegalMoveGenerator,
+                &MaterialScorer,
+                SearchConfig {
+                    max_depth: FIXTURE_DEPTH,
+                    ..SearchConfig::default()
+                },
+            )
+            .expect("legacy search should run");
+            (result.best_move, result.best_score, result.nodes)
+        }};
+    }
+
+    fn legacy_search(version: u8, game: &GameState) -> (Option<u64>, i32, u64) {
+        match version {
+            3 => legacy_search!(iterative_deepening_v3, game),
+            4 => legacy_search!(iterative_deepening_v4, game),
+            5 => legacy_search!(iterative_deepening_v5, game),
+            6 => legacy_search!(iterative_deepening_v6, game),
+            7 => legacy_search!(iterative_deepening_v7, game),
+            8 => legacy_search!(iterative_deepening_v8, game),
+            9 => legacy_search!(iterative_deepening_v9, game),
+            10 => legacy_search!(iterative_deepening_v10, game),
+            11 => legacy_search!(iterative_deepening_v11, game),
+            12 => legacy_search!(iterative_deepening_v12, game),
+            14 => legacy_search!(iterative_deepening_v14, game),
+            _ => panic!("no legacy search for v{version}"),
+        }
+    }
+
+    fn assert_matches_fixture(version: u8, fen_index: usize, got: (Option<u64>, i32, u64)) {
+        let game = GameState::from_fen(FIXTURE_FENS[fen_index]).expect("FEN should parse");
+        let (_, _, best, score, nodes) = LEGACY_FIXTURES
+            .iter()
+            .find(|f| f.0 == version && f.1 == fen_index)
+            .expect("fixture");
+        let lan = got
+            .0
+            .and_then(|mv| move_description_to_long_algebraic(mv, &game).ok());
+        assert_eq!(
+            (lan.as_deref(), got.1, got.2),
+            (Some(*best), *score, *nodes),
+            "v{version} on {}",
+            FIXTURE_FENS[fen_index]
+        );
+    }
+
+    #[test]
+    fn legacy_searches_reproduce_fixtures() {
+        for &(version, fen_index, ..) in &LEGACY_FIXTURES {
+            if EXACT_PRESET_VERSIONS.contains(&version) {
+                continue;
+            }
+            let game = GameState::from_fen(FIXTURE_FENS[fen_index]).expect("FEN should parse");
+            assert_matches_fixture(version, fen_index, legacy_search(version, &game));
+        }
+    }
+
+    #[test]
+    fn exact_presets_reproduce_legacy_fixtures() {
+        for &(version, fen_index, ..) in &LEGACY_FIXTURES {
+            if !EXACT_PRESET_VERSIONS.contains(&version) {
+                continue;
+            }
+            let game = GameState::from_fen(FIXTURE_FENS[fen_index]).expect("FEN should parse");
+            let result = iterative_deepening_search(
+                &game,
+                &LegalMoveGenerator,
+                &MaterialScorer,
+                SearchConfig {
+                    max_depth: FIXTURE_DEPTH,
+                    features: SearchFeatures::for_version(version).unwrap(),
+                    ..SearchConfig::default()
+                },
+            )
+            .expect("search should run");
+            let got = (result.best_move, result.best_score, result.nodes);
+            assert_matches_fixture(version, fen_index, got);
+        }
+    }
+
+    #[test]
+    fn every_preset_finds_short_tactics_within_node_cap() {
+        let gen = LegalMoveGenerator;
+        let scorer = MaterialScorer;
+        let mate_in_one =
+            GameState::from_fen("6k1/5Q2/6K1/8/8/8/8/8 w - - 0 1").expect("FEN should parse");
+        let free_queen =
+            GameState::from_fen("4k3/8/8/8/8/8/4q3/4KQ2 w - - 0 1").expect("FEN should parse");
+        let presets = PRESET_VERSIONS
+            .iter()
+            .map(|&version| (version, SearchFeatures::for_version(version).unwrap()))
+            .chain([(0, SearchFeatures::baseline())]);
+
+        for (version, features) in presets {
+            let search = |game: &GameState, max_depth: u8, max_nodes: Option<u64>| {
+                iterative_deepening_search(
+                    game,
+                    &gen,
+                    &scorer,
+                    SearchConfig {
+                        max_depth,
+                        max_nodes,
+                        features,
+                        ..SearchConfig::default()
+                    },
+                )
+                .expect("search should run")
+            };
+
+            let mate = search(&mate_in_one, 2, None);
+            assert!(mate.best_score > 29000, "v{version}: {}", mate.best_score);
+
+            let capture = search(&free_queen, 1, None).best_move.expect("move");
+            let lan = move_description_to_long_algebraic(capture, &free_queen).unwrap();
+            assert_eq!(lan, "f1e2", "v{version}");
+
+            let capped = search(&GameState::new_game(), 8, Some(500));
+            assert!(capped.nodes <= 500, "v{version}: {} nodes", capped.nodes);
+        }
+    }
+
+    #[test]
+    fn draw_score_policies_match_their_versions() {
+        assert_eq!(draw_score(DrawScore::Zero, 500), 0);
+
+        assert_eq!(draw_score(DrawScore::WinningPenalty, 50), 0);
+        assert_eq!(draw_score(DrawScore::WinningPenalty, 300), -115);
+        assert_eq!(draw_score(DrawScore::WinningPenalty, -300), 115);
+
+        assert_eq!(draw_score(DrawScore::TightWinningPenalty, 300), -270);
+        assert_eq!(draw_score(DrawScore::TightWinningPenalty, -300), 85);
+
+        assert_eq!(draw_score(DrawScore::Contempt, 0), -18);
+        assert_eq!(draw_score(DrawScore::Contempt, -10), 9);
+        assert_eq!(draw_score(DrawScore::Contempt, 300), -310);
+    }
+
+    #[test]
+    fn mate_score_tt_roundtrip_is_consistent() {
+        let ply = 7u8;
+        let mate_win_score = MATE_SCORE - 12;
+        let mate_loss_score = -MATE_SCORE + 9;
+
+        let stored_win = tt_score_for_storage(mate_win_score, ply);
+        let stored_loss = tt_score_for_storage(mate_loss_score, ply);
+
+        assert_eq!(tt_score_from_storage(stored_win, ply), mate_win_score);
+        assert_eq!(tt_score_from_storage(stored_loss, ply), mate_loss_score);
+    }
 }
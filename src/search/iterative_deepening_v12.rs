@@ -21,7 +21,9 @@
 //! - Stronger SEE thresholds for tactical pruning/order quality.
 
 use crate::game_state::game_state::GameState;
-use crate::move_generation::legal_move_apply::{make_move_in_place, unmake_move_in_place};
+use crate::move_generation::legal_move_apply::{
+    make_move_in_place, make_null_move_in_place, unmake_move_in_place, unmake_null_move_in_place,
+};
 use crate::move_generation::legal_move_checks::is_king_in_check;
 use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
 use crate::move_generation::move_generator::{MoveGenResult, MoveGenerationError, MoveGenerator};
@@ -404,7 +406,7 @@ fn negamax<S: BoardScorer>(
 
     let in_check = is_king_in_check(game_state, game_state.side_to_move);
     if allow_null_pruning && should_try_null_move(depth, in_check, beta, game_state) {
-        let null = make_null_move(game_state);
+        make_null_move_in_place(game_state);
         let reduction = if depth >= 6 { 3 } else { 2 };
         let score_opt = negamax(
             game_state,
@@ -423,7 +425,9 @@ fn negamax<S: BoardScorer>(
             tt,
             heuristics,
         )?;
-        unmake_null_move(game_state, null);
+        unmake_null_move_in_place(game_state).map_err(|x| {
+            MoveGenerationError::InvalidState(format!("unmake_null_move_in_place failed: {x}"))
+        })?;
 
         let Some(score) = score_opt else {
             return Ok(None);
@@ -867,16 +871,7 @@ fn quiescence<S: BoardScorer>(
 
 #[inline]
 fn is_draw_state(game_state: &GameState) -> bool {
-    if game_state.halfmove_clock >= 100 {
-        return true;
-    }
-    let current = game_state.zobrist_key;
-    let count = game_state
-        .repetition_history
-        .iter()
-        .filter(|h| **h == current)
-        .count();
-    count >= 3
+    game_state.is_draw_by_rule()
 }
 
 #[inline]
@@ -1162,16 +1157,6 @@ fn should_lmp_prune(
     move_index >= threshold
 }
 
-#[derive(Debug, Clone, Copy)]
-struct NullMoveUndo {
-    prev_side_to_move: crate::game_state::chess_types::Color,
-    prev_en_passant_square: Option<crate::game_state::chess_types::Square>,
-    prev_halfmove_clock: u16,
-    prev_ply: u16,
-    prev_zobrist_key: u64,
-    prev_repetition_len: usize,
-}
-
 #[inline]
 fn should_try_null_move(depth: u8, in_check: bool, beta: i32, game_state: &GameState) -> bool {
     if in_check || depth < 3 {
@@ -1203,41 +1188,6 @@ fn has_non_pawn_material(
         || game_state.pieces[idx][crate::game_state::chess_types::PieceKind::Queen.index()] != 0
 }
 
-fn make_null_move(game_state: &mut GameState) -> NullMoveUndo {
-    use crate::search::zobrist::{en_passant_file_key, side_to_move_key};
-    let undo = NullMoveUndo {
-        prev_side_to_move: game_state.side_to_move,
-        prev_en_passant_square: game_state.en_passant_square,
-        prev_halfmove_clock: game_state.halfmove_clock,
-        prev_ply: game_state.ply,
-        prev_zobrist_key: game_state.zobrist_key,
-        prev_repetition_len: game_state.repetition_history.len(),
-    };
-
-    if let Some(ep) = game_state.en_passant_square {
-        game_state.zobrist_key ^= en_passant_file_key(ep % 8);
-    }
-    game_state.en_passant_square = None;
-    game_state.side_to_move = game_state.side_to_move.opposite();
-    game_state.zobrist_key ^= side_to_move_key();
-    game_state.halfmove_clock = game_state.halfmove_clock.saturating_add(1);
-    game_state.ply = game_state.ply.saturating_add(1);
-    game_state.repetition_history.push(game_state.zobrist_key);
-
-    undo
-}
-
-fn unmake_null_move(game_state: &mut GameState, undo: NullMoveUndo) {
-    game_state.side_to_move = undo.prev_side_to_move;
-    game_state.en_passant_square = undo.prev_en_passant_square;
-    game_state.halfmove_clock = undo.prev_halfmove_clock;
-    game_state.ply = undo.prev_ply;
-    game_state.zobrist_key = undo.prev_zobrist_key;
-    game_state
-        .repetition_history
-        .truncate(undo.prev_repetition_len);
-}
-
 type HistoryTable = [[[i32; 64]; 6]; 2];
 type CounterMoveTable = [[u64; 64]; 6];
 type ContinuationHistoryTable = [[[[[i32; 64]; 6]; 64]; 6]; 2];
@@ -0,0 +1,436 @@
+//! Staged, lazily generated move ordering for search nodes.
+//!
+//! Yields TT move -> good captures -> killers -> quiets -> bad captures, and
+//! only generates quiet moves once the tactical stages are exhausted, so early
+//! cutoffs never pay for quiet generation. In check, all evasions are yielded
+//! after the TT move.
+
+use crate::game_state::chess_types::{Color, PieceKind};
+use crate::game_state::game_state::GameState;
+use crate::move_generation::legal_move_checks::attackers_bitboard;
+use crate::move_generation::legal_move_pin_mask::side_to_move_in_check;
+use crate::move_generation::legal_move_staged::{
+    generate_captures, generate_evasions, generate_quiets,
+};
+use crate::move_generation::move_legality::is_legal;
+use crate::move_generation::move_list::MoveList;
+use crate::moves::bishop_moves::bishop_attacks;
+use crate::moves::move_descriptions::{
+    move_captured_piece_code, move_from, move_moved_piece_code, move_promotion_piece_code, move_to,
+    piece_kind_from_code, FLAG_CAPTURE, FLAG_EN_PASSANT, NO_PIECE_CODE,
+};
+use crate::moves::rook_moves::rook_attacks;
+
+/// Stages walked by [`MovePicker`], in yield order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerStage {
+    TtMove,
+    GenerateCaptures,
+    GoodCaptures,
+    Killers,
+    GenerateQuiets,
+    Quiets,
+    BadCaptures,
+    GenerateEvasions,
+    Evasions,
+    Done,
+}
+
+pub struct MovePicker {
+    stage: PickerStage,
+    tt_move: Option<u64>,
+    killers: [u64; 2],
+    killer_index: usize,
+    in_check: bool,
+    captures_only: bool,
+    scored: MoveList,
+    bad_captures: MoveList,
+    cursor: usize,
+}
+
+impl MovePicker {
+    /// Picker for a full-width search node.
+    pub fn new(game_state: &GameState, tt_move: Option<u64>, killers: [u64; 2]) -> Self {
+        let in_check = side_to_move_in_check(game_state);
+        Self::with_mode(game_state, tt_move, killers, in_check, false)
+    }
+
+    /// Picker that yields tactical moves only (TT move, good then bad
+    /// captures); in check it yields all evasions instead.
+    pub fn new_quiescence(game_state: &GameState, tt_move: Option<u64>) -> Self {
+        let in_check = side_to_move_in_check(game_state);
+        Self::with_mode(game_state, tt_move, [0; 2], in_check, true)
+    }
+
+    fn with_mode(
+        game_state: &GameState,
+        tt_move: Option<u64>,
+        killers: [u64; 2],
+        in_check: bool,
+        captures_only: bool,
+    ) -> Self {
+        let tt_move = tt_move.filter(|mv| {
+            (!captures_only || in_check || !is_quiet(*mv)) && is_legal(game_state, *mv)
+        });
+        let stage = if tt_move.is_some() {
+            PickerStage::TtMove
+        } else if in_check {
+            PickerStage::GenerateEvasions
+        } else {
+            PickerStage::GenerateCaptures
+        };
+        Self {
+            stage,
+            tt_move,
+            killers,
+            killer_index: 0,
+            in_check,
+            captures_only,
+            scored: MoveList::new(),
+            bad_captures: MoveList::new(),
+            cursor: 0,
+        }
+    }
+
+    /// Current stage (the stage the next yielded move will come from).
+    #[inline]
+    pub fn stage(&self) -> PickerStage {
+        self.stage
+    }
+
+    /// Drop the remaining killer and quiet stages (for example after move-count pruning).
+    pub fn skip_quiets(&mut self) {
+        match self.stage {
+            PickerStage::Killers | PickerStage::GenerateQuiets | PickerStage::Quiets => {
+                self.stage = PickerStage::BadCaptures;
+                self.cursor = 0;
+            }
+            _ => self.captures_only = true,
+        }
+    }
+
+    /// Yield the next move, or `None` once all stages are exhausted.
+    ///
+    /// `quiet_score` ranks quiet moves (history, countermove, ...); higher first.
+    pub fn next_move(
+        &mut self,
+        game_state: &GameState,
+        quiet_score: &dyn Fn(u64) -> i32,
+    ) -> Option<u64> {
+        loop {
+            match self.stage {
+                PickerStage::TtMove => {
+                    self.stage = if self.in_check {
+                        PickerStage::GenerateEvasions
+                    } else {
+                        PickerStage::GenerateCaptures
+                    };
+                    if let Some(mv) = self.tt_move {
+                        return Some(mv);
+                    }
+                }
+                PickerStage::GenerateCaptures => {
+                    self.scored.clear();
+                    generate_captures(game_state, &mut self.scored);
+                    let tt_move = self.tt_move;
+                    self.scored.retain(|mv| Some(mv) != tt_move);
+                    for i in 0..self.scored.len() {
+                        self.scored
+                            .set_score(i, capture_order_score(self.scored[i]));
+                    }
+                    self.cursor = 0;
+                    self.stage = PickerStage::GoodCaptures;
+                }
+                PickerStage::GoodCaptures => {
+                    let Some(mv) = self.pick_best() else {
+                        self.stage = if self.captures_only {
+                            PickerStage::BadCaptures
+                        } else {
+                            PickerStage::Killers
+                        };
+                        self.cursor = 0;
+                        continue;
+                    };
+                    if (mv & FLAG_CAPTURE) != 0 && static_exchange_evaluation(game_state, mv) < 0 {
+                        self.bad_captures.push(mv);
+                        continue;
+                    }
+                    return Some(mv);
+                }
+                PickerStage::Killers => {
+                    if self.killer_index >= self.killers.len() {
+                        self.stage = PickerStage::GenerateQuiets;
+                        continue;
+                    }
+                    let killer = self.killers[self.killer_index];
+                    self.killer_index += 1;
+                    if killer != 0
+                        && Some(killer) != self.tt_move
+                        && is_quiet(killer)
+                        && is_legal(game_state, killer)
+                    {
+                        return Some(killer);
+                    }
+                }
+                PickerStage::GenerateQuiets => {
+                    self.scored.clear();
+                    generate_quiets(game_state, &mut self.scored);
+                    let (tt_move, killers) = (self.tt_move, self.killers);
+                    self.scored
+                        .retain(|mv| Some(mv) != tt_move && !killers.contains(&mv));
+                    for i in 0..self.scored.len() {
+                        self.scored.set_score(i, quiet_score(self.scored[i]));
+                    }
+                    self.cursor = 0;
+                    self.stage = PickerStage::Quiets;
+                }
+                PickerStage::Quiets => {
+                    if let Some(mv) = self.pick_best() {
+                        return Some(mv);
+                    }
+                    self.stage = PickerStage::BadCaptures;
+                    self.cursor = 0;
+                }
+                PickerStage::BadCaptures => {
+                    if self.cursor < self.bad_captures.len() {
+                        let mv = self.bad_captures[self.cursor];
+                        self.cursor += 1;
+                        return Some(mv);
+                    }
+                    self.stage = PickerStage::Done;
+                }
+                PickerStage::GenerateEvasions => {
+                    self.scored.clear();
+                    generate_evasions(game_state, &mut self.scored);
+                    let tt_move = self.tt_move;
+                    self.scored.retain(|mv| Some(mv) != tt_move);
+                    for i in 0..self.scored.len() {
+                        let mv = self.scored[i];
+                        let score = if is_quiet(mv) {
+                            quiet_score(mv)
+                        } else {
+                            1_000_000 + capture_order_score(mv)
+                        };
+                        self.scored.set_score(i, score);
+                    }
+                    self.cursor = 0;
+                    self.stage = PickerStage::Evasions;
+                }
+                PickerStage::Evasions => {
+                    if let Some(mv) = self.pick_best() {
+                        return Some(mv);
+                    }
+                    self.stage = PickerStage::Done;
+                }
+                PickerStage::Done => return None,
+            }
+        }
+    }
+
+    /// Selection-sort step: yield the best remaining entry at `cursor`.
+    fn pick_best(&mut self) -> Option<u64> {
+        let mv = self.scored.pick_best(self.cursor)?;
+        self.cursor += 1;
+        Some(mv)
+    }
+}
+
+#[inline]
+fn is_quiet(move_description: u64) -> bool {
+    (move_description & (FLAG_CAPTURE | FLAG_EN_PASSANT)) == 0
+        && move_promotion_piece_code(move_description) == NO_PIECE_CODE
+}
+
+#[inline]
+fn see_piece_value(piece: PieceKind) -> i32 {
+    match piece {
+        PieceKind::Pawn => 100,
+        PieceKind::Knight => 320,
+        PieceKind::Bishop => 330,
+        PieceKind::Rook => 500,
+        PieceKind::Queen => 900,
+        PieceKind::King => 20_000,
+    }
+}
+
+/// MVV/LVA ordering key for tactical moves (promotions add the promoted value).
+#[inline]
+fn capture_order_score(move_description: u64) -> i32 {
+    let victim = piece_kind_from_code(move_captured_piece_code(move_description))
+        .map(see_piece_value)
+        .unwrap_or(0);
+    let aggressor = piece_kind_from_code(move_moved_piece_code(move_description))
+        .map(see_piece_value)
+        .unwrap_or(100);
+    let promo = piece_kind_from_code(move_promotion_piece_code(move_description))
+        .map(see_piece_value)
+        .unwrap_or(0);
+    victim * 16 - aggressor + promo * 16
+}
+
+/// Static exchange evaluation of a capture on its target square.
+///
+/// Plays out the full capture sequence with least-valuable attackers
+/// (including x-ray sliders) and returns the material balance for the mover.
+pub fn static_exchange_evaluation(game_state: &GameState, move_description: u64) -> i32 {
+    let from = move_from(move_description);
+    let to = move_to(move_description);
+    let side = game_state.side_to_move;
+    let Some(moved) = piece_kind_from_code(move_moved_piece_code(move_description)) else {
+        return 0;
+    };
+    let promotion = piece_kind_from_code(move_promotion_piece_code(move_description));
+    let victim = piece_kind_from_code(move_captured_piece_code(move_description))
+        .map(see_piece_value)
+        .unwrap_or(0);
+
+    let mut occ = game_state.occupancy_all;
+    if (move_description & FLAG_EN_PASSANT) != 0 {
+        let captured_sq = if side == Color::Light { to - 8 } else { to + 8 };
+        occ &= !(1u64 << captured_sq);
+    }
+
+    let diagonal =
+        piece_set(game_state, PieceKind::Bishop) | piece_set(game_state, PieceKind::Queen);
+    let straight = piece_set(game_state, PieceKind::Rook) | piece_set(game_state, PieceKind::Queen);
+
+    let mut gain = [0i32; 32];
+    gain[0] = victim
+        + promotion
+            .map(|p| see_piece_value(p) - see_piece_value(PieceKind::Pawn))
+            .unwrap_or(0);
+    let mut attacker_value = see_piece_value(promotion.unwrap_or(moved));
+    let mut from_set = 1u64 << from;
+    let mut attackers = attackers_bitboard(game_state, to, Color::Light, occ)
+        | attackers_bitboard(game_state, to, Color::Dark, occ);
+    let mut mover = side;
+    let mut depth = 0usize;
+
+    loop {
+        depth += 1;
+        gain[depth] = attacker_value - gain[depth - 1];
+        if depth + 1 >= gain.len() {
+            break;
+        }
+
+        occ &= !from_set;
+        attackers &= !from_set;
+        // Reveal sliders standing behind the piece that just captured.
+        attackers |=
+            ((bishop_attacks(to, occ) & diagonal) | (rook_attacks(to, occ) & straight)) & occ;
+
+        mover = mover.opposite();
+        let Some((square, piece)) = least_valuable_attacker(game_state, attackers, mover) else {
+            break;
+        };
+        from_set = 1u64 << square;
+        attacker_value = see_piece_value(piece);
+    }
+
+    while depth > 1 {
+        depth -= 1;
+        gain[depth - 1] = -(-gain[depth - 1]).max(gain[depth]);
+    }
+    gain[0]
+}
+
+#[inline]
+fn piece_set(game_state: &GameState, piece: PieceKind) -> u64 {
+    game_state.pieces[Color::Light.index()][piece.index()]
+        | game_state.pieces[Color::Dark.index()][piece.index()]
+}
+
+#[inline]
+fn least_valuable_attacker(
+    game_state: &GameState,
+    attackers: u64,
+    color: Color,
+) -> Option<(u8, PieceKind)> {
+    for piece in [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ] {
+        let set = attackers & game_state.pieces[color.index()][piece.index()];
+        if set != 0 {
+            return Some((set.trailing_zeros() as u8, piece));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{static_exchange_evaluation, MovePicker, PickerStage};
+    use crate::game_state::game_state::GameState;
+    use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+    use crate::utils::long_algebraic::long_algebraic_to_move_description;
+
+    fn drain(picker: &mut MovePicker, game: &GameState) -> Vec<u64> {
+        let mut out = Vec::new();
+        while let Some(mv) = picker.next_move(game, &|_| 0) {
+            out.push(mv);
+        }
+        out
+    }
+
+    #[test]
+    fn picker_yields_each_legal_move_exactly_once() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/8/8/8/8/2n5/4r3/4K2R w K - 0 1",
+        ];
+        for fen in fens {
+            let mut game = GameState::from_fen(fen).expect("FEN should parse");
+            let mut expected = generate_legal_move_descriptions_in_place(&mut game).expect("moves");
+            let tt_move = expected.last().copied();
+            let killers = [expected[0], 0];
+            let mut picker = MovePicker::new(&game, tt_move, killers);
+            let mut got = drain(&mut picker, &game);
+            assert_eq!(got.first().copied(), tt_move);
+            got.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(got, expected, "picker mismatch for {fen}");
+        }
+    }
+
+    #[test]
+    fn picker_orders_good_captures_before_quiets_and_bad_captures_last() {
+        // Qxh6 wins a rook outright; Nxd5 and Qxd5 both lose material to exd5.
+        let game = GameState::from_fen("4k3/8/4p2r/3p4/8/2N5/3Q4/4K3 w - - 0 1")
+            .expect("FEN should parse");
+        let lan = |s: &str| long_algebraic_to_move_description(s, &game).expect("legal LAN");
+        let mut picker = MovePicker::new(&game, None, [0; 2]);
+        assert_eq!(picker.next_move(&game, &|_| 0), Some(lan("d2h6")));
+        assert_eq!(picker.stage(), PickerStage::GoodCaptures);
+
+        let rest = drain(&mut picker, &game);
+        assert_eq!(rest[rest.len() - 2..], [lan("c3d5"), lan("d2d5")]);
+        assert_eq!(picker.stage(), PickerStage::Done);
+    }
+
+    #[test]
+    fn quiescence_picker_skips_quiet_moves() {
+        let game = GameState::new_game();
+        let mut picker = MovePicker::new_quiescence(&game, None);
+        assert!(drain(&mut picker, &game).is_empty());
+    }
+
+    #[test]
+    fn see_resolves_defended_and_undefended_captures() {
+        let game =
+            GameState::from_fen("4k3/8/4p3/3p4/8/2N5/3Q4/4K3 w - - 0 1").expect("FEN should parse");
+        let nxd5 = long_algebraic_to_move_description("c3d5", &game).expect("Nxd5");
+        let qxd5 = long_algebraic_to_move_description("d2d5", &game).expect("Qxd5");
+        // N takes P, P takes N, Q takes P: 100 - 320 + 100.
+        assert_eq!(static_exchange_evaluation(&game, nxd5), -120);
+        assert_eq!(static_exchange_evaluation(&game, qxd5), -700);
+
+        let free = GameState::from_fen("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1").expect("FEN");
+        let nxd5 = long_algebraic_to_move_description("c3d5", &free).expect("Nxd5");
+        assert_eq!(static_exchange_evaluation(&free, nxd5), 100);
+    }
+}
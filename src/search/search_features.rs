@@ -0,0 +1,384 @@
+//! Heuristic switches for the iterative deepening search core.
+//!
+//! `SearchFeatures` toggles each pruning, reduction, extension and ordering
+//! heuristic independently so one can be A/B tested in isolation. Presets
+//! enable the heuristic sets of the historical `iterative_deepening_vN`
+//! searches. Every preset shares the staged move picker and bucketed TT, so
+//! only the versions in `EXACT_PRESET_VERSIONS` reproduce their search move
+//! for move; the others keep their legacy module as the reference until their
+//! preset matches its fixtures too.
+
+/// Versions with a `SearchFeatures::for_version` preset (there was no v13).
+pub const PRESET_VERSIONS: [u8; 12] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 14, 15];
+
+/// Versions whose preset reproduces the legacy search's best move, score and
+/// node count on the fixture positions.
+pub const EXACT_PRESET_VERSIONS: [u8; 1] = [15];
+
+/// Score returned for rule draws (repetition, 50-move, dead positions),
+/// relative to the side to move's static eval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DrawScore {
+    /// Plain 0.
+    Zero,
+    /// Penalise draws beyond +80cp, seek them below -80cp (v3).
+    WinningPenalty,
+    /// Steeper penalty from +40cp, milder draw-seeking when worse (v14).
+    TightWinningPenalty,
+    /// Small contempt near equality plus strong penalties when clearly winning (v15).
+    Contempt,
+}
+
+/// One-ply extensions near the leaves of late endgames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndgameExtensions {
+    Off,
+    /// Checking moves only (v3).
+    Checks,
+    /// Checks, advanced passed pawns and king-pawn races (v15).
+    Selective,
+}
+
+/// Late move reduction schedule for quiet moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LateMoveReductions {
+    Off,
+    /// One ply, two from depth 6 and the ninth move (v4).
+    Basic,
+    /// Up to three plies, scaling with depth and move index (v15).
+    Deep,
+}
+
+/// Late move pruning of quiet moves at depth 3 and below.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LateMovePruning {
+    Off,
+    /// Prune from move 4/8/12 at depth 1/2/3 (v9).
+    Basic,
+    /// Prune from move 3/6/10, never in king-pawn or late endgames (v15).
+    Tight,
+}
+
+/// Null-move pruning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NullMovePruning {
+    Off,
+    /// Try a null move from `min_depth`. Fail-highs at `verify_depth` or
+    /// deeper are confirmed by a reduced real search; with verification,
+    /// consecutive null moves are also disallowed.
+    On {
+        min_depth: u8,
+        verify_depth: Option<u8>,
+    },
+}
+
+/// Singular extensions of the TT move. A reduced search of the node with the
+/// TT move excluded decides whether that move is the only one reaching the
+/// TT score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingularExtensions {
+    Off,
+    /// From `min_depth`, extend a singular TT move one ply, or two when every
+    /// alternative falls `double_margin` short (at most `max_double` times per
+    /// line). If the alternatives also beat beta the node is cut (multi-cut);
+    /// if only the TT score does, the TT move is reduced one ply instead.
+    On {
+        min_depth: u8,
+        double_margin: i32,
+        max_double: u8,
+    },
+}
+
+impl SingularExtensions {
+    /// Settings used when the engine's `SingularExtensions` option is on.
+    pub const DEFAULT_ON: Self = Self::On {
+        min_depth: 4,
+        double_margin: 24,
+        max_double: 4,
+    };
+}
+
+/// ProbCut at non-PV nodes: a capture that beats `beta + margin` in a
+/// quiescence probe and then a search four plies shallower cuts the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProbCut {
+    Off,
+    On { min_depth: u8, margin: i32 },
+}
+
+impl ProbCut {
+    pub const DEFAULT_MIN_DEPTH: u8 = 5;
+    /// Default for the engine's `ProbCutMargin` option.
+    pub const DEFAULT_MARGIN: i32 = 200;
+    pub const DEFAULT_ON: Self = Self::On {
+        min_depth: Self::DEFAULT_MIN_DEPTH,
+        margin: Self::DEFAULT_MARGIN,
+    };
+}
+
+/// Internal iterative reduction: PV nodes and expected cut nodes (zero
+/// window, static eval at or above beta) without a TT move are searched
+/// `reduction` plies shallower from `min_depth`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InternalIterativeReduction {
+    Off,
+    On { min_depth: u8, reduction: u8 },
+}
+
+impl InternalIterativeReduction {
+    /// Default for the engine's `IIRMinDepth` option.
+    pub const DEFAULT_MIN_DEPTH: u8 = 4;
+    pub const DEFAULT_REDUCTION: u8 = 1;
+    pub const DEFAULT_ON: Self = Self::On {
+        min_depth: Self::DEFAULT_MIN_DEPTH,
+        reduction: Self::DEFAULT_REDUCTION,
+    };
+}
+
+/// SEE and delta pruning of quiescence captures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeePruning {
+    Off,
+    /// Fixed delta margin and bad-capture threshold (v8).
+    Fixed,
+    /// Margin and threshold loosen with quiescence ply (v12).
+    PlyScaled,
+    /// Ply-scaled, relaxed further in late and king-pawn endgames (v15).
+    EndgameAware,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchFeatures {
+    pub draw_score: DrawScore,
+    pub endgame_extensions: EndgameExtensions,
+    /// Killer moves and butterfly history for quiet ordering.
+    pub killers_and_history: bool,
+    /// Countermove and continuation history for quiet ordering.
+    pub countermoves: bool,
+    pub late_move_reductions: LateMoveReductions,
+    /// Aspiration windows around the previous iteration's score.
+    pub aspiration: bool,
+    pub null_move: NullMovePruning,
+    /// Principal variation search: zero-window probes for non-PV moves.
+    pub pvs: bool,
+    pub see_pruning: SeePruning,
+    pub late_move_pruning: LateMovePruning,
+    /// Quiet checking moves at the first quiescence ply.
+    pub quiet_checks: bool,
+    /// Store mate scores relative to the node so TT hits keep mate distance.
+    pub mate_distance_tt: bool,
+    /// Reverse futility pruning at depth 1-2 in zero-window nodes.
+    pub reverse_futility: bool,
+    /// Return the actual score on cutoffs instead of the bound.
+    pub fail_soft: bool,
+    pub singular_extensions: SingularExtensions,
+    pub probcut: ProbCut,
+    pub internal_iterative_reduction: InternalIterativeReduction,
+}
+
+impl Default for SearchFeatures {
+    fn default() -> Self {
+        Self::v15()
+    }
+}
+
+impl SearchFeatures {
+    /// Plain alpha-beta with TT move ordering and quiescence; every switch off.
+    pub const fn baseline() -> Self {
+        Self {
+            draw_score: DrawScore::Zero,
+            endgame_extensions: EndgameExtensions::Off,
+            killers_and_history: false,
+            countermoves: false,
+            late_move_reductions: LateMoveReductions::Off,
+            aspiration: false,
+            null_move: NullMovePruning::Off,
+            pvs: false,
+            see_pruning: SeePruning::Off,
+            late_move_pruning: LateMovePruning::Off,
+            quiet_checks: false,
+            mate_distance_tt: false,
+            reverse_futility: false,
+            fail_soft: false,
+            singular_extensions: SingularExtensions::Off,
+            probcut: ProbCut::Off,
+            internal_iterative_reduction: InternalIterativeReduction::Off,
+        }
+    }
+
+    /// Repetition-while-winning draw penalty and late-endgame check extension.
+    pub const fn v3() -> Self {
+        Self {
+            draw_score: DrawScore::WinningPenalty,
+            endgame_extensions: EndgameExtensions::Checks,
+            ..Self::baseline()
+        }
+    }
+
+    /// Killer/history ordering and LMR.
+    pub const fn v4() -> Self {
+        Self {
+            killers_and_history: true,
+            late_move_reductions: LateMoveReductions::Basic,
+            ..Self::v3()
+        }
+    }
+
+    /// Aspiration windows and null-move pruning.
+    pub const fn v5() -> Self {
+        Self {
+            aspiration: true,
+            null_move: NullMovePruning::On {
+                min_depth: 3,
+                verify_depth: None,
+            },
+            ..Self::v4()
+        }
+    }
+
+    /// Principal variation search.
+    pub const fn v6() -> Self {
+        Self {
+            pvs: true,
+            ..Self::v5()
+        }
+    }
+
+    /// Countermove and continuation-history ordering.
+    pub const fn v7() -> Self {
+        Self {
+            countermoves: true,
+            ..Self::v6()
+        }
+    }
+
+    /// SEE/delta pruning in quiescence.
+    pub const fn v8() -> Self {
+        Self {
+            see_pruning: SeePruning::Fixed,
+            ..Self::v7()
+        }
+    }
+
+    /// Late move pruning.
+    pub const fn v9() -> Self {
+        Self {
+            late_move_pruning: LateMovePruning::Basic,
+            ..Self::v8()
+        }
+    }
+
+    /// Null-move verification search.
+    pub const fn v10() -> Self {
+        Self {
+            null_move: NullMovePruning::On {
+                min_depth: 3,
+                verify_depth: Some(6),
+            },
+            ..Self::v9()
+        }
+    }
+
+    /// v11 only replaced the transposition table, which every preset now shares.
+    pub const fn v11() -> Self {
+        Self::v10()
+    }
+
+    /// Quiet checks in quiescence and ply-scaled SEE thresholds.
+    pub const fn v12() -> Self {
+        Self {
+            see_pruning: SeePruning::PlyScaled,
+            quiet_checks: true,
+            ..Self::v11()
+        }
+    }
+
+    /// Tightened draw penalty and mate-distance TT normalisation.
+    pub const fn v14() -> Self {
+        Self {
+            draw_score: DrawScore::TightWinningPenalty,
+            mate_distance_tt: true,
+            ..Self::v12()
+        }
+    }
+
+    /// Contempt, selective endgame extensions, retuned LMR/LMP/null move,
+    /// reverse futility pruning and fail-soft cutoffs. Quiet checks are off.
+    pub const fn v15() -> Self {
+        Self {
+            draw_score: DrawScore::Contempt,
+            endgame_extensions: EndgameExtensions::Selective,
+            late_move_reductions: LateMoveReductions::Deep,
+            null_move: NullMovePruning::On {
+                min_depth: 4,
+                verify_depth: Some(8),
+            },
+            see_pruning: SeePruning::EndgameAware,
+            late_move_pruning: LateMovePruning::Tight,
+            quiet_checks: false,
+            reverse_futility: true,
+            fail_soft: true,
+            ..Self::v14()
+        }
+    }
+
+    /// Preset for `iterative_deepening_v{version}`, if that version existed.
+    pub const fn for_version(version: u8) -> Option<Self> {
+        Some(match version {
+            3 => Self::v3(),
+            4 => Self::v4(),
+            5 => Self::v5(),
+            6 => Self::v6(),
+            7 => Self::v7(),
+            8 => Self::v8(),
+            9 => Self::v9(),
+            10 => Self::v10(),
+            11 => Self::v11(),
+            12 => Self::v12(),
+            14 => Self::v14(),
+            15 => Self::v15(),
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DrawScore, InternalIterativeReduction, LateMovePruning, NullMovePruning, ProbCut,
+        SearchFeatures, SeePruning, SingularExtensions, PRESET_VERSIONS,
+    };
+
+    #[test]
+    fn presets_add_heuristics_in_version_order() {
+        assert_eq!(SearchFeatures::default(), SearchFeatures::v15());
+        assert_eq!(SearchFeatures::for_version(13), None);
+        assert!(PRESET_VERSIONS
+            .iter()
+            .all(|&v| SearchFeatures::for_version(v).is_some()));
+
+        assert_eq!(SearchFeatures::v3().draw_score, DrawScore::WinningPenalty);
+        assert!(!SearchFeatures::v3().killers_and_history);
+        assert!(!SearchFeatures::v5().pvs && SearchFeatures::v6().pvs);
+        assert_eq!(SearchFeatures::v7().see_pruning, SeePruning::Off);
+        assert_eq!(
+            SearchFeatures::v9().late_move_pruning,
+            LateMovePruning::Basic
+        );
+        assert_eq!(SearchFeatures::v11(), SearchFeatures::v10());
+        assert!(SearchFeatures::v14().quiet_checks && !SearchFeatures::v15().quiet_checks);
+        assert!(PRESET_VERSIONS.iter().all(|&v| {
+            let features = SearchFeatures::for_version(v).unwrap();
+            features.singular_extensions == SingularExtensions::Off
+                && features.probcut == ProbCut::Off
+                && features.internal_iterative_reduction == InternalIterativeReduction::Off
+        }));
+        assert_eq!(
+            SearchFeatures::v15().null_move,
+            NullMovePruning::On {
+                min_depth: 4,
+                verify_depth: Some(8),
+            }
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Live search progress reporting.
+//!
+//! `iterative_deepening_search_with_tt` reports to an optional `SearchObserver`
+//! after each completed depth, whenever the root best move changes and about
+//! every `PROGRESS_NODE_INTERVAL` nodes, so front-ends can stream `info` output
+//! while the search runs instead of parsing engine text afterwards.
+
+use std::fmt;
+use std::sync::{mpsc, Mutex};
+
+/// Node spacing of `on_progress` reports, sampled between root moves.
+pub const PROGRESS_NODE_INTERVAL: u64 = 1 << 16;
+
+/// Snapshot of the search as seen from the root.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth: u8,
+    /// Deepest ply reached in this iteration, quiescence included.
+    pub seldepth: u8,
+    /// Centipawns from the side to move; mate scores sit near `MATE_SCORE`.
+    pub score: i32,
+    pub pv: Vec<u64>,
+    pub nodes: u64,
+    pub elapsed_ms: u64,
+    pub nps: u64,
+    /// TT occupancy per mille, from `TTStats::hashfull`.
+    pub hashfull: u16,
+    /// Root move about to be searched (progress reports only).
+    pub currmove: Option<u64>,
+    /// 1-based position of `currmove` in this iteration's root move order.
+    pub currmovenumber: Option<usize>,
+}
+
+/// Receives search reports. Called on the searching thread, so keep it cheap.
+pub trait SearchObserver: Send + Sync {
+    /// A depth finished; `info.pv` is the full principal variation.
+    fn on_iteration(&self, _info: &SearchInfo) {}
+    /// A different root move became best partway through an iteration.
+    fn on_best_move(&self, _info: &SearchInfo) {}
+    /// Periodic node/time update carrying `currmove`.
+    fn on_progress(&self, _info: &SearchInfo) {}
+}
+
+impl fmt::Debug for dyn SearchObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SearchObserver")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchEvent {
+    Iteration(SearchInfo),
+    BestMove(SearchInfo),
+    Progress(SearchInfo),
+}
+
+/// Channel subscription: each report is forwarded as a `SearchEvent`.
+/// Send errors are ignored so a dropped receiver never aborts the search.
+impl SearchObserver for mpsc::Sender<SearchEvent> {
+    fn on_iteration(&self, info: &SearchInfo) {
+        let _ = self.send(SearchEvent::Iteration(info.clone()));
+    }
+
+    fn on_best_move(&self, info: &SearchInfo) {
+        let _ = self.send(SearchEvent::BestMove(info.clone()));
+    }
+
+    fn on_progress(&self, info: &SearchInfo) {
+        let _ = self.send(SearchEvent::Progress(info.clone()));
+    }
+}
+
+/// Keeps the most recent completed iteration for callers that only need
+/// the final depth/nodes of each search (e.g. match statistics).
+#[derive(Debug, Default)]
+pub struct LastIterationRecorder {
+    last: Mutex<Option<SearchInfo>>,
+}
+
+impl LastIterationRecorder {
+    pub fn take(&self) -> Option<SearchInfo> {
+        self.last.lock().ok().and_then(|mut guard| guard.take())
+    }
+}
+
+impl SearchObserver for LastIterationRecorder {
+    fn on_iteration(&self, info: &SearchInfo) {
+        if let Ok(mut guard) = self.last.lock() {
+            *guard = Some(info.clone());
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Tactical test-suite loading and solving.
+//!
+//! Reads EPD-style lines (`<fen> bm <move> [<move>...]; id "<name>";`) with
+//! best moves in long algebraic notation, searches each position with a given
+//! `SearchFeatures` set and reports whether a best move was found. Used to
+//! check that selectivity heuristics do not lose tactics.
+
+use std::fs;
+
+use crate::game_state::game_state::GameState;
+use crate::move_generation::legal_move_generator::{generate_legal_move_list, LegalMoveGenerator};
+use crate::search::board_scoring::BoardScorer;
+use crate::search::iterative_deepening::{iterative_deepening_search, SearchConfig};
+use crate::search::search_features::SearchFeatures;
+use crate::utils::long_algebraic::{
+    long_algebraic_to_move_description, move_description_to_long_algebraic,
+};
+
+/// Opening positions of the "Win at Chess" suite, best moves converted to
+/// long algebraic.
+pub const WIN_AT_CHESS_SAMPLE: &str = "\
+2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm g3g6; id \"WAC.001\";
+8/7p/5k2/5p2/p1p2P2/Pr1pPK2/1P1R3P/6R1 b - - bm b3b2; id \"WAC.002\";
+5rk1/1ppb3p/p1pb4/6q1/3P1p1r/2P1R2P/PP1BQ1P1/5RKN w - - bm e3g3; id \"WAC.003\";
+r1bq2rk/pp3pbp/2p1p1pQ/7P/3P4/2PB1N2/PP3PPR/2KR4 w - - bm h6h7; id \"WAC.004\";
+5k2/6pp/p1qN4/1p1p4/3P4/2PKP2Q/PP3r2/3R4 b - - bm c6c4; id \"WAC.005\";
+7k/p7/1R5K/6r1/6p1/6P1/8/8 w - - bm b6b7; id \"WAC.006\";
+rnbqkb1r/pppp1ppp/8/4P3/6n1/7P/PPPNPPP1/R1BQKBNR b KQkq - bm g4e3; id \"WAC.007\";
+r4q1k/p2bR1rp/2p2Q1N/5p2/5p2/2P5/PP3PPP/R5K1 w - - bm e7f7; id \"WAC.008\";
+3q1rk1/p4pp1/2pb3p/3p4/6Pr/1PNQ4/P1PB1PP1/4RRK1 b - - bm d6h2; id \"WAC.009\";
+2br2k1/2q3rn/p2NppQ1/2p1P3/Pp5R/4P3/1P3PPP/3R2K1 w - - bm h4h7; id \"WAC.010\";
+r1b1kb1r/3q1ppp/pBp1pn2/8/Np3P2/5B2/PPP3PP/R2Q1RK1 w kq - bm f3c6; id \"WAC.011\";
+4k1r1/2p3r1/1pR1p3/3pP2p/3P2qP/P4N2/1PQ4P/5R1K b - - bm g4f3; id \"WAC.012\";
+5rk1/pp4p1/2n1p2p/2Npq3/2p5/6P1/P3P1BP/R4Q1K w - - bm f1f8; id \"WAC.013\";
+r2rb1k1/pp1q1p1p/2n1p1p1/2bp4/5P2/PP1BPR1Q/1BPN2PP/R5K1 w - - bm h3h7; id \"WAC.014\";
+1R6/1brk2p1/4p2p/p1P1Pp2/P7/6P1/1P4P1/2R3K1 w - - bm b8b7; id \"WAC.015\";
+r4rk1/ppp2ppp/2n5/2bqp3/8/P2PB3/1PP1NPPP/R2Q1RK1 w - - bm e2c3; id \"WAC.016\";
+1k5r/pppbn1pp/4q1r1/1P3p2/2NPp3/1QP5/P4PPP/R1B1R1K1 w - - bm c4e5; id \"WAC.017\";
+R7/P4k2/8/8/8/8/r7/6K1 w - - bm a8h8; id \"WAC.018\";
+r1b2rk1/ppbn1ppp/4p3/1QP4q/3P4/N4N2/5PPP/R1B2RK1 w - - bm c5c6; id \"WAC.019\";
+r2qkb1r/1ppb1ppp/p7/4p3/P1Q1P3/2P5/5PPP/R1B2KNR b kq - bm d7b5; id \"WAC.020\";
+";
+
+/// One suite line: a position and the moves that solve it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TacticalPosition {
+    pub id: String,
+    pub fen: String,
+    /// Long-algebraic best moves; any of them counts as solved.
+    pub best_moves: Vec<String>,
+}
+
+/// Search outcome for one suite position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TacticalResult {
+    pub id: String,
+    pub found: Option<String>,
+    pub solved: bool,
+    pub nodes: u64,
+}
+
+/// Parse suite text; blank lines and `#` comments are skipped.
+///
+/// Four-field EPD positions get default `0 1` clocks, and every best move
+/// must be legal in its position.
+pub fn parse_tactical_suite(text: &str) -> Result<Vec<TacticalPosition>, String> {
+    let mut positions = Vec::new();
+    for (line_idx, raw_line) in text.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        positions.push(
+            parse_tactical_line(line)
+                .map_err(|e| format!("tactical suite line {}: {e}", line_idx + 1))?,
+        );
+    }
+    Ok(positions)
+}
+
+/// Read and parse a tactical-suite file.
+pub fn load_tactical_suite(path: &str) -> Result<Vec<TacticalPosition>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("failed to read {path}: {e}"))?;
+    parse_tactical_suite(&text)
+}
+
+fn parse_tactical_line(line: &str) -> Result<TacticalPosition, String> {
+    let (position, operations) = line
+        .split_once(" bm ")
+        .ok_or_else(|| "missing bm operation".to_owned())?;
+    let position = position.trim();
+    let fen = match position.split_whitespace().count() {
+        4 => format!("{position} 0 1"),
+        6 => position.to_owned(),
+        n => return Err(format!("expected 4 or 6 FEN fields, found {n}")),
+    };
+
+    let mut operations = operations.split(';');
+    let best_moves: Vec<String> = operations
+        .next()
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(str::to_owned)
+        .collect();
+    if best_moves.is_empty() {
+        return Err("empty bm operation".to_owned());
+    }
+    let game_state = GameState::from_fen(&fen)?;
+    let legal_moves = generate_legal_move_list(&game_state);
+    for mv in &best_moves {
+        let description = long_algebraic_to_move_description(mv, &game_state)
+            .map_err(|e| format!("bm {mv}: {e}"))?;
+        if !legal_moves.as_slice().contains(&description) {
+            return Err(format!("bm {mv} is not legal"));
+        }
+    }
+
+    let id = operations
+        .filter_map(|op| op.trim().strip_prefix("id "))
+        .map(|id| id.trim().trim_matches('"').to_owned())
+        .next()
+        .unwrap_or_else(|| fen.clone());
+
+    Ok(TacticalPosition {
+        id,
+        fen,
+        best_moves,
+    })
+}
+
+impl TacticalPosition {
+    /// Search to `max_depth` with `features` and compare against `best_moves`.
+    pub fn solve(
+        &self,
+        scorer: &impl BoardScorer,
+        features: SearchFeatures,
+        max_depth: u8,
+    ) -> Result<TacticalResult, String> {
+        let game_state = GameState::from_fen(&self.fen)?;
+        let result = iterative_deepening_search(
+            &game_state,
+            &LegalMoveGenerator,
+            scorer,
+            SearchConfig {
+                max_depth,
+                features,
+                ..SearchConfig::default()
+            },
+        )
+        .map_err(|e| e.to_string())?;
+        let found = result
+            .best_move
+            .and_then(|mv| move_description_to_long_algebraic(mv, &game_state).ok());
+        Ok(TacticalResult {
+            id: self.id.clone(),
+            solved: found
+                .as_ref()
+                .is_some_and(|mv| self.best_moves.contains(mv)),
+            found,
+            nodes: result.nodes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_tactical_suite, WIN_AT_CHESS_SAMPLE};
+    use crate::search::board_scoring::EndgameTaperedScorerV14;
+    use crate::search::search_features::{InternalIterativeReduction, ProbCut, SearchFeatures};
+
+    #[test]
+    fn parses_bm_and_id_operations() {
+        let positions = parse_tactical_suite(WIN_AT_CHESS_SAMPLE).expect("suite should parse");
+        assert_eq!(positions.len(), 20);
+        assert_eq!(positions[0].id, "WAC.001");
+        assert_eq!(positions[0].best_moves, vec!["g3g6".to_owned()]);
+        assert!(positions[0].fen.ends_with(" w - - 0 1"));
+
+        let suite = "# comment\n\n6k1/5Q2/6K1/8/8/8/8/8 w - - bm f7g7 f7f8";
+        let positions = parse_tactical_suite(suite).expect("suite should parse");
+        assert_eq!(positions[0].best_moves.len(), 2);
+        assert_eq!(positions[0].id, "6k1/5Q2/6K1/8/8/8/8/8 w - - 0 1");
+        assert!(parse_tactical_suite("8/8/8/8 w bm a1a2").is_err());
+        assert!(parse_tactical_suite("6k1/5Q2/6K1/8/8/8/8/8 w - - bm f7a1").is_err());
+        assert!(parse_tactical_suite("6k1/5Q2/6K1/8/8/8/8/8 w - - id \"x\";").is_err());
+    }
+
+    #[test]
+    fn selectivity_features_keep_sample_tactics() {
+        let positions = parse_tactical_suite(WIN_AT_CHESS_SAMPLE).expect("suite should parse");
+        let scorer = EndgameTaperedScorerV14::standard();
+        let solved = |features: SearchFeatures| {
+            positions
+                .iter()
+                .map(|p| p.solve(&scorer, features, 5).expect("search should run"))
+                .filter(|r| r.solved)
+                .map(|r| r.id)
+                .collect::<Vec<_>>()
+        };
+
+        let baseline = solved(SearchFeatures::v15());
+        let selective = solved(SearchFeatures {
+            probcut: ProbCut::DEFAULT_ON,
+            internal_iterative_reduction: InternalIterativeReduction::DEFAULT_ON,
+            ..SearchFeatures::v15()
+        });
+        assert!(!baseline.is_empty());
+        assert!(
+            baseline.iter().all(|id| selective.contains(id)),
+            "baseline {baseline:?} selective {selective:?}"
+        );
+    }
+}
@@ -6,12 +6,14 @@
 //! actual parallel root/work splitting lands in later steps.
 
 use std::sync::{
-    atomic::{AtomicBool, AtomicU64, Ordering},
+    atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering},
     Arc, Mutex,
 };
 use std::time::Instant;
 
-use crate::search::transposition_table_v11::{TTEntry, TTStats, TranspositionTable};
+use crate::search::transposition_table_v11::{
+    replacement_priority, Bound, SearchTable, TTEntry, TTStats,
+};
 
 /// Search execution model.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -122,6 +124,9 @@ impl ThreadContextPool {
 pub struct SharedSearchState {
     stop: AtomicBool,
     pub nodes_visited: AtomicU64,
+    tt_probes: AtomicU64,
+    tt_hits: AtomicU64,
+    tt_stores: AtomicU64,
     node_budget: AtomicU64,    // 0 means unlimited
     time_budget_ms: AtomicU64, // 0 means unlimited
     started_at: Mutex<Option<Instant>>,
@@ -132,6 +137,9 @@ impl SharedSearchState {
         Arc::new(Self {
             stop: AtomicBool::new(false),
             nodes_visited: AtomicU64::new(0),
+            tt_probes: AtomicU64::new(0),
+            tt_hits: AtomicU64::new(0),
+            tt_stores: AtomicU64::new(0),
             node_budget: AtomicU64::new(0),
             time_budget_ms: AtomicU64::new(0),
             started_at: Mutex::new(None),
@@ -172,9 +180,29 @@ impl SharedSearchState {
         }
     }
 
+    /// Adds one thread's TT counts since its last report.
+    pub fn add_tt_counts(&self, probes: u64, hits: u64, stores: u64) {
+        self.tt_probes.fetch_add(probes, Ordering::Relaxed);
+        self.tt_hits.fetch_add(hits, Ordering::Relaxed);
+        self.tt_stores.fetch_add(stores, Ordering::Relaxed);
+    }
+
+    /// TT counts summed over every thread; `hashfull` is left at zero.
+    pub fn tt_counts(&self) -> TTStats {
+        TTStats {
+            probes: self.tt_probes.load(Ordering::Relaxed),
+            hits: self.tt_hits.load(Ordering::Relaxed),
+            stores: self.tt_stores.load(Ordering::Relaxed),
+            hashfull: 0,
+        }
+    }
+
     #[inline]
     pub fn reset_accounting(&self) {
         self.nodes_visited.store(0, Ordering::Relaxed);
+        self.tt_probes.store(0, Ordering::Relaxed);
+        self.tt_hits.store(0, Ordering::Relaxed);
+        self.tt_stores.store(0, Ordering::Relaxed);
         self.stop.store(false, Ordering::Relaxed);
         self.reset_started_at();
     }
@@ -206,85 +234,277 @@ impl SharedSearchState {
     }
 }
 
-/// Thread-safe transposition table façade for shared worker access.
+/// Lock-free transposition table shared by search threads.
 ///
-/// Step 2 uses coarse-grained per-shard mutexes for simplicity and correctness.
-/// A future step can replace this with lock-free or striped atomic buckets.
+/// Each slot is a pair of atomic words: the packed entry and the key XORed
+/// with it. A reader accepts a slot only if the two words agree with the
+/// probed key, so a torn write from a racing store reads as a miss instead
+/// of a corrupted move. Buckets, replacement and aging follow
+/// `transposition_table_v11`.
 #[derive(Debug)]
 pub struct SharedTranspositionTable {
-    shards: Vec<Mutex<TranspositionTable>>,
+    buckets: Vec<AtomicBucket>,
+    bucket_mask: usize,
+    current_generation: AtomicU8,
+}
+
+const SHARED_BUCKET_SIZE: usize = 4;
+
+/// One 4-way bucket, sized and aligned to a cache line.
+#[derive(Debug, Default)]
+#[repr(align(64))]
+struct AtomicBucket([AtomicSlot; SHARED_BUCKET_SIZE]);
+
+#[derive(Debug, Default)]
+struct AtomicSlot {
+    key_xor_data: AtomicU64,
+    data: AtomicU64,
+}
+
+/// Packed slot data: move (25 bits), score (16), depth (8), bound (2) and
+/// generation (8). A zero word is an empty slot since bounds start at 1.
+const MOVE_BITS: u64 = 25;
+const SCORE_SHIFT: u64 = MOVE_BITS;
+const DEPTH_SHIFT: u64 = SCORE_SHIFT + 16;
+const BOUND_SHIFT: u64 = DEPTH_SHIFT + 8;
+const GENERATION_SHIFT: u64 = BOUND_SHIFT + 2;
+
+#[inline]
+fn pack_entry(entry: TTEntry, generation: u8) -> u64 {
+    let mv = entry.best_move.unwrap_or(0);
+    debug_assert!(mv < 1 << MOVE_BITS, "move description exceeds packed width");
+    let score = entry.score.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16;
+    let bound: u64 = match entry.bound {
+        Bound::Exact => 1,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+    (mv & ((1 << MOVE_BITS) - 1))
+        | (u64::from(score as u16) << SCORE_SHIFT)
+        | (u64::from(entry.depth) << DEPTH_SHIFT)
+        | (bound << BOUND_SHIFT)
+        | (u64::from(generation) << GENERATION_SHIFT)
+}
+
+#[inline]
+fn unpack_entry(key: u64, data: u64) -> (TTEntry, u8) {
+    let mv = data & ((1 << MOVE_BITS) - 1);
+    let bound = match (data >> BOUND_SHIFT) & 0b11 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        _ => Bound::Upper,
+    };
+    let entry = TTEntry {
+        key,
+        depth: (data >> DEPTH_SHIFT) as u8,
+        score: i32::from((data >> SCORE_SHIFT) as u16 as i16),
+        bound,
+        best_move: (mv != 0).then_some(mv),
+    };
+    (entry, (data >> GENERATION_SHIFT) as u8)
+}
+
+impl AtomicSlot {
+    /// The slot's entry if it holds `key` and was not torn by a racing store.
+    #[inline]
+    fn load(&self, key: u64) -> Option<(TTEntry, u8)> {
+        let data = self.data.load(Ordering::Relaxed);
+        let key_xor_data = self.key_xor_data.load(Ordering::Relaxed);
+        (data != 0 && key_xor_data ^ data == key).then(|| unpack_entry(key, data))
+    }
+
+    /// Any resident entry with its key recovered; torn slots read as empty.
+    #[inline]
+    fn load_any(&self) -> Option<(TTEntry, u8)> {
+        let data = self.data.load(Ordering::Relaxed);
+        let key_xor_data = self.key_xor_data.load(Ordering::Relaxed);
+        (data != 0).then(|| unpack_entry(key_xor_data ^ data, data))
+    }
+
+    #[inline]
+    fn write(&self, entry: TTEntry, generation: u8) {
+        let data = pack_entry(entry, generation);
+        self.key_xor_data.store(entry.key ^ data, Ordering::Relaxed);
+        self.data.store(data, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.data.load(Ordering::Relaxed) == 0
+    }
+
+    fn clear(&self) {
+        self.data.store(0, Ordering::Relaxed);
+        self.key_xor_data.store(0, Ordering::Relaxed);
+    }
 }
 
 impl SharedTranspositionTable {
-    pub fn new_with_mb(total_mb: usize, shard_count: usize) -> Arc<Self> {
-        let shards = shard_count.max(1);
-        let mb_per_shard = (total_mb.max(1) / shards).max(1);
-        let mut vec = Vec::with_capacity(shards);
-        for _ in 0..shards {
-            vec.push(Mutex::new(TranspositionTable::new_with_mb(mb_per_shard)));
-        }
-        Arc::new(Self { shards: vec })
+    pub fn new_with_mb(size_mb: usize) -> Arc<Self> {
+        let bytes = size_mb.max(1) * 1024 * 1024;
+        let raw_bucket_count = (bytes / std::mem::size_of::<AtomicBucket>()).max(1);
+        let bucket_count = raw_bucket_count.next_power_of_two();
+        let mut buckets = Vec::with_capacity(bucket_count);
+        buckets.resize_with(bucket_count, AtomicBucket::default);
+        Arc::new(Self {
+            buckets,
+            bucket_mask: bucket_count - 1,
+            current_generation: AtomicU8::new(0),
+        })
+    }
+
+    #[inline]
+    fn bucket(&self, key: u64) -> &AtomicBucket {
+        &self.buckets[(key as usize) & self.bucket_mask]
     }
 
     #[inline]
-    fn shard_idx(&self, key: u64) -> usize {
-        (key as usize) % self.shards.len()
+    fn generation(&self) -> u8 {
+        self.current_generation.load(Ordering::Relaxed)
     }
 
     pub fn probe(&self, key: u64) -> Option<TTEntry> {
-        let idx = self.shard_idx(key);
-        let Ok(mut guard) = self.shards[idx].lock() else {
-            return None;
-        };
-        guard.probe(key)
+        let generation = self.generation();
+        for slot in &self.bucket(key).0 {
+            if let Some((entry, slot_generation)) = slot.load(key) {
+                if slot_generation != generation {
+                    slot.write(entry, generation);
+                }
+                return Some(entry);
+            }
+        }
+        None
     }
 
+    /// Same policy as `TranspositionTable::store`: same key replaced only by
+    /// equal or greater depth, then an empty slot, then the weakest resident
+    /// if the incoming entry is at least as strong.
     pub fn store(&self, entry: TTEntry) {
-        let idx = self.shard_idx(entry.key);
-        if let Ok(mut guard) = self.shards[idx].lock() {
-            guard.store(entry);
+        let generation = self.generation();
+        let bucket = &self.bucket(entry.key).0;
+
+        for slot in bucket {
+            if let Some((existing, _)) = slot.load(entry.key) {
+                if entry.depth >= existing.depth {
+                    slot.write(entry, generation);
+                }
+                return;
+            }
+        }
+
+        if let Some(slot) = bucket.iter().find(|slot| slot.is_empty()) {
+            slot.write(entry, generation);
+            return;
+        }
+
+        let mut victim_idx = 0usize;
+        let mut victim_score = i32::MAX;
+        for (i, slot) in bucket.iter().enumerate() {
+            let score = slot
+                .load_any()
+                .map_or(i32::MIN, |(existing, slot_generation)| {
+                    replacement_priority(existing, slot_generation, generation)
+                });
+            if score < victim_score {
+                victim_score = score;
+                victim_idx = i;
+            }
+        }
+        if replacement_priority(entry, generation, generation) >= victim_score {
+            bucket[victim_idx].write(entry, generation);
         }
     }
 
+    /// Empties every slot; callers must not search concurrently.
     pub fn clear(&self) {
-        for shard in &self.shards {
-            if let Ok(mut guard) = shard.lock() {
-                guard.clear();
+        for bucket in &self.buckets {
+            for slot in &bucket.0 {
+                slot.clear();
             }
         }
+        self.current_generation.store(0, Ordering::Relaxed);
     }
 
     pub fn new_generation(&self) {
-        for shard in &self.shards {
-            if let Ok(mut guard) = shard.lock() {
-                guard.new_generation();
-            }
-        }
+        self.current_generation.fetch_add(1, Ordering::Relaxed);
     }
 
     pub fn len(&self) -> usize {
-        self.shards
+        self.buckets.len() * SHARED_BUCKET_SIZE
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Per-mille occupancy by current-generation entries, sampled from the
+    /// first 1000 slots as UCI `hashfull` expects.
+    pub fn hashfull(&self) -> u16 {
+        let generation = self.generation();
+        let sample_buckets = self.buckets.len().min(1000 / SHARED_BUCKET_SIZE);
+        let sampled = sample_buckets * SHARED_BUCKET_SIZE;
+        let used = self.buckets[..sample_buckets]
             .iter()
-            .filter_map(|s| s.lock().ok().map(|g| g.len()))
-            .sum()
+            .flat_map(|bucket| &bucket.0)
+            .filter(|slot| slot.load_any().is_some_and(|(_, g)| g == generation))
+            .count();
+        (used * 1000 / sampled.max(1)) as u16
+    }
+}
+
+/// One search thread's view of a `SharedTranspositionTable`.
+///
+/// Probe, hit and store counts are kept here in plain integers, so threads
+/// never contend on counters; Lazy SMP adds each thread's counts to its
+/// `SharedSearchState` at the end of every iteration.
+#[derive(Debug)]
+pub struct SharedTableHandle<'a> {
+    table: &'a SharedTranspositionTable,
+    probes: u64,
+    hits: u64,
+    stores: u64,
+}
+
+impl<'a> SharedTableHandle<'a> {
+    pub fn new(table: &'a SharedTranspositionTable) -> Self {
+        Self {
+            table,
+            probes: 0,
+            hits: 0,
+            stores: 0,
+        }
     }
+}
 
+impl SearchTable for SharedTableHandle<'_> {
     #[inline]
-    pub fn shard_count(&self) -> usize {
-        self.shards.len()
-    }
-
-    pub fn stats(&self) -> TTStats {
-        let mut merged = TTStats::default();
-        for shard in &self.shards {
-            if let Ok(guard) = shard.lock() {
-                let s = guard.stats();
-                merged.probes += s.probes;
-                merged.hits += s.hits;
-                merged.stores += s.stores;
-            }
+    fn probe(&mut self, key: u64) -> Option<TTEntry> {
+        self.probes += 1;
+        let entry = self.table.probe(key);
+        self.hits += u64::from(entry.is_some());
+        entry
+    }
+
+    #[inline]
+    fn store(&mut self, entry: TTEntry) {
+        self.stores += 1;
+        self.table.store(entry);
+    }
+
+    #[inline]
+    fn new_generation(&mut self) {
+        self.table.new_generation();
+    }
+
+    /// This thread's counts; `hashfull` covers the whole table.
+    fn stats(&self) -> TTStats {
+        TTStats {
+            probes: self.probes,
+            hits: self.hits,
+            stores: self.stores,
+            hashfull: self.table.hashfull(),
         }
-        merged
     }
 }
 
@@ -324,7 +544,7 @@ mod tests {
 
     #[test]
     fn shared_tt_store_and_probe() {
-        let tt = SharedTranspositionTable::new_with_mb(4, 2);
+        let tt = SharedTranspositionTable::new_with_mb(4);
         let entry = TTEntry {
             key: 12345,
             depth: 6,
@@ -339,9 +559,136 @@ mod tests {
     }
 
     #[test]
-    fn shared_tt_reports_shard_count() {
-        let tt = SharedTranspositionTable::new_with_mb(8, 6);
-        assert_eq!(tt.shard_count(), 6);
+    fn shared_tt_round_trips_packed_fields() {
+        let tt = SharedTranspositionTable::new_with_mb(1);
+        for (key, score, bound, best_move) in [
+            (1u64, -29_950, Bound::Upper, None),
+            (2, 29_990, Bound::Lower, Some((1u64 << 25) - 1)),
+            (3, 0, Bound::Exact, Some(0x0012_3456)),
+        ] {
+            tt.store(TTEntry {
+                key,
+                depth: 255,
+                score,
+                bound,
+                best_move,
+            });
+            let got = tt.probe(key).expect("entry should exist");
+            assert_eq!(got.score, score);
+            assert_eq!(got.depth, 255);
+            assert_eq!(got.bound, bound);
+            assert_eq!(got.best_move, best_move);
+        }
+    }
+
+    #[test]
+    fn shared_tt_rejects_torn_slot() {
+        let tt = SharedTranspositionTable::new_with_mb(1);
+        let entry = TTEntry {
+            key: 0xABCD,
+            depth: 3,
+            score: 10,
+            bound: Bound::Exact,
+            best_move: Some(77),
+        };
+        tt.store(entry);
+        // Half of a racing store for another key landed in the same slot.
+        let slot = tt
+            .bucket(entry.key)
+            .0
+            .iter()
+            .find(|s| !s.is_empty())
+            .unwrap();
+        slot.data.store(
+            pack_entry(
+                TTEntry {
+                    key: 0x1ABCD,
+                    best_move: Some(99),
+                    ..entry
+                },
+                0,
+            ),
+            Ordering::Relaxed,
+        );
+        assert!(tt.probe(entry.key).is_none());
+        assert!(tt.probe(0x1ABCD).is_none());
+    }
+
+    #[test]
+    fn shared_tt_keeps_deeper_entry_for_same_key() {
+        let tt = SharedTranspositionTable::new_with_mb(1);
+        let deep = TTEntry {
+            key: 42,
+            depth: 8,
+            score: 5,
+            bound: Bound::Exact,
+            best_move: Some(11),
+        };
+        tt.store(deep);
+        tt.store(TTEntry { depth: 2, ..deep });
+        assert_eq!(tt.probe(42).map(|e| e.depth), Some(8));
+    }
+
+    #[test]
+    fn shared_tt_hashfull_tracks_current_generation() {
+        let tt = SharedTranspositionTable::new_with_mb(1);
+        assert_eq!(tt.hashfull(), 0);
+        for key in 0..1000u64 {
+            tt.store(TTEntry {
+                key,
+                depth: 1,
+                score: 0,
+                bound: Bound::Exact,
+                best_move: None,
+            });
+        }
+        assert!(tt.hashfull() > 0);
+        tt.new_generation();
+        assert_eq!(tt.hashfull(), 0);
+        tt.clear();
+        assert!(tt.probe(1).is_none());
+    }
+
+    #[test]
+    fn shared_tt_never_returns_corrupted_entries_under_contention() {
+        // Every key's payload is derived from the key, so any hit whose move
+        // or score disagrees with its key is a torn or mixed entry.
+        fn payload(key: u64) -> (u64, i32) {
+            let mv = (key.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 40) & ((1 << 25) - 1);
+            (mv.max(1), (key % 20_000) as i32 - 10_000)
+        }
+
+        let tt = SharedTranspositionTable::new_with_mb(1);
+        let threads = 4u64;
+        let rounds = 200_000u64;
+        std::thread::scope(|scope| {
+            for t in 0..threads {
+                let tt = tt.as_ref();
+                scope.spawn(move || {
+                    let mut x = 0x2545_F491_4F6C_DD1D ^ t;
+                    for i in 0..rounds {
+                        x ^= x << 13;
+                        x ^= x >> 7;
+                        x ^= x << 17;
+                        // Few distinct buckets so stores keep colliding.
+                        let key = (x & !0xFFFF) | (x & 0xFF);
+                        let (mv, score) = payload(key);
+                        if i % 2 == 0 {
+                            tt.store(TTEntry {
+                                key,
+                                depth: (x >> 8) as u8 % 32,
+                                score,
+                                bound: Bound::Lower,
+                                best_move: Some(mv),
+                            });
+                        } else if let Some(hit) = tt.probe(key) {
+                            assert_eq!(hit.key, key);
+                            assert_eq!((hit.best_move, hit.score), (Some(mv), score));
+                        }
+                    }
+                });
+            }
+        });
     }
 
     #[test]
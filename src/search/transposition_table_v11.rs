@@ -26,6 +26,8 @@ pub struct TTStats {
     pub probes: u64,
     pub hits: u64,
     pub stores: u64,
+    /// Current-generation occupancy per mille, as reported by UCI `hashfull`.
+    pub hashfull: u16,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -76,9 +78,24 @@ impl TranspositionTable {
         self.buckets.len() * Self::BUCKET_SIZE
     }
 
-    #[inline]
     pub fn stats(&self) -> TTStats {
-        self.stats
+        TTStats {
+            hashfull: self.hashfull(),
+            ..self.stats
+        }
+    }
+
+    /// Per-mille occupancy by current-generation entries, sampled from the
+    /// first 1000 slots as UCI `hashfull` expects.
+    pub fn hashfull(&self) -> u16 {
+        let sample_buckets = self.buckets.len().min(1000 / Self::BUCKET_SIZE);
+        let sampled = sample_buckets * Self::BUCKET_SIZE;
+        let used = self.buckets[..sample_buckets]
+            .iter()
+            .flatten()
+            .filter(|s| s.entry.is_some() && s.generation == self.current_generation)
+            .count();
+        (used * 1000 / sampled.max(1)) as u16
     }
 
     #[inline]
@@ -154,8 +171,42 @@ impl TranspositionTable {
     }
 }
 
+/// Table operations used by the search core, so one thread can search on its
+/// own `TranspositionTable` while Lazy SMP threads share a
+/// `SharedTranspositionTable`.
+pub trait SearchTable {
+    fn probe(&mut self, key: u64) -> Option<TTEntry>;
+    fn store(&mut self, entry: TTEntry);
+    fn new_generation(&mut self);
+    fn stats(&self) -> TTStats;
+}
+
+impl SearchTable for TranspositionTable {
+    #[inline]
+    fn probe(&mut self, key: u64) -> Option<TTEntry> {
+        TranspositionTable::probe(self, key)
+    }
+
+    #[inline]
+    fn store(&mut self, entry: TTEntry) {
+        TranspositionTable::store(self, entry);
+    }
+
+    #[inline]
+    fn new_generation(&mut self) {
+        TranspositionTable::new_generation(self);
+    }
+
+    #[inline]
+    fn stats(&self) -> TTStats {
+        TranspositionTable::stats(self)
+    }
+}
+
+/// Depth/bound/age score of a resident entry; the lowest in a full bucket is
+/// evicted. Shared with the lock-free `SharedTranspositionTable`.
 #[inline]
-fn replacement_priority(entry: TTEntry, generation: u8, current_generation: u8) -> i32 {
+pub(crate) fn replacement_priority(entry: TTEntry, generation: u8, current_generation: u8) -> i32 {
     let age = current_generation.wrapping_sub(generation) as i32;
     let bound_bonus = match entry.bound {
         Bound::Exact => 8,
@@ -215,4 +266,23 @@ mod tests {
         assert_eq!(got.depth, 6);
         assert_eq!(got.score, 3);
     }
+
+    #[test]
+    fn hashfull_counts_only_current_generation() {
+        let mut tt = TranspositionTable::new_with_mb(1);
+        assert_eq!(tt.hashfull(), 0);
+        for key in 0..250u64 {
+            tt.store(TTEntry {
+                key,
+                depth: 1,
+                score: 0,
+                bound: Bound::Exact,
+                best_move: None,
+            });
+        }
+        assert_eq!(tt.hashfull(), 250);
+        assert_eq!(tt.stats().hashfull, 250);
+        tt.new_generation();
+        assert_eq!(tt.hashfull(), 0);
+    }
 }
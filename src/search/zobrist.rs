@@ -1,11 +1,18 @@
 //! Zobrist hashing support for fast position identity and repetition tracking.
 //!
 //! The keys are generated from a fixed seed so hashes are deterministic across
-//! runs, which is useful for testing and debugging.
+//! runs, which is useful for testing and debugging. With the `polyglot-zobrist`
+//! feature the standard-chess keys are the Polyglot `Random64` table instead,
+//! so `zobrist_key` equals the Polyglot book key.
 
 use std::sync::OnceLock;
 
 use crate::game_state::{chess_types::*, game_state::GameState};
+#[cfg(feature = "polyglot-zobrist")]
+use crate::search::polyglot::{
+    en_passant_capture_possible, polyglot_castling_key, polyglot_piece_square_key,
+    POLYGLOT_EN_PASSANT_OFFSET, POLYGLOT_RANDOM_64, POLYGLOT_TURN_OFFSET,
+};
 
 #[derive(Debug)]
 struct ZobristTables {
@@ -13,6 +20,7 @@ struct ZobristTables {
     side_to_move: u64,
     castling: [u64; 16],
     en_passant_file: [u64; 8],
+    castling_rook_file: [[u64; 8]; 2],
 }
 
 static TABLES: OnceLock<ZobristTables> = OnceLock::new();
@@ -46,12 +54,50 @@ fn build_tables() -> ZobristTables {
         *key = next_random_u64(&mut seed);
     }
 
-    ZobristTables {
+    // Drawn last so the standard-chess keys above keep their values.
+    let mut castling_rook_file = [[0u64; 8]; 2];
+    for color in &mut castling_rook_file {
+        for key in color {
+            *key = next_random_u64(&mut seed);
+        }
+    }
+
+    #[allow(unused_mut)]
+    let mut tables = ZobristTables {
         piece_square,
         side_to_move,
         castling,
         en_passant_file,
+        castling_rook_file,
+    };
+    #[cfg(feature = "polyglot-zobrist")]
+    use_polyglot_keys(&mut tables);
+    tables
+}
+
+/// Replace the standard-chess keys with the Polyglot table; Chess960 rook-file
+/// keys stay seeded since Polyglot has none.
+#[cfg(feature = "polyglot-zobrist")]
+fn use_polyglot_keys(tables: &mut ZobristTables) {
+    for color in [Color::Light, Color::Dark] {
+        for piece in [
+            PieceKind::Pawn,
+            PieceKind::Knight,
+            PieceKind::Bishop,
+            PieceKind::Rook,
+            PieceKind::Queen,
+            PieceKind::King,
+        ] {
+            for sq in 0..64u8 {
+                tables.piece_square[color.index()][piece.index()][sq as usize] =
+                    polyglot_piece_square_key(color, piece, sq);
+            }
+        }
     }
+    tables.side_to_move = POLYGLOT_RANDOM_64[POLYGLOT_TURN_OFFSET];
+    tables.castling = std::array::from_fn(|mask| polyglot_castling_key(mask as CastlingRights));
+    tables.en_passant_file =
+        std::array::from_fn(|file| POLYGLOT_RANDOM_64[POLYGLOT_EN_PASSANT_OFFSET + file]);
 }
 
 #[inline]
@@ -66,7 +112,7 @@ fn next_random_u64(state: &mut u64) -> u64 {
 
 /// Return the Zobrist key for a `(color, piece, square)` occupancy term.
 #[inline]
-pub fn piece_square_key(color: Color, piece: PieceKind, square: Square) -> u64 {
+pub fn piece_square_key(color: Color, piece: PieceKind, square: SquareIndex) -> u64 {
     tables().piece_square[color.index()][piece.index()][square as usize]
 }
 
@@ -76,18 +122,59 @@ pub fn castling_key(castling_rights: CastlingRights) -> u64 {
     tables().castling[(castling_rights & 0x0F) as usize]
 }
 
+/// Return the full castling contribution, including Chess960 rook files.
+///
+/// Rights whose rook sits on the standard a/h file hash exactly like
+/// `castling_key`; any other castling rook file mixes in an extra key.
+#[inline]
+pub fn castling_rights_key(castling_rights: CastlingRights, rook_files: &CastlingRookFiles) -> u64 {
+    let mut key = castling_key(castling_rights);
+    for color in [Color::Light, Color::Dark] {
+        for side in [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN] {
+            let file = rook_files[color.index()][side];
+            if (castling_rights & castling_right_flag(color, side)) != 0
+                && file != STANDARD_CASTLING_ROOK_FILES[color.index()][side]
+            {
+                key ^= tables().castling_rook_file[color.index()][file as usize];
+            }
+        }
+    }
+    key
+}
+
 /// Return the Zobrist key contribution for a valid en-passant file.
 #[inline]
 pub fn en_passant_file_key(file: u8) -> u64 {
     tables().en_passant_file[file as usize]
 }
 
-/// Return the side-to-move toggle key (xor in when dark to move).
+/// En-passant contribution of the current position.
+///
+/// By default any en-passant square hashes its file. Under `polyglot-zobrist`
+/// the file is only hashed when a pawn of the side to move can capture there.
+#[inline]
+pub fn en_passant_key(game_state: &GameState) -> u64 {
+    match game_state.en_passant_square {
+        #[cfg(feature = "polyglot-zobrist")]
+        Some(ep_square) if !en_passant_capture_possible(game_state, ep_square) => 0,
+        Some(ep_square) => en_passant_file_key(ep_square % 8),
+        None => 0,
+    }
+}
+
+/// Return the side-to-move toggle key (xor in when dark to move, or when light
+/// to move under `polyglot-zobrist`).
 #[inline]
 pub fn side_to_move_key() -> u64 {
     tables().side_to_move
 }
 
+/// Side whose turn mixes in `side_to_move_key`.
+#[cfg(not(feature = "polyglot-zobrist"))]
+const KEYED_SIDE: Color = Color::Dark;
+#[cfg(feature = "polyglot-zobrist")]
+const KEYED_SIDE: Color = Color::Light;
+
 /// Compute the full position Zobrist key from the complete game state.
 pub fn compute_zobrist_key(game_state: &GameState) -> u64 {
     let mut key = 0u64;
@@ -103,23 +190,19 @@ pub fn compute_zobrist_key(game_state: &GameState) -> u64 {
         ] {
             let mut bb = game_state.pieces[color.index()][piece.index()];
             while bb != 0 {
-                let sq = bb.trailing_zeros() as Square;
+                let sq = bb.trailing_zeros() as SquareIndex;
                 key ^= piece_square_key(color, piece, sq);
                 bb &= bb - 1;
             }
         }
     }
 
-    if game_state.side_to_move == Color::Dark {
+    if game_state.side_to_move == KEYED_SIDE {
         key ^= side_to_move_key();
     }
 
-    key ^= castling_key(game_state.castling_rights);
-
-    if let Some(ep_square) = game_state.en_passant_square {
-        let file = ep_square % 8;
-        key ^= en_passant_file_key(file);
-    }
+    key ^= castling_rights_key(game_state.castling_rights, &game_state.castling_rook_files);
+    key ^= en_passant_key(game_state);
 
     key
 }
@@ -135,7 +218,7 @@ pub fn compute_pawn_zobrist_key(game_state: &GameState) -> u64 {
         for piece in [PieceKind::Pawn, PieceKind::King] {
             let mut bb = game_state.pieces[color.index()][piece.index()];
             while bb != 0 {
-                let sq = bb.trailing_zeros() as Square;
+                let sq = bb.trailing_zeros() as SquareIndex;
                 key ^= piece_square_key(color, piece, sq);
                 bb &= bb - 1;
             }
@@ -183,6 +266,7 @@ mod tests {
         assert_ne!(with_rights.zobrist_key, without_rights.zobrist_key);
     }
 
+    #[cfg(not(feature = "polyglot-zobrist"))]
     #[test]
     fn en_passant_file_changes_hash() {
         let no_ep =
@@ -203,4 +287,43 @@ mod tests {
         refresh_game_state_hashes(&mut next);
         assert_eq!(next.zobrist_key, compute_zobrist_key(&next));
     }
+
+    #[cfg(feature = "polyglot-zobrist")]
+    #[test]
+    fn polyglot_mode_keys_equal_polyglot_keys_through_make_unmake() {
+        use crate::move_generation::legal_move_apply::{make_move_in_place, unmake_move_in_place};
+        use crate::move_generation::legal_move_generator::generate_legal_move_list;
+        use crate::search::polyglot::compute_polyglot_key;
+
+        let mut game = GameState::new_game();
+        assert_eq!(game.zobrist_key, 0x463B_9618_1691_FC9C);
+        for lan in ["a2a4", "b7b5", "h2h4", "b5b4", "c2c4"] {
+            let mv = long_algebraic_to_move_description(lan, &game).expect("move should parse");
+            make_move_in_place(&mut game, mv).expect("move should apply");
+            assert_eq!(game.zobrist_key, compute_polyglot_key(&game), "{lan}");
+        }
+        assert_eq!(game.zobrist_key, 0x3C81_23EA_7B06_7637);
+
+        // An en-passant square nobody can capture on is not hashed.
+        let no_ep =
+            GameState::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1").expect("FEN should parse");
+        let ep = GameState::from_fen("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1").expect("FEN should parse");
+        assert_eq!(no_ep.zobrist_key, ep.zobrist_key);
+
+        for mv in generate_legal_move_list(&game) {
+            make_move_in_place(&mut game, mv).expect("move should apply");
+            assert_eq!(game.zobrist_key, compute_polyglot_key(&game));
+            unmake_move_in_place(&mut game).expect("move should unmake");
+        }
+    }
+
+    #[test]
+    fn chess960_castling_rook_file_changes_hash() {
+        let outer =
+            GameState::from_fen("4k3/8/8/8/8/8/8/4KR1R w H - 0 1").expect("FEN should parse");
+        let inner =
+            GameState::from_fen("4k3/8/8/8/8/8/8/4KR1R w F - 0 1").expect("FEN should parse");
+        assert_ne!(outer.zobrist_key, inner.zobrist_key);
+        assert_eq!(inner.zobrist_key, compute_zobrist_key(&inner));
+    }
 }
@@ -9,13 +9,14 @@ use std::path::Path;
 
 use rand::Rng;
 
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
 use crate::move_generation::legal_move_apply::apply_move;
 use crate::utils::long_algebraic::long_algebraic_to_move_description;
 
 #[derive(Debug, Clone)]
 pub struct BookMove {
-    pub move_description: u64,
+    pub mv: Move,
     pub weight: u32,
 }
 
@@ -111,7 +112,7 @@ impl OpeningBook {
                 let mut row = Vec::with_capacity(moves.len());
                 for (mv, weight) in moves {
                     row.push(BookMove {
-                        move_description: mv,
+                        mv: Move::from_raw(mv),
                         weight,
                     });
                 }
@@ -132,7 +133,7 @@ impl OpeningBook {
         &self,
         game_state: &GameState,
         rng: &mut R,
-    ) -> Option<u64> {
+    ) -> Option<Move> {
         let moves = self.moves_for(game_state)?;
         if moves.is_empty() {
             return None;
@@ -140,19 +141,19 @@ impl OpeningBook {
 
         let total_weight: u64 = moves.iter().map(|m| u64::from(m.weight)).sum();
         if total_weight == 0 {
-            return Some(moves[0].move_description);
+            return Some(moves[0].mv);
         }
 
         let mut pick = rng.random_range(0..total_weight);
         for m in moves {
             let w = u64::from(m.weight);
             if pick < w {
-                return Some(m.move_description);
+                return Some(m.mv);
             }
             pick -= w;
         }
 
-        Some(moves[0].move_description)
+        Some(moves[0].mv)
     }
 }
 
@@ -185,7 +186,7 @@ mod tests {
         let mv = book
             .choose_weighted_move(&start, &mut rng)
             .expect("book should choose");
-        let lan = move_description_to_long_algebraic(mv, &start).expect("lan conversion");
+        let lan = move_description_to_long_algebraic(mv.raw(), &start).expect("lan conversion");
         assert!(lan == "e2e4" || lan == "d2d4");
     }
 }
@@ -6,20 +6,25 @@
 use std::io::{self, BufRead, Write};
 use std::sync::{
     atomic::{AtomicBool, Ordering},
-    mpsc, Arc, Mutex,
+    mpsc, Arc, Mutex, MutexGuard, PoisonError,
 };
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use crate::engines::engine_greedy::GreedyEngine;
 use crate::engines::engine_humanized_v5::HumanizedEngineV5;
 use crate::engines::engine_iterative_v16::IterativeEngine;
 use crate::engines::engine_iterative_v17::IterativeEngineV17;
 use crate::engines::engine_random::RandomEngine;
-use crate::engines::engine_trait::{Engine, GoParams};
+use crate::engines::engine_trait::{Engine, GoParams, Score};
+use crate::engines::time_management::{resolve_go_params, TimeManagementStrategy};
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
+use crate::game_state::game_status::GameStatus;
 use crate::move_generation::legal_move_apply::apply_move;
 use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
-use crate::search::board_scoring::MATE_SCORE;
+use crate::move_generation::perft::{format_perft_divide, perft_divide};
+use crate::search::search_observer::{SearchInfo, SearchObserver};
 use crate::utils::long_algebraic::{
     long_algebraic_to_move_description, move_description_to_long_algebraic,
 };
@@ -27,13 +32,15 @@ use crate::utils::long_algebraic::{
 const UCI_ENGINE_NAME: &str = "Plum Chess";
 const UCI_ENGINE_AUTHOR: &str = "jwkunz using Codex";
 const UCI_ENGINE_ABOUT: &str = "Plum Chess by jwkunz using Codex";
+/// Search time after `ponderhit` when `go ponder` carried no clock or limit.
+const PONDERHIT_DEFAULT_MOVETIME_MS: u64 = 250;
 
 pub fn run_stdio_loop() -> io::Result<()> {
     let (output_tx, output_rx) = mpsc::channel::<String>();
     let output_thread = thread::spawn(move || -> io::Result<()> {
         for line in output_rx {
-            // Acquire and release stdout lock per line so the main UCI command
-            // loop can also write synchronously without deadlocking.
+            // All output, including command replies, arrives here so lines
+            // streamed by a running search stay ahead of its `bestmove`.
             let stdout = io::stdout();
             let mut lock = stdout.lock();
             writeln!(lock, "{}", line)?;
@@ -46,11 +53,11 @@ pub fn run_stdio_loop() -> io::Result<()> {
     let mut uci = UciState::new();
     uci.set_async_info_sender(Some(output_tx.clone()));
 
+    let mut out = ChannelLineWriter::new(output_tx.clone());
     for line in stdin.lock().lines() {
         let line = line?;
-        let mut stdout = io::stdout();
-        let should_quit = uci.handle_command(&line, &mut stdout)?;
-        stdout.flush()?;
+        let should_quit = uci.handle_command(&line, &mut out)?;
+        out.flush()?;
         if should_quit {
             break;
         }
@@ -58,6 +65,7 @@ pub fn run_stdio_loop() -> io::Result<()> {
 
     uci.set_async_info_sender(None);
     drop(uci);
+    drop(out);
     drop(output_tx);
     output_thread
         .join()
@@ -66,9 +74,64 @@ pub fn run_stdio_loop() -> io::Result<()> {
     Ok(())
 }
 
+/// `Write` adapter that forwards each complete line to the output thread.
+struct ChannelLineWriter {
+    tx: mpsc::Sender<String>,
+    pending: Vec<u8>,
+}
+
+impl ChannelLineWriter {
+    fn new(tx: mpsc::Sender<String>) -> Self {
+        Self {
+            tx,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Write for ChannelLineWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        while let Some(end) = self.pending.iter().position(|b| *b == b'\n') {
+            let line = String::from_utf8_lossy(&self.pending[..end]).into_owned();
+            self.pending.drain(..=end);
+            self.tx
+                .send(line)
+                .map_err(|_| io::Error::other("output thread stopped"))?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Renders search reports as UCI `info` lines for the output channel.
+struct UciInfoObserver {
+    root: GameState,
+    tx: mpsc::Sender<String>,
+}
+
+impl SearchObserver for UciInfoObserver {
+    fn on_iteration(&self, info: &SearchInfo) {
+        let _ = self.tx.send(format_search_info_line(info, &self.root));
+    }
+
+    fn on_best_move(&self, info: &SearchInfo) {
+        let _ = self.tx.send(format_search_info_line(info, &self.root));
+    }
+
+    fn on_progress(&self, info: &SearchInfo) {
+        let _ = self.tx.send(format_search_info_line(info, &self.root));
+    }
+}
+
 struct UciState {
     game_state: GameState,
-    engine: Box<dyn Engine>,
+    /// One engine for the whole session, lent to the search thread during
+    /// `go` so its hash and history tables carry over between moves.
+    engine: Arc<Mutex<Box<dyn Engine>>>,
     skill_level: u8,
     limit_strength: bool,
     uci_elo: u16,
@@ -79,6 +142,11 @@ struct UciState {
     deterministic_search: bool,
     root_parallel_min_depth: u8,
     root_parallel_min_moves: usize,
+    singular_extensions: bool,
+    probcut: bool,
+    probcut_margin: i32,
+    iir: bool,
+    iir_min_depth: u8,
     own_book: bool,
     ponder: bool,
     analyse_mode: bool,
@@ -100,9 +168,26 @@ struct AsyncSearchHandle {
     error: Arc<Mutex<Option<String>>>,
     go_params: GoParams,
     is_ponder: bool,
+    /// Set after `ponderhit` so the worker sends its own `bestmove` when the
+    /// move clock runs out; whoever swaps it back to `false` reports.
+    worker_reports: Arc<AtomicBool>,
+    worker_reports_armed: bool,
     handle: JoinHandle<()>,
 }
 
+impl AsyncSearchHandle {
+    /// Whether the caller, rather than the worker, must print `bestmove`.
+    fn claim_bestmove(&self) -> bool {
+        !self.worker_reports_armed || self.worker_reports.swap(false, Ordering::AcqRel)
+    }
+
+    /// The worker stores its outcome before checking `worker_reports`.
+    fn search_finished(&self) -> bool {
+        self.latest.lock().is_ok_and(|r| r.is_some())
+            || self.error.lock().is_ok_and(|e| e.is_some())
+    }
+}
+
 impl UciState {
     fn new() -> Self {
         let skill_level = 1;
@@ -120,7 +205,7 @@ impl UciState {
         let _ = engine.set_option("OwnBook", if own_book { "true" } else { "false" });
         Self {
             game_state: GameState::new_game(),
-            engine,
+            engine: Arc::new(Mutex::new(engine)),
             skill_level,
             limit_strength,
             uci_elo,
@@ -131,6 +216,11 @@ impl UciState {
             deterministic_search: false,
             root_parallel_min_depth,
             root_parallel_min_moves,
+            singular_extensions: false,
+            probcut: false,
+            probcut_margin: 200,
+            iir: false,
+            iir_min_depth: 4,
             own_book,
             ponder: false,
             analyse_mode: false,
@@ -147,6 +237,12 @@ impl UciState {
         }
     }
 
+    /// Only contended while an async search runs; every command that
+    /// touches the engine stops that search first.
+    fn engine(&self) -> MutexGuard<'_, Box<dyn Engine>> {
+        self.engine.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
     fn set_async_info_sender(&mut self, sender: Option<mpsc::Sender<String>>) {
         self.async_info_tx = sender;
     }
@@ -200,6 +296,20 @@ impl UciState {
                     out,
                     "option name RootParallelMinMoves type spin default 2 min 2 max 256"
                 )?;
+                writeln!(
+                    out,
+                    "option name SingularExtensions type check default false"
+                )?;
+                writeln!(out, "option name ProbCut type check default false")?;
+                writeln!(
+                    out,
+                    "option name ProbCutMargin type spin default 200 min 0 max 1000"
+                )?;
+                writeln!(out, "option name IIR type check default false")?;
+                writeln!(
+                    out,
+                    "option name IIRMinDepth type spin default 4 min 2 max 32"
+                )?;
                 writeln!(out, "option name Ponder type check default false")?;
                 writeln!(out, "option name UCI_AnalyseMode type check default false")?;
                 writeln!(out, "option name UCI_Chess960 type check default false")?;
@@ -238,7 +348,7 @@ impl UciState {
             "ucinewgame" => {
                 let _ = self.stop_async_search_and_collect();
                 self.game_state = GameState::new_game();
-                self.engine.new_game();
+                self.engine().new_game();
             }
             "position" => {
                 if let Err(err) = self.handle_position(trimmed) {
@@ -327,7 +437,7 @@ impl UciState {
                 .parse::<usize>()
                 .map_err(|_| format!("invalid MultiPV value '{}'", value))?;
             self.multipv = parsed.clamp(1, 32);
-            self.engine
+            self.engine()
                 .set_option("MultiPV", &self.multipv.to_string())?;
         } else if name.eq_ignore_ascii_case("FixedDepth") {
             let parsed = value
@@ -339,21 +449,22 @@ impl UciState {
                 .parse::<usize>()
                 .map_err(|_| format!("invalid Hash value '{}'", value))?;
             self.hash_mb = parsed.max(1);
-            self.engine.set_option("Hash", &self.hash_mb.to_string())?;
+            self.engine()
+                .set_option("Hash", &self.hash_mb.to_string())?;
         } else if name.eq_ignore_ascii_case("Clear Hash") {
             // UCI button option: clear transposition state without changing position.
-            self.engine.new_game();
+            self.engine().new_game();
         } else if name.eq_ignore_ascii_case("Threads") {
             let parsed = value
                 .parse::<usize>()
                 .map_err(|_| format!("invalid Threads value '{}'", value))?;
             self.threads = parsed.max(1);
-            self.engine
+            self.engine()
                 .set_option("Threads", &self.threads.to_string())?;
         } else if name.eq_ignore_ascii_case("DeterministicSearch") {
             let lower = value.to_ascii_lowercase();
             self.deterministic_search = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
-            self.engine.set_option(
+            self.engine().set_option(
                 "DeterministicSearch",
                 if self.deterministic_search {
                     "true"
@@ -366,7 +477,7 @@ impl UciState {
                 .parse::<u8>()
                 .map_err(|_| format!("invalid RootParallelMinDepth value '{}'", value))?;
             self.root_parallel_min_depth = parsed.max(1);
-            self.engine.set_option(
+            self.engine().set_option(
                 "RootParallelMinDepth",
                 &self.root_parallel_min_depth.to_string(),
             )?;
@@ -375,26 +486,61 @@ impl UciState {
                 .parse::<usize>()
                 .map_err(|_| format!("invalid RootParallelMinMoves value '{}'", value))?;
             self.root_parallel_min_moves = parsed.max(2);
-            self.engine.set_option(
+            self.engine().set_option(
                 "RootParallelMinMoves",
                 &self.root_parallel_min_moves.to_string(),
             )?;
+        } else if name.eq_ignore_ascii_case("SingularExtensions") {
+            let lower = value.to_ascii_lowercase();
+            self.singular_extensions = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
+            self.engine().set_option(
+                "SingularExtensions",
+                if self.singular_extensions {
+                    "true"
+                } else {
+                    "false"
+                },
+            )?;
+        } else if name.eq_ignore_ascii_case("ProbCut") {
+            let lower = value.to_ascii_lowercase();
+            self.probcut = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
+            self.engine()
+                .set_option("ProbCut", if self.probcut { "true" } else { "false" })?;
+        } else if name.eq_ignore_ascii_case("ProbCutMargin") {
+            let parsed = value
+                .parse::<i32>()
+                .map_err(|_| format!("invalid ProbCutMargin value '{}'", value))?;
+            self.probcut_margin = parsed.clamp(0, 1000);
+            self.engine()
+                .set_option("ProbCutMargin", &self.probcut_margin.to_string())?;
+        } else if name.eq_ignore_ascii_case("IIR") {
+            let lower = value.to_ascii_lowercase();
+            self.iir = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
+            self.engine()
+                .set_option("IIR", if self.iir { "true" } else { "false" })?;
+        } else if name.eq_ignore_ascii_case("IIRMinDepth") {
+            let parsed = value
+                .parse::<u8>()
+                .map_err(|_| format!("invalid IIRMinDepth value '{}'", value))?;
+            self.iir_min_depth = parsed.clamp(2, 32);
+            self.engine()
+                .set_option("IIRMinDepth", &self.iir_min_depth.to_string())?;
         } else if name.eq_ignore_ascii_case("Ponder") {
             let lower = value.to_ascii_lowercase();
             self.ponder = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
-            self.engine
+            self.engine()
                 .set_option("Ponder", if self.ponder { "true" } else { "false" })?;
         } else if name.eq_ignore_ascii_case("UCI_AnalyseMode") {
             let lower = value.to_ascii_lowercase();
             self.analyse_mode = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
-            self.engine.set_option(
+            self.engine().set_option(
                 "UCI_AnalyseMode",
                 if self.analyse_mode { "true" } else { "false" },
             )?;
         } else if name.eq_ignore_ascii_case("UCI_Chess960") {
             let lower = value.to_ascii_lowercase();
             self.chess960 = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
-            self.engine
+            self.engine()
                 .set_option("UCI_Chess960", if self.chess960 { "true" } else { "false" })?;
         } else if name.eq_ignore_ascii_case("UCI_ShowWDL") {
             let lower = value.to_ascii_lowercase();
@@ -405,7 +551,7 @@ impl UciState {
         } else if name.eq_ignore_ascii_case("UCI_ShowRefutations") {
             let lower = value.to_ascii_lowercase();
             self.show_refutations = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
-            self.engine.set_option(
+            self.engine().set_option(
                 "UCI_ShowRefutations",
                 if self.show_refutations {
                     "true"
@@ -415,7 +561,8 @@ impl UciState {
             )?;
         } else if name.eq_ignore_ascii_case("UCI_Opponent") {
             self.uci_opponent = value.trim().to_owned();
-            self.engine.set_option("UCI_Opponent", &self.uci_opponent)?;
+            self.engine()
+                .set_option("UCI_Opponent", &self.uci_opponent)?;
         } else if name.eq_ignore_ascii_case("UCI_EngineAbout") {
             // Read-only informational option in practice; accept and ignore for compatibility.
         } else if name.eq_ignore_ascii_case("UCI_SetPositionValue") {
@@ -425,19 +572,19 @@ impl UciState {
             } else {
                 Some(normalized.clone())
             };
-            self.engine
+            self.engine()
                 .set_option("UCI_SetPositionValue", &normalized)?;
         } else if name.eq_ignore_ascii_case("TimeStrategy") {
             let normalized = value.trim().to_ascii_lowercase();
             self.time_strategy = normalized.clone();
-            self.engine.set_option("TimeStrategy", &normalized)?;
+            self.engine().set_option("TimeStrategy", &normalized)?;
         } else if name.eq_ignore_ascii_case("OwnBook") {
             let lower = value.to_ascii_lowercase();
             self.own_book = matches!(lower.as_str(), "true" | "1" | "yes" | "on");
-            self.engine
+            self.engine()
                 .set_option("OwnBook", if self.own_book { "true" } else { "false" })?;
         } else {
-            self.engine.set_option(&name, &value)?;
+            self.engine().set_option(&name, &value)?;
         }
 
         Ok(())
@@ -452,9 +599,9 @@ impl UciState {
     }
 
     fn rebuild_engine_for_current_strength(&mut self) -> Result<(), String> {
-        self.engine = build_engine(self.effective_skill_level());
+        *self.engine() = build_engine(self.effective_skill_level());
         self.apply_engine_options()?;
-        self.engine.new_game();
+        self.engine().new_game();
         Ok(())
     }
 
@@ -478,13 +625,16 @@ impl UciState {
                         return Err("missing FEN after 'position fen'".to_owned());
                     }
                     let fen = fen_parts.join(" ");
-                    GameState::from_fen(&fen)?
+                    GameState::from_fen_strict(&fen)?
                 }
                 other => return Err(format!("unsupported position token '{}'", other)),
             }
         } else {
             return Err("incomplete position command".to_owned());
         };
+        if self.chess960 {
+            base_state.chess960 = true;
+        }
 
         if tokens.peek().copied() == Some("moves") {
             let _ = tokens.next();
@@ -516,6 +666,17 @@ impl UciState {
 
     fn handle_go(&mut self, line: &str, out: &mut impl Write) -> Result<(), String> {
         let _ = self.stop_async_search_and_collect();
+        if line.split_whitespace().nth(1) == Some("perft") {
+            return self.handle_go_perft(line, out);
+        }
+        let status = self.game_state.status();
+        if status.is_game_over() {
+            writeln!(out, "info string game over: {status}").map_err(|e| e.to_string())?;
+        }
+        if matches!(status, GameStatus::Checkmate { .. } | GameStatus::Stalemate) {
+            writeln!(out, "bestmove 0000").map_err(|e| e.to_string())?;
+            return Ok(());
+        }
         let mut params = parse_go_params(line, &self.game_state)?;
         if params.mate.is_some() && params.depth.is_none() && params.nodes.is_none() {
             // `go mate N` should remain mate-driven and not be masked by a fixed-depth override.
@@ -533,7 +694,7 @@ impl UciState {
         }
         if params.mate.is_none() && (params.infinite || params.ponder) {
             let start_mode = if params.ponder { "ponder" } else { "infinite" };
-            self.start_async_search(params)?;
+            self.start_async_search(params, self.async_info_tx.clone(), false)?;
             writeln!(
                 out,
                 "info string async search started mode={start_mode}; waiting for stop/ponderhit"
@@ -541,12 +702,78 @@ impl UciState {
             .map_err(|e| e.to_string())?;
             return Ok(());
         }
-        let result = self.engine.choose_move(&self.game_state, &params)?;
-        let result = self.enforce_searchmoves(result, &params)?;
-        self.emit_engine_output(&result, out)
+        // Limited searches run on the worker too, so `stop` and `isready`
+        // are handled while they think; the worker prints its own `bestmove`.
+        let Some(tx) = self.async_info_tx.clone() else {
+            // Without an output channel (tests), wait here and write the
+            // buffered reports ahead of the result.
+            let (tx, rx) = mpsc::channel();
+            self.start_async_search(params.clone(), Some(tx), false)?;
+            let result = self
+                .finish_async_search()?
+                .ok_or("search stopped before completing a depth")?;
+            for line in rx.try_iter() {
+                writeln!(out, "{}", line).map_err(|e| e.to_string())?;
+            }
+            let result = self.enforce_searchmoves(result, &params)?;
+            return self.emit_engine_output(&result, out);
+        };
+        self.start_async_search(params, Some(tx), true)
+    }
+
+    /// Synchronous `choose_move` with live `info` streaming. Without an async
+    /// sender (tests), reports are buffered and written ahead of the result.
+    fn run_search(
+        &mut self,
+        params: &GoParams,
+        out: &mut impl Write,
+    ) -> Result<crate::engines::engine_trait::EngineOutput, String> {
+        let (tx, buffered) = match self.async_info_tx.clone() {
+            Some(tx) => (tx, None),
+            None => {
+                let (tx, rx) = mpsc::channel();
+                (tx, Some(rx))
+            }
+        };
+        let result = {
+            let mut engine = self.engine();
+            engine.set_search_observer(Some(Arc::new(UciInfoObserver {
+                root: self.game_state.clone(),
+                tx,
+            })));
+            let result = engine.choose_move(&self.game_state, params);
+            engine.set_search_observer(None);
+            result
+        };
+        if let Some(rx) = buffered {
+            for line in rx.try_iter() {
+                writeln!(out, "{}", line).map_err(|e| e.to_string())?;
+            }
+        }
+        result
+    }
+
+    /// Debug command `go perft N`: print per-root-move divide counts.
+    fn handle_go_perft(&mut self, line: &str, out: &mut impl Write) -> Result<(), String> {
+        let depth = line
+            .split_whitespace()
+            .nth(2)
+            .and_then(|x| x.parse::<u8>().ok())
+            .ok_or("go perft requires a depth")?;
+        let entries = perft_divide(&self.game_state, depth).map_err(|e| e.to_string())?;
+        write!(out, "{}", format_perft_divide(&entries)).map_err(|e| e.to_string())
     }
 
     fn handle_stop(&mut self, out: &mut impl Write) -> Result<(), String> {
+        if self
+            .async_search
+            .as_ref()
+            .is_some_and(|h| !h.claim_bestmove())
+        {
+            // After `ponderhit` the worker prints its own `bestmove`.
+            let _ = self.stop_async_search_and_collect();
+            return Ok(());
+        }
         let active_params = self.async_search.as_ref().map(|h| h.go_params.clone());
         let had_async = self.async_search.is_some();
         if let Some(result) = self.stop_async_search_and_collect()? {
@@ -569,7 +796,7 @@ impl UciState {
             {
                 fallback.movetime_ms = Some(100);
             }
-            let result = self.engine.choose_move(&self.game_state, &fallback)?;
+            let result = self.run_search(&fallback, out)?;
             let result = self.enforce_searchmoves(result, &fallback)?;
             return self.emit_engine_output(&result, out);
         }
@@ -577,76 +804,61 @@ impl UciState {
     }
 
     fn handle_ponderhit(&mut self, out: &mut impl Write) -> Result<(), String> {
-        if let Some(active) = self.async_search.as_ref() {
+        let worker_reports = self.async_info_tx.is_some();
+        if let Some(active) = self.async_search.as_mut() {
             if active.is_ponder {
-                let mut resumed = active.go_params.clone();
-                resumed.ponder = false;
-                resumed.infinite = false;
-                if resumed.movetime_ms.is_none()
-                    && resumed.wtime_ms.is_none()
-                    && resumed.btime_ms.is_none()
-                {
-                    resumed.movetime_ms = Some(250);
+                // Keep the running search (and everything it has learned) and
+                // put it on the move clock instead of restarting it.
+                active.is_ponder = false;
+                active.go_params.ponder = false;
+                let strategy = self
+                    .time_strategy
+                    .parse()
+                    .unwrap_or(TimeManagementStrategy::AdaptiveV13);
+                let limited = active.go_params.depth.is_some() || active.go_params.nodes.is_some();
+                let budget_ms = resolve_go_params(&self.game_state, &active.go_params, strategy)
+                    .movetime_ms
+                    .or((!limited).then_some(PONDERHIT_DEFAULT_MOVETIME_MS));
+                if let Some(budget_ms) = budget_ms {
+                    let stop = Arc::clone(&active.stop);
+                    thread::spawn(move || {
+                        thread::sleep(Duration::from_millis(budget_ms));
+                        stop.store(true, Ordering::Relaxed);
+                    });
                 }
-                let _ = self.stop_async_search_and_collect()?;
-                self.start_async_search(resumed)?;
+                active.worker_reports_armed = worker_reports;
+                active
+                    .worker_reports
+                    .store(worker_reports, Ordering::Release);
                 writeln!(
                     out,
-                    "info string ponderhit accepted; switched to normal async search"
+                    "info string ponderhit accepted; search continues on the move clock"
                 )
                 .map_err(|e| e.to_string())?;
+                // A ponder search that already hit its own limit has no one
+                // left to print its `bestmove`.
+                if worker_reports && active.search_finished() && active.claim_bestmove() {
+                    let params = active.go_params.clone();
+                    return match self.stop_async_search_and_collect()? {
+                        Some(result) => {
+                            let result = self.enforce_searchmoves(result, &params)?;
+                            self.emit_engine_output(&result, out)
+                        }
+                        None => writeln!(out, "bestmove 0000").map_err(|e| e.to_string()),
+                    };
+                }
                 return Ok(());
             }
         }
-        let active_params = self.async_search.as_ref().map(|h| h.go_params.clone());
-        let had_async = self.async_search.is_some();
-        if let Some(result) = self.stop_async_search_and_collect()? {
-            let params = active_params.clone().unwrap_or_default();
-            let result = self.enforce_searchmoves(result, &params)?;
-            return self.emit_engine_output(&result, out);
-        }
-        if had_async {
-            let mut fallback = active_params.unwrap_or_default();
-            fallback.ponder = false;
-            fallback.infinite = false;
-            fallback.mate = None;
-            fallback.nodes = None;
-            if fallback.depth.is_none() {
-                fallback.depth = self.fixed_depth_override.or(Some(2));
-            }
-            if fallback.movetime_ms.is_none()
-                && fallback.wtime_ms.is_none()
-                && fallback.btime_ms.is_none()
-            {
-                fallback.movetime_ms = Some(100);
-            }
-            let result = self.engine.choose_move(&self.game_state, &fallback)?;
-            let result = self.enforce_searchmoves(result, &fallback)?;
-            return self.emit_engine_output(&result, out);
-        }
-        Ok(())
+        self.handle_stop(out)
     }
 
     fn enforce_searchmoves(
         &self,
-        mut result: crate::engines::engine_trait::EngineOutput,
+        result: crate::engines::engine_trait::EngineOutput,
         params: &GoParams,
     ) -> Result<crate::engines::engine_trait::EngineOutput, String> {
-        let Some(allowed) = params.searchmoves.as_ref() else {
-            return Ok(result);
-        };
-        if result.best_move.is_some_and(|mv| allowed.contains(&mv)) {
-            return Ok(result);
-        }
-
-        let mut probe = self.game_state.clone();
-        let legal =
-            generate_legal_move_descriptions_in_place(&mut probe).map_err(|e| e.to_string())?;
-        result.best_move = legal.into_iter().find(|mv| allowed.contains(mv));
-        result
-            .info_lines
-            .push("info string uci searchmoves constraint applied".to_owned());
-        Ok(result)
+        apply_searchmoves(result, params, &self.game_state)
     }
 
     fn emit_engine_output(
@@ -654,61 +866,21 @@ impl UciState {
         result: &crate::engines::engine_trait::EngineOutput,
         out: &mut impl Write,
     ) -> Result<(), String> {
-        for info in &result.info_lines {
-            writeln!(out, "{}", info).map_err(|e| e.to_string())?;
-        }
-        if self.show_wdl {
-            if let Some(cp) = extract_last_cp_score(&result.info_lines) {
-                let (w, d, l) = cp_to_wdl(cp);
-                writeln!(out, "info wdl {} {} {}", w, d, l).map_err(|e| e.to_string())?;
-            }
-        }
-        if !has_mate_score_line(&result.info_lines) {
-            if let Some(cp) = extract_last_cp_score(&result.info_lines) {
-                if let Some(mate_moves) = cp_to_mate_moves(cp) {
-                    writeln!(out, "info score mate {}", mate_moves).map_err(|e| e.to_string())?;
-                }
-            }
-        }
-        if self.show_currline {
-            if let Some(currline) = build_currline_text(result, &self.game_state) {
-                writeln!(out, "info currline 1 {}", currline).map_err(|e| e.to_string())?;
-            }
-        }
-
-        if let Some(best_move) = result.best_move {
-            let lan = move_description_to_long_algebraic(best_move, &self.game_state)?;
-            if let Some(ponder_move) = result.ponder_move {
-                let next_state = apply_move(&self.game_state, best_move)?;
-                let mut probe = next_state.clone();
-                let legal_ponder = generate_legal_move_descriptions_in_place(&mut probe)
-                    .map_err(|e| e.to_string())?;
-                if legal_ponder.contains(&ponder_move) {
-                    if let Ok(ponder_lan) =
-                        move_description_to_long_algebraic(ponder_move, &next_state)
-                    {
-                        writeln!(out, "bestmove {} ponder {}", lan, ponder_lan)
-                            .map_err(|e| e.to_string())?;
-                    } else {
-                        writeln!(out, "bestmove {}", lan).map_err(|e| e.to_string())?;
-                    }
-                } else {
-                    writeln!(out, "bestmove {}", lan).map_err(|e| e.to_string())?;
-                }
-            } else {
-                writeln!(out, "bestmove {}", lan).map_err(|e| e.to_string())?;
-            }
-        } else {
-            writeln!(out, "bestmove 0000").map_err(|e| e.to_string())?;
-        }
-        Ok(())
+        write_engine_output(
+            result,
+            &self.game_state,
+            self.show_wdl,
+            self.show_currline,
+            out,
+        )
     }
 
     fn apply_engine_options(&mut self) -> Result<(), String> {
-        self.engine.set_option("Hash", &self.hash_mb.to_string())?;
-        self.engine
+        self.engine()
+            .set_option("Hash", &self.hash_mb.to_string())?;
+        self.engine()
             .set_option("Threads", &self.threads.to_string())?;
-        self.engine.set_option(
+        self.engine().set_option(
             "DeterministicSearch",
             if self.deterministic_search {
                 "true"
@@ -716,35 +888,51 @@ impl UciState {
                 "false"
             },
         )?;
-        self.engine.set_option(
+        self.engine().set_option(
             "RootParallelMinDepth",
             &self.root_parallel_min_depth.to_string(),
         )?;
-        self.engine.set_option(
+        self.engine().set_option(
             "RootParallelMinMoves",
             &self.root_parallel_min_moves.to_string(),
         )?;
-        self.engine
+        self.engine().set_option(
+            "SingularExtensions",
+            if self.singular_extensions {
+                "true"
+            } else {
+                "false"
+            },
+        )?;
+        self.engine()
+            .set_option("ProbCut", if self.probcut { "true" } else { "false" })?;
+        self.engine()
+            .set_option("ProbCutMargin", &self.probcut_margin.to_string())?;
+        self.engine()
+            .set_option("IIR", if self.iir { "true" } else { "false" })?;
+        self.engine()
+            .set_option("IIRMinDepth", &self.iir_min_depth.to_string())?;
+        self.engine()
             .set_option("Ponder", if self.ponder { "true" } else { "false" })?;
-        self.engine.set_option(
+        self.engine().set_option(
             "UCI_LimitStrength",
             if self.limit_strength { "true" } else { "false" },
         )?;
-        self.engine
+        self.engine()
             .set_option("UCI_Elo", &self.uci_elo.to_string())?;
-        self.engine
+        self.engine()
             .set_option("MultiPV", &self.multipv.to_string())?;
-        self.engine.set_option(
+        self.engine().set_option(
             "UCI_AnalyseMode",
             if self.analyse_mode { "true" } else { "false" },
         )?;
-        self.engine
+        self.engine()
             .set_option("UCI_Chess960", if self.chess960 { "true" } else { "false" })?;
-        self.engine.set_option(
+        self.engine().set_option(
             "UCI_ShowCurrLine",
             if self.show_currline { "true" } else { "false" },
         )?;
-        self.engine.set_option(
+        self.engine().set_option(
             "UCI_ShowRefutations",
             if self.show_refutations {
                 "true"
@@ -752,36 +940,28 @@ impl UciState {
                 "false"
             },
         )?;
-        self.engine.set_option("UCI_Opponent", &self.uci_opponent)?;
-        self.engine
+        self.engine()
+            .set_option("UCI_Opponent", &self.uci_opponent)?;
+        self.engine()
             .set_option("OwnBook", if self.own_book { "true" } else { "false" })?;
-        self.engine
+        self.engine()
             .set_option("TimeStrategy", &self.time_strategy)?;
         Ok(())
     }
 
-    fn start_async_search(&mut self, params: GoParams) -> Result<(), String> {
+    /// Search `params` on a worker thread. With `worker_reports` set the
+    /// worker prints `bestmove` to `info_tx` when it finishes on its own.
+    fn start_async_search(
+        &mut self,
+        params: GoParams,
+        info_tx: Option<mpsc::Sender<String>>,
+        worker_reports: bool,
+    ) -> Result<(), String> {
         let is_ponder = params.ponder;
         let game_state = self.game_state.clone();
-        let skill_level = self.effective_skill_level();
-        let hash_mb = self.hash_mb;
-        let threads = self.threads;
-        let deterministic_search = self.deterministic_search;
-        let root_parallel_min_depth = self.root_parallel_min_depth;
-        let root_parallel_min_moves = self.root_parallel_min_moves;
-        let own_book = self.own_book;
-        let ponder = self.ponder;
-        let limit_strength = self.limit_strength;
-        let uci_elo = self.uci_elo;
-        let multipv = self.multipv;
-        let analyse_mode = self.analyse_mode;
-        let chess960 = self.chess960;
+        let engine = Arc::clone(&self.engine);
+        let show_wdl = self.show_wdl;
         let show_currline = self.show_currline;
-        let show_refutations = self.show_refutations;
-        let uci_opponent = self.uci_opponent.clone();
-        let time_strategy = self.time_strategy.clone();
-        let info_tx = self.async_info_tx.clone();
-        let depth_override = self.fixed_depth_override;
         let params_for_worker = params.clone();
 
         let stop = Arc::new(AtomicBool::new(false));
@@ -790,120 +970,63 @@ impl UciState {
         let stop_flag = Arc::clone(&stop);
         let latest_ref = Arc::clone(&latest);
         let error_ref = Arc::clone(&error);
+        let worker_reports_armed = worker_reports && info_tx.is_some();
+        let worker_reports = Arc::new(AtomicBool::new(worker_reports_armed));
+        let worker_reports_ref = Arc::clone(&worker_reports);
 
         let handle = thread::spawn(move || {
-            let mut worker_engine = build_engine(skill_level);
-            worker_engine.set_stop_signal(Some(Arc::clone(&stop_flag)));
-            let _ = worker_engine.set_option("Hash", &hash_mb.to_string());
-            let _ = worker_engine.set_option("Threads", &threads.to_string());
-            let _ = worker_engine.set_option(
-                "DeterministicSearch",
-                if deterministic_search {
-                    "true"
-                } else {
-                    "false"
-                },
-            );
-            let _ = worker_engine
-                .set_option("RootParallelMinDepth", &root_parallel_min_depth.to_string());
-            let _ = worker_engine
-                .set_option("RootParallelMinMoves", &root_parallel_min_moves.to_string());
-            let _ = worker_engine.set_option("OwnBook", if own_book { "true" } else { "false" });
-            let _ = worker_engine.set_option("Ponder", if ponder { "true" } else { "false" });
-            let _ = worker_engine.set_option(
-                "UCI_LimitStrength",
-                if limit_strength { "true" } else { "false" },
-            );
-            let _ = worker_engine.set_option("UCI_Elo", &uci_elo.to_string());
-            let _ = worker_engine.set_option("MultiPV", &multipv.to_string());
-            let _ = worker_engine.set_option(
-                "UCI_AnalyseMode",
-                if analyse_mode { "true" } else { "false" },
-            );
-            let _ =
-                worker_engine.set_option("UCI_Chess960", if chess960 { "true" } else { "false" });
-            let _ = worker_engine.set_option(
-                "UCI_ShowCurrLine",
-                if show_currline { "true" } else { "false" },
-            );
-            let _ = worker_engine.set_option(
-                "UCI_ShowRefutations",
-                if show_refutations { "true" } else { "false" },
-            );
-            let _ = worker_engine.set_option("UCI_Opponent", &uci_opponent);
-            let _ = worker_engine.set_option("TimeStrategy", &time_strategy);
-            worker_engine.new_game();
-
-            let mut iter_depth = 1u8;
-            loop {
-                if stop_flag.load(Ordering::Relaxed) {
-                    break;
-                }
-                let mut iter_params = params_for_worker.clone();
-                iter_params.ponder = false;
-                iter_params.infinite = false;
-                if iter_params.movetime_ms.is_none() {
-                    iter_params.movetime_ms = Some(75);
+            // One search for the whole `go`: it deepens until `stop`, the
+            // ponderhit deadline, or its own depth/node/mate limit ends it.
+            let outcome = {
+                let mut engine = engine.lock().unwrap_or_else(PoisonError::into_inner);
+                engine.set_stop_signal(Some(stop_flag));
+                if let Some(tx) = &info_tx {
+                    engine.set_search_observer(Some(Arc::new(UciInfoObserver {
+                        root: game_state.clone(),
+                        tx: tx.clone(),
+                    })));
                 }
-
-                if iter_params.depth.is_none() {
-                    iter_params.depth = depth_override.or(Some(iter_depth));
-                    if depth_override.is_none() {
-                        iter_depth = iter_depth.saturating_add(1).min(32);
+                let outcome = engine.choose_move(&game_state, &params_for_worker);
+                engine.set_search_observer(None);
+                engine.set_stop_signal(None);
+                outcome
+            };
+            match outcome {
+                Ok(out) => {
+                    if let Ok(mut guard) = latest_ref.lock() {
+                        *guard = Some(out);
                     }
                 }
-
-                match worker_engine.choose_move(&game_state, &iter_params) {
-                    Ok(out) => {
-                        if let Some(tx) = &info_tx {
-                            for line in &out.info_lines {
-                                if show_refutations && line.starts_with("info refutation ") {
-                                    let ref_payload = line.trim_start_matches("info ").trim();
-                                    let _ = tx
-                                        .send(format!("info depth {} {}", iter_depth, ref_payload));
-                                } else {
-                                    let _ = tx.send(line.clone());
-                                }
-                            }
-                            if show_currline {
-                                if let Some(currline) = build_currline_text(&out, &game_state) {
-                                    let _ = tx.send(format!(
-                                        "info depth {} currline 1 {}",
-                                        iter_depth, currline
-                                    ));
-                                }
-                            }
-                            if let Some((nodes, time, nps)) =
-                                extract_last_search_stats(&out.info_lines)
-                            {
-                                let _ = tx.send(format!(
-                                    "info depth {} seldepth {} nodes {} time {} nps {}",
-                                    iter_depth, iter_depth, nodes, time, nps
-                                ));
-                            }
-                            if let Some(best) = out.best_move {
-                                if let Ok(curr_lan) =
-                                    move_description_to_long_algebraic(best, &game_state)
-                                {
-                                    let _ = tx.send(format!(
-                                        "info currmove {} currmovenumber {}",
-                                        curr_lan, iter_depth
-                                    ));
-                                }
-                            }
-                        }
-                        if let Ok(mut guard) = latest_ref.lock() {
-                            *guard = Some(out);
-                        }
-                    }
-                    Err(e) => {
-                        if let Ok(mut guard) = error_ref.lock() {
-                            *guard = Some(e);
-                        }
-                        break;
+                Err(e) => {
+                    if let Ok(mut guard) = error_ref.lock() {
+                        *guard = Some(e);
                     }
                 }
             }
+            if !worker_reports_ref.swap(false, Ordering::AcqRel) {
+                return;
+            }
+            let Some(tx) = &info_tx else {
+                return;
+            };
+            let outcome = match error_ref.lock().ok().and_then(|mut e| e.take()) {
+                Some(e) => Err(e),
+                None => latest_ref
+                    .lock()
+                    .ok()
+                    .and_then(|mut r| r.take())
+                    .ok_or_else(|| "search stopped before completing a depth".to_owned()),
+            };
+            let mut out = ChannelLineWriter::new(tx.clone());
+            let written = outcome
+                .and_then(|result| apply_searchmoves(result, &params_for_worker, &game_state))
+                .and_then(|result| {
+                    write_engine_output(&result, &game_state, show_wdl, show_currline, &mut out)
+                });
+            if let Err(e) = written {
+                let _ = tx.send(format!("info string async search error: {e}"));
+                let _ = tx.send("bestmove 0000".to_owned());
+            }
         });
 
         self.async_search = Some(AsyncSearchHandle {
@@ -912,6 +1035,8 @@ impl UciState {
             error,
             go_params: params,
             is_ponder,
+            worker_reports,
+            worker_reports_armed,
             handle,
         });
         Ok(())
@@ -919,11 +1044,20 @@ impl UciState {
 
     fn stop_async_search_and_collect(
         &mut self,
+    ) -> Result<Option<crate::engines::engine_trait::EngineOutput>, String> {
+        if let Some(async_handle) = &self.async_search {
+            async_handle.stop.store(true, Ordering::Relaxed);
+        }
+        self.finish_async_search()
+    }
+
+    /// Wait for the worker to end on its own limits and take its result.
+    fn finish_async_search(
+        &mut self,
     ) -> Result<Option<crate::engines::engine_trait::EngineOutput>, String> {
         let Some(async_handle) = self.async_search.take() else {
             return Ok(None);
         };
-        async_handle.stop.store(true, Ordering::Relaxed);
         async_handle
             .handle
             .join()
@@ -941,108 +1075,176 @@ impl UciState {
     }
 }
 
-fn extract_last_cp_score(info_lines: &[String]) -> Option<i32> {
-    for line in info_lines.iter().rev() {
-        let tokens = line.split_whitespace().collect::<Vec<_>>();
-        for w in tokens.windows(2) {
-            if w[0] == "cp" {
-                if let Ok(v) = w[1].parse::<i32>() {
-                    return Some(v);
+/// Falls back to the first legal `go searchmoves` entry when the engine
+/// returned a move outside the restriction.
+fn apply_searchmoves(
+    mut result: crate::engines::engine_trait::EngineOutput,
+    params: &GoParams,
+    root: &GameState,
+) -> Result<crate::engines::engine_trait::EngineOutput, String> {
+    let Some(allowed) = params.searchmoves.as_ref() else {
+        return Ok(result);
+    };
+    if result.best_move.is_some_and(|mv| allowed.contains(&mv)) {
+        return Ok(result);
+    }
+
+    let mut probe = root.clone();
+    let legal = generate_legal_move_descriptions_in_place(&mut probe).map_err(|e| e.to_string())?;
+    result.best_move = legal
+        .into_iter()
+        .map(Move::from_raw)
+        .find(|mv| allowed.contains(mv));
+    result
+        .info_lines
+        .push("info string uci searchmoves constraint applied".to_owned());
+    Ok(result)
+}
+
+/// Final `info` lines followed by `bestmove` (with `ponder` when legal).
+fn write_engine_output(
+    result: &crate::engines::engine_trait::EngineOutput,
+    root: &GameState,
+    show_wdl: bool,
+    show_currline: bool,
+    out: &mut impl Write,
+) -> Result<(), String> {
+    for line in format_engine_output_info(result, root, show_wdl, show_currline) {
+        writeln!(out, "{}", line).map_err(|e| e.to_string())?;
+    }
+
+    if let Some(best_move) = result.best_move {
+        let lan = move_description_to_long_algebraic(best_move.raw(), root)?;
+        if let Some(ponder_move) = result.ponder_move.map(Move::raw) {
+            let next_state = apply_move(root, best_move.raw())?;
+            let mut probe = next_state.clone();
+            let legal_ponder =
+                generate_legal_move_descriptions_in_place(&mut probe).map_err(|e| e.to_string())?;
+            if legal_ponder.contains(&ponder_move) {
+                if let Ok(ponder_lan) = move_description_to_long_algebraic(ponder_move, &next_state)
+                {
+                    writeln!(out, "bestmove {} ponder {}", lan, ponder_lan)
+                        .map_err(|e| e.to_string())?;
+                } else {
+                    writeln!(out, "bestmove {}", lan).map_err(|e| e.to_string())?;
                 }
+            } else {
+                writeln!(out, "bestmove {}", lan).map_err(|e| e.to_string())?;
             }
+        } else {
+            writeln!(out, "bestmove {}", lan).map_err(|e| e.to_string())?;
         }
+    } else {
+        writeln!(out, "bestmove 0000").map_err(|e| e.to_string())?;
     }
-    None
+    Ok(())
 }
 
-fn extract_last_search_stats(info_lines: &[String]) -> Option<(u64, u64, u64)> {
-    for line in info_lines.iter().rev() {
-        let tokens = line.split_whitespace().collect::<Vec<_>>();
-        let mut nodes: Option<u64> = None;
-        let mut time: Option<u64> = None;
-        let mut nps: Option<u64> = None;
-        let mut i = 0usize;
-        while i + 1 < tokens.len() {
-            match tokens[i] {
-                "nodes" => nodes = tokens[i + 1].parse::<u64>().ok(),
-                "time" => time = tokens[i + 1].parse::<u64>().ok(),
-                "nps" => nps = tokens[i + 1].parse::<u64>().ok(),
-                _ => {}
-            }
-            i += 1;
+/// UCI `info` lines for a finished `choose_move`, ahead of `bestmove`.
+fn format_engine_output_info(
+    result: &crate::engines::engine_trait::EngineOutput,
+    root: &GameState,
+    show_wdl: bool,
+    show_currline: bool,
+) -> Vec<String> {
+    let mut lines = result.info_lines.clone();
+    for line in &result.multipv {
+        let pv = pv_to_long_algebraic(&line.pv, root);
+        if !pv.is_empty() {
+            lines.push(format!(
+                "info depth {} multipv {} score {} pv {}",
+                line.depth,
+                line.multipv,
+                line.score,
+                pv.join(" ")
+            ));
         }
-        if let (Some(nodes), Some(time), Some(nps)) = (nodes, time, nps) {
-            return Some((nodes, time, nps));
+    }
+    for &(alternative, best) in &result.refutations {
+        if let (Ok(alternative), Ok(best)) = (
+            move_description_to_long_algebraic(alternative, root),
+            move_description_to_long_algebraic(best, root),
+        ) {
+            lines.push(format!("info refutation {} {}", alternative, best));
         }
     }
-    None
-}
-
-fn extract_last_pv_moves(info_lines: &[String]) -> Option<String> {
-    for line in info_lines.iter().rev() {
-        if let Some(rest) = line.strip_prefix("info pv ") {
-            let trimmed = rest.trim();
-            if !trimmed.is_empty() {
-                return Some(trimmed.to_owned());
-            }
+    let pv = pv_to_long_algebraic(&result.pv, root);
+    if let Some(score) = result.score {
+        let mut line = format!(
+            "info depth {} seldepth {} score {} nodes {} nps {} hashfull {} time {}",
+            result.depth,
+            result.seldepth,
+            score,
+            result.nodes,
+            result.nps,
+            result.hashfull,
+            result.time_ms
+        );
+        if !pv.is_empty() {
+            line.push_str(&format!(" pv {}", pv.join(" ")));
         }
+        lines.push(line);
     }
-    None
-}
-
-fn build_currline_text(
-    result: &crate::engines::engine_trait::EngineOutput,
-    game_state: &GameState,
-) -> Option<String> {
-    if let Some(pv) = extract_last_pv_moves(&result.info_lines) {
-        return Some(pv);
-    }
-    if let Some(best) = result.best_move {
-        if let Ok(best_lan) = move_description_to_long_algebraic(best, game_state) {
-            return Some(best_lan);
+    if show_wdl {
+        if let Some(wdl) = result.wdl {
+            lines.push(format!("info wdl {} {} {}", wdl.win, wdl.draw, wdl.loss));
         }
     }
-    None
-}
-
-fn cp_to_wdl(cp: i32) -> (u16, u16, u16) {
-    let cp_f = cp as f64;
-    let win_sigmoid = 1.0 / (1.0 + (-cp_f / 180.0).exp());
-    let draw = (0.30 - (cp_f.abs() / 1200.0)).clamp(0.05, 0.30);
-    let decisive = 1.0 - draw;
-    let win = decisive * win_sigmoid;
-    let loss = decisive * (1.0 - win_sigmoid);
-
-    let mut w = (win * 1000.0).round() as i32;
-    let mut d = (draw * 1000.0).round() as i32;
-    let mut l = (loss * 1000.0).round() as i32;
-    let delta = 1000 - (w + d + l);
-    if delta > 0 {
-        d += delta;
-    } else if delta < 0 {
-        let remove = (-delta).min(d);
-        d -= remove;
-    }
-    w = w.clamp(0, 1000);
-    d = d.clamp(0, 1000);
-    l = (1000 - w - d).clamp(0, 1000);
-    (w as u16, d as u16, l as u16)
+    if show_currline {
+        let currline = if pv.is_empty() {
+            result
+                .best_move
+                .and_then(|mv| move_description_to_long_algebraic(mv.raw(), root).ok())
+        } else {
+            Some(pv.join(" "))
+        };
+        if let Some(currline) = currline {
+            lines.push(format!("info currline 1 {}", currline));
+        }
+    }
+    lines
 }
 
-fn has_mate_score_line(info_lines: &[String]) -> bool {
-    info_lines
-        .iter()
-        .any(|line| line.contains(" score mate ") || line.starts_with("info score mate "))
+/// Long-algebraic text of `pv` from `root`, cut at the first illegal move.
+fn pv_to_long_algebraic(pv: &[u64], root: &GameState) -> Vec<String> {
+    let mut state = root.clone();
+    let mut out = Vec::with_capacity(pv.len());
+    for &mv in pv {
+        let Ok(lan) = move_description_to_long_algebraic(mv, &state) else {
+            break;
+        };
+        let Ok(next) = apply_move(&state, mv) else {
+            break;
+        };
+        out.push(lan);
+        state = next;
+    }
+    out
 }
 
-fn cp_to_mate_moves(cp: i32) -> Option<i32> {
-    let abs_cp = cp.abs();
-    if abs_cp < (MATE_SCORE - 256) {
-        return None;
-    }
-    let mate_plies = (MATE_SCORE - abs_cp).max(1);
-    let mate_moves = (mate_plies + 1) / 2;
-    Some(if cp >= 0 { mate_moves } else { -mate_moves })
+/// One `info` line per report: `currmove` for progress updates, otherwise
+/// score and PV.
+fn format_search_info_line(info: &SearchInfo, root: &GameState) -> String {
+    let mut line = format!("info depth {} seldepth {}", info.depth, info.seldepth);
+    if let Some(mv) = info.currmove {
+        if let Ok(lan) = move_description_to_long_algebraic(mv, root) {
+            line.push_str(&format!(" currmove {}", lan));
+            if let Some(number) = info.currmovenumber {
+                line.push_str(&format!(" currmovenumber {}", number));
+            }
+        }
+    } else {
+        line.push_str(&format!(" score {}", Score::from_search(info.score)));
+    }
+    line.push_str(&format!(
+        " nodes {} nps {} hashfull {} time {}",
+        info.nodes, info.nps, info.hashfull, info.elapsed_ms
+    ));
+    let pv_lan = pv_to_long_algebraic(&info.pv, root);
+    if !pv_lan.is_empty() {
+        line.push_str(&format!(" pv {}", pv_lan.join(" ")));
+    }
+    line
 }
 
 fn parse_go_params(line: &str, game_state: &GameState) -> Result<GoParams, String> {
@@ -1095,10 +1297,10 @@ fn parse_go_params(line: &str, game_state: &GameState) -> Result<GoParams, Strin
             }
             "searchmoves" => {
                 i += 1;
-                let mut moves = Vec::<u64>::new();
+                let mut moves = Vec::<Move>::new();
                 while i < tokens.len() && !is_go_keyword(tokens[i]) {
                     let mv = long_algebraic_to_move_description(tokens[i], game_state)?;
-                    moves.push(mv);
+                    moves.push(Move::from_raw(mv));
                     i += 1;
                 }
                 i = i.saturating_sub(1);
@@ -1162,7 +1364,10 @@ fn build_engine(skill_level: u8) -> Box<dyn Engine> {
 
 #[cfg(test)]
 mod tests {
-    use super::{elo_to_skill_level, UciState};
+    use super::{
+        elo_to_skill_level, format_search_info_line, long_algebraic_to_move_description, GameState,
+        Score, SearchInfo, UciState,
+    };
 
     fn extract_bestmove_lan(output: &str) -> Option<String> {
         for line in output.lines() {
@@ -1191,10 +1396,67 @@ mod tests {
     fn position_fen_without_moves_updates_state() {
         let mut state = UciState::new();
         state
-            .handle_position("position fen 8/8/8/8/8/8/4P3/4K3 w - - 0 1")
+            .handle_position("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1")
             .expect("position fen should parse");
 
-        assert_eq!(state.game_state.get_fen(), "8/8/8/8/8/8/4P3/4K3 w - - 0 1");
+        assert_eq!(
+            state.game_state.get_fen(),
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"
+        );
+    }
+
+    #[test]
+    fn go_perft_prints_divide_and_total() {
+        let mut state = UciState::new();
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("position startpos moves e2e4", &mut out)
+            .expect("position should be handled");
+        state
+            .handle_command("go perft 2", &mut out)
+            .expect("go perft should be handled");
+
+        let text = String::from_utf8(out).expect("utf8 output");
+        assert_eq!(text.lines().filter(|l| l.contains(": ")).count(), 21);
+        assert!(text.contains("d7d5: 31\n"), "{text}");
+        assert!(text.ends_with("\nNodes searched: 600\n"), "{text}");
+        assert!(!text.contains("bestmove"));
+    }
+
+    #[test]
+    fn position_fen_rejects_illegal_position_with_info_string() {
+        let mut state = UciState::new();
+        let before = state.game_state.get_fen();
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("position fen 8/8/8/8/8/8/4P3/4K3 w - - 0 1", &mut out)
+            .expect("command should be handled");
+
+        let text = String::from_utf8(out).expect("utf8 output");
+        assert!(
+            text.starts_with("info string position error: Illegal position in FEN"),
+            "{text}"
+        );
+        assert!(text.contains("Dark has 0 kings"), "{text}");
+        assert_eq!(state.game_state.get_fen(), before);
+    }
+
+    #[test]
+    fn go_in_finished_game_reports_status_and_null_bestmove() {
+        let mut state = UciState::new();
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("position startpos moves f2f3 e7e5 g2g4 d8h4", &mut out)
+            .expect("command should be handled");
+        state
+            .handle_command("go depth 2", &mut out)
+            .expect("command should be handled");
+
+        let text = String::from_utf8(out).expect("utf8 output");
+        assert_eq!(
+            text,
+            "info string game over: checkmate, Dark wins\nbestmove 0000\n"
+        );
     }
 
     #[test]
@@ -1337,6 +1599,21 @@ mod tests {
         assert!(state.chess960);
     }
 
+    #[test]
+    fn chess960_position_accepts_king_takes_rook_castling() {
+        let mut state = UciState::new();
+        state
+            .handle_setoption("setoption name UCI_Chess960 value true")
+            .expect("chess960 should parse");
+        state
+            .handle_position("position fen 4k3/8/8/8/8/8/8/R4K1R w HA - 0 1 moves f1h1")
+            .expect("h-side castle should apply");
+        assert_eq!(
+            state.game_state.get_fen(),
+            "4k3/8/8/8/8/8/8/R4RK1 b - - 1 1"
+        );
+    }
+
     #[test]
     fn setoption_show_currline_and_refutations_parse() {
         let mut state = UciState::new();
@@ -1352,6 +1629,58 @@ mod tests {
         assert!(state.show_refutations);
     }
 
+    #[test]
+    fn setoption_singular_extensions_parse_and_search() {
+        let mut state = UciState::new();
+        assert!(!state.singular_extensions);
+        state
+            .handle_setoption("setoption name SingularExtensions value true")
+            .expect("singular extensions should parse");
+        assert!(state.singular_extensions);
+
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("setoption name OwnBook value false", &mut out)
+            .expect("setoption should succeed");
+        state
+            .handle_command("go depth 5", &mut out)
+            .expect("go should succeed");
+        let text = String::from_utf8(out).expect("utf8");
+        assert!(extract_bestmove_lan(&text).is_some());
+    }
+
+    #[test]
+    fn setoption_probcut_and_iir_parse_and_search() {
+        let mut state = UciState::new();
+        assert!(!state.probcut && !state.iir);
+        for command in [
+            "setoption name ProbCut value true",
+            "setoption name ProbCutMargin value 150",
+            "setoption name IIR value true",
+            "setoption name IIRMinDepth value 1",
+        ] {
+            state
+                .handle_setoption(command)
+                .expect("selectivity option should parse");
+        }
+        assert!(state.probcut && state.iir);
+        assert_eq!(state.probcut_margin, 150);
+        assert_eq!(state.iir_min_depth, 2);
+        assert!(state
+            .handle_setoption("setoption name ProbCutMargin value wide")
+            .is_err());
+
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("setoption name OwnBook value false", &mut out)
+            .expect("setoption should succeed");
+        state
+            .handle_command("go depth 6", &mut out)
+            .expect("go should succeed");
+        let text = String::from_utf8(out).expect("utf8");
+        assert!(extract_bestmove_lan(&text).is_some());
+    }
+
     #[test]
     fn setoption_uci_opponent_parse() {
         let mut state = UciState::new();
@@ -1611,6 +1940,93 @@ mod tests {
             .unwrap_or(true));
     }
 
+    #[test]
+    fn ponderhit_search_reports_bestmove_once_on_move_clock() {
+        let (tx, rx) = std::sync::mpsc::channel::<String>();
+        let mut state = UciState::new();
+        state
+            .handle_setoption("setoption name Skill Level value 3")
+            .expect("skill should parse");
+        state
+            .handle_setoption("setoption name OwnBook value false")
+            .expect("ownbook should parse");
+        state.set_async_info_sender(Some(tx));
+
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("go ponder movetime 100", &mut out)
+            .expect("go ponder should succeed");
+        state
+            .handle_command("ponderhit", &mut out)
+            .expect("ponderhit should succeed");
+        let text = String::from_utf8(out).expect("valid utf8");
+        assert!(text.contains("ponderhit accepted"));
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        let mut bestmoves = 0;
+        while std::time::Instant::now() < deadline && bestmoves == 0 {
+            if let Ok(line) = rx.recv_timeout(std::time::Duration::from_millis(20)) {
+                bestmoves += usize::from(line.starts_with("bestmove"));
+            }
+        }
+        assert_eq!(
+            bestmoves, 1,
+            "worker should report bestmove after ponderhit"
+        );
+
+        let mut stop_out = Vec::<u8>::new();
+        state
+            .handle_command("stop", &mut stop_out)
+            .expect("stop should succeed");
+        let stop_text = String::from_utf8(stop_out).expect("valid utf8");
+        assert!(!stop_text.contains("bestmove"));
+        assert!(rx.try_iter().all(|line| !line.starts_with("bestmove")));
+    }
+
+    #[test]
+    fn async_searches_reuse_session_engine_until_ucinewgame() {
+        use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
+        use crate::game_state::game_state::GameState;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        #[derive(Default)]
+        struct Counts {
+            searches: AtomicUsize,
+            resets: AtomicUsize,
+        }
+        struct CountingEngine(Arc<Counts>);
+        impl Engine for CountingEngine {
+            fn new_game(&mut self) {
+                self.0.resets.fetch_add(1, Ordering::Relaxed);
+            }
+            fn choose_move(&mut self, _: &GameState, _: &GoParams) -> Result<EngineOutput, String> {
+                self.0.searches.fetch_add(1, Ordering::Relaxed);
+                Ok(EngineOutput::default())
+            }
+        }
+
+        let counts = Arc::new(Counts::default());
+        let mut state = UciState::new();
+        *state.engine() = Box::new(CountingEngine(Arc::clone(&counts)));
+        let mut out = Vec::<u8>::new();
+        for _ in 0..2 {
+            state
+                .handle_command("go infinite", &mut out)
+                .expect("go infinite should succeed");
+            state
+                .handle_command("stop", &mut out)
+                .expect("stop should succeed");
+        }
+        assert_eq!(counts.searches.load(Ordering::Relaxed), 2);
+        assert_eq!(counts.resets.load(Ordering::Relaxed), 0);
+
+        state
+            .handle_command("ucinewgame", &mut out)
+            .expect("ucinewgame should succeed");
+        assert_eq!(counts.resets.load(Ordering::Relaxed), 1);
+    }
+
     #[test]
     fn ponder_stop_emits_bestmove() {
         let mut state = UciState::new();
@@ -1716,6 +2132,74 @@ mod tests {
             .expect("stop should succeed");
     }
 
+    #[test]
+    fn clock_search_answers_isready_and_stops_promptly() {
+        let (tx, rx) = std::sync::mpsc::channel::<String>();
+        let mut state = UciState::new();
+        state
+            .handle_setoption("setoption name OwnBook value false")
+            .expect("ownbook should parse");
+        state.set_async_info_sender(Some(tx));
+
+        let started = std::time::Instant::now();
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("go movetime 8000", &mut out)
+            .expect("go should start");
+        state
+            .handle_command("isready", &mut out)
+            .expect("isready should respond while searching");
+        assert!(String::from_utf8(out).expect("utf8").contains("readyok"));
+        assert!(state.async_search.is_some());
+
+        let mut stop_out = Vec::<u8>::new();
+        state
+            .handle_command("stop", &mut stop_out)
+            .expect("stop should succeed");
+        assert!(started.elapsed() < std::time::Duration::from_secs(4));
+        let stop_text = String::from_utf8(stop_out).expect("utf8");
+        let bestmoves = stop_text
+            .lines()
+            .chain(rx.try_iter().collect::<Vec<_>>().iter().map(String::as_str))
+            .filter(|line| line.starts_with("bestmove"))
+            .count();
+        assert_eq!(bestmoves, 1);
+    }
+
+    #[test]
+    fn depth_search_worker_reports_bestmove_once() {
+        let (tx, rx) = std::sync::mpsc::channel::<String>();
+        let mut state = UciState::new();
+        state
+            .handle_setoption("setoption name OwnBook value false")
+            .expect("ownbook should parse");
+        state.set_async_info_sender(Some(tx));
+
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("go depth 2", &mut out)
+            .expect("go should start");
+        assert!(!String::from_utf8(out).expect("utf8").contains("bestmove"));
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        let mut bestmoves = 0;
+        while std::time::Instant::now() < deadline && bestmoves == 0 {
+            if let Ok(line) = rx.recv_timeout(std::time::Duration::from_millis(20)) {
+                bestmoves += usize::from(line.starts_with("bestmove"));
+            }
+        }
+        assert_eq!(bestmoves, 1, "worker should report bestmove");
+
+        let mut stop_out = Vec::<u8>::new();
+        state
+            .handle_command("stop", &mut stop_out)
+            .expect("stop should succeed");
+        assert!(!String::from_utf8(stop_out)
+            .expect("utf8")
+            .contains("bestmove"));
+        assert!(rx.try_iter().all(|line| !line.starts_with("bestmove")));
+    }
+
     #[test]
     fn show_wdl_outputs_wdl_info_line() {
         let mut state = UciState::new();
@@ -1825,6 +2309,35 @@ mod tests {
         assert!(text.contains("bestmove "));
     }
 
+    #[test]
+    fn go_streams_search_info_before_bestmove() {
+        let mut state = UciState::new();
+        let mut out = Vec::<u8>::new();
+        state
+            .handle_command("setoption name OwnBook value false", &mut out)
+            .expect("setoption should parse");
+        state
+            .handle_command("setoption name Skill Level value 18", &mut out)
+            .expect("setoption should parse");
+        out.clear();
+        state
+            .handle_command("go depth 3", &mut out)
+            .expect("go should succeed");
+        let text = String::from_utf8(out).expect("utf8");
+        let iteration_lines: Vec<&str> = text
+            .lines()
+            .filter(|line| line.starts_with("info depth ") && line.contains(" seldepth "))
+            .collect();
+        assert!(iteration_lines
+            .iter()
+            .any(|line| line.starts_with("info depth 3 ") && line.contains(" pv ")));
+        assert!(iteration_lines
+            .iter()
+            .all(|line| line.contains(" hashfull ")));
+        let last_info = text.rfind("info depth 3 seldepth").expect("depth 3 line");
+        assert!(last_info < text.find("bestmove ").expect("bestmove"));
+    }
+
     #[test]
     fn level_19_uses_v17_scaffold_profile() {
         let mut state = UciState::new();
@@ -1846,26 +2359,44 @@ mod tests {
     }
 
     #[test]
-    fn cp_to_mate_moves_detects_mate_band() {
-        assert_eq!(super::cp_to_mate_moves(29_999), Some(1));
-        assert_eq!(super::cp_to_mate_moves(-29_998), Some(-1));
-        assert_eq!(super::cp_to_mate_moves(500), None);
-    }
-
-    #[test]
-    fn emit_engine_output_adds_mate_score_line_from_cp() {
+    fn emit_engine_output_renders_mate_score() {
         let mut state = UciState::new();
         let result = crate::engines::engine_trait::EngineOutput {
-            best_move: None,
-            ponder_move: None,
-            info_lines: vec!["info depth 5 score cp 29999 nodes 123 time 1 nps 1000".to_owned()],
+            score: Some(Score::from_search(29_999)),
+            depth: 5,
+            nodes: 123,
+            time_ms: 1,
+            nps: 1000,
+            ..Default::default()
         };
         let mut out = Vec::<u8>::new();
         state
             .emit_engine_output(&result, &mut out)
             .expect("emit should succeed");
         let text = String::from_utf8(out).expect("utf8");
-        assert!(text.contains("info score mate 1"));
+        assert!(text.contains("info depth 5 seldepth 0 score mate 1 nodes 123"));
+        assert!(text.contains(" hashfull 0 time 1"));
+    }
+
+    #[test]
+    fn search_progress_line_reports_currmove_and_number() {
+        let root = GameState::new_game();
+        let mv = long_algebraic_to_move_description("g1f3", &root).expect("legal move");
+        let info = SearchInfo {
+            depth: 6,
+            seldepth: 11,
+            nodes: 4096,
+            hashfull: 37,
+            currmove: Some(mv),
+            currmovenumber: Some(3),
+            ..SearchInfo::default()
+        };
+        let line = format_search_info_line(&info, &root);
+        assert_eq!(
+            line,
+            "info depth 6 seldepth 11 currmove g1f3 currmovenumber 3 nodes 4096 nps 0 \
+             hashfull 37 time 0"
+        );
     }
 
     #[test]
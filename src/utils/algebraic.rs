@@ -3,11 +3,11 @@
 //! Converts between human-readable coordinates (e.g., `e4`) and internal
 //! square/bitboard representations reused by FEN/PGN/UCI components.
 
-use crate::game_state::chess_types::Square;
+use crate::game_state::chess_types::SquareIndex;
 
 /// Convert long algebraic notation (for example: "e4") to a square index.
 #[inline]
-pub fn algebraic_to_square(square: &str) -> Result<Square, String> {
+pub fn algebraic_to_square(square: &str) -> Result<SquareIndex, String> {
     let bytes = square.as_bytes();
     if bytes.len() != 2 {
         return Err(format!("Invalid algebraic square: {square}"));
@@ -37,9 +37,9 @@ pub fn algebraic_to_bitboard(square: &str) -> Result<u64, String> {
 
 /// Convert a square index (`0..=63`) to long algebraic notation (for example: "e4").
 #[inline]
-pub fn square_to_algebraic(square: Square) -> Result<String, String> {
+pub fn square_to_algebraic(square: SquareIndex) -> Result<String, String> {
     if square > 63 {
-        return Err(format!("Square index out of bounds: {square}"));
+        return Err(format!("SquareIndex index out of bounds: {square}"));
     }
 
     let file = square % 8;
@@ -63,7 +63,7 @@ pub fn bitboard_to_algebraic(bitboard: u64) -> Result<String, String> {
         ));
     }
 
-    let square = bitboard.trailing_zeros() as Square;
+    let square = bitboard.trailing_zeros() as SquareIndex;
     square_to_algebraic(square)
 }
 
@@ -4,14 +4,16 @@
 //! UCI I/O, with an optional seeded random opening prefix.
 
 use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::sync::Arc;
 use std::time::Instant;
 
 use crate::engines::engine_trait::{Engine, GoParams};
-use crate::game_state::chess_types::Color;
+use crate::game_state::chess_types::{Color, Move};
 use crate::game_state::game_state::GameState;
+use crate::game_state::game_status::GameStatus;
 use crate::move_generation::legal_move_apply::apply_move;
-use crate::move_generation::legal_move_checks::is_king_in_check;
 use crate::move_generation::legal_move_generator::generate_legal_move_descriptions_in_place;
+use crate::search::search_observer::LastIterationRecorder;
 use crate::tables::opening_book::OpeningBook;
 use crate::utils::long_algebraic::move_description_to_long_algebraic;
 
@@ -22,6 +24,8 @@ pub enum MatchOutcome {
     DrawStalemate,
     DrawRepetition,
     DrawFiftyMoveRule,
+    /// Insufficient material or a dead position.
+    DrawInsufficientMaterial,
     DrawMaxPlies,
 }
 
@@ -33,10 +37,15 @@ pub enum PlayerId {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SeriesOutcome {
-    PlayerWinCheckmate { player: PlayerId, color: Color },
+    PlayerWinCheckmate {
+        player: PlayerId,
+        color: Color,
+    },
     DrawStalemate,
     DrawRepetition,
     DrawFiftyMoveRule,
+    /// Insufficient material or a dead position.
+    DrawInsufficientMaterial,
     DrawMaxPlies,
 }
 
@@ -59,6 +68,42 @@ impl Default for MatchConfig {
     }
 }
 
+/// Per-player totals of the last completed iteration of each searched move,
+/// collected through the engine's search observer. Book and non-searching
+/// moves are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchTotals {
+    pub searched_moves: u32,
+    pub depth_sum: u64,
+    pub nodes: u64,
+}
+
+impl SearchTotals {
+    pub fn avg_depth(&self) -> f64 {
+        if self.searched_moves == 0 {
+            0.0
+        } else {
+            self.depth_sum as f64 / f64::from(self.searched_moves)
+        }
+    }
+
+    fn add(&mut self, other: SearchTotals) {
+        self.searched_moves = self.searched_moves.saturating_add(other.searched_moves);
+        self.depth_sum = self.depth_sum.saturating_add(other.depth_sum);
+        self.nodes = self.nodes.saturating_add(other.nodes);
+    }
+
+    fn record(&mut self, recorder: &LastIterationRecorder) {
+        if let Some(info) = recorder.take() {
+            self.add(SearchTotals {
+                searched_moves: 1,
+                depth_sum: u64::from(info.depth),
+                nodes: info.nodes,
+            });
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct MatchResult {
     pub outcome: MatchOutcome,
@@ -69,6 +114,8 @@ pub struct MatchResult {
     pub black_move_count: u32,
     pub white_total_time_ns: u128,
     pub black_total_time_ns: u128,
+    pub white_search: SearchTotals,
+    pub black_search: SearchTotals,
 }
 
 #[derive(Debug, Clone)]
@@ -101,6 +148,8 @@ pub struct MatchSeriesStats {
     pub player2_moves: u32,
     pub player1_total_time_ns: u128,
     pub player2_total_time_ns: u128,
+    pub player1_search: SearchTotals,
+    pub player2_search: SearchTotals,
     pub player1_avg_move_time_ms: f64,
     pub player2_avg_move_time_ms: f64,
     pub overall_avg_move_time_ms: f64,
@@ -109,14 +158,16 @@ pub struct MatchSeriesStats {
 impl MatchSeriesStats {
     pub fn report(&self) -> String {
         format!(
-            "games={} player1_wins={} player2_wins={} draws={} p1_avg_ms={:.3} p2_avg_ms={:.3} overall_avg_ms={:.3}",
+            "games={} player1_wins={} player2_wins={} draws={} p1_avg_ms={:.3} p2_avg_ms={:.3} overall_avg_ms={:.3} p1_avg_depth={:.2} p2_avg_depth={:.2}",
             self.games,
             self.player1_wins,
             self.player2_wins,
             self.draws,
             self.player1_avg_move_time_ms,
             self.player2_avg_move_time_ms,
-            self.overall_avg_move_time_ms
+            self.overall_avg_move_time_ms,
+            self.player1_search.avg_depth(),
+            self.player2_search.avg_depth()
         )
     }
 }
@@ -171,6 +222,10 @@ fn play_engine_match_from_state_internal(
 ) -> Result<MatchResult, String> {
     engine_white.new_game();
     engine_black.new_game();
+    let white_recorder = Arc::new(LastIterationRecorder::default());
+    let black_recorder = Arc::new(LastIterationRecorder::default());
+    engine_white.set_search_observer(Some(white_recorder.clone()));
+    engine_black.set_search_observer(Some(black_recorder.clone()));
 
     let opening_moves_lan = if apply_random_opening {
         let (state_after_opening, opening_moves_lan) = apply_seeded_random_opening(
@@ -190,24 +245,32 @@ fn play_engine_match_from_state_internal(
     let mut black_move_count = 0u32;
     let mut white_total_time_ns = 0u128;
     let mut black_total_time_ns = 0u128;
+    let mut white_search = SearchTotals::default();
+    let mut black_search = SearchTotals::default();
 
     for _ in 0..config.max_plies {
-        if state.halfmove_clock >= 100 {
-            return Ok(MatchResult {
-                outcome: MatchOutcome::DrawFiftyMoveRule,
-                final_state: state,
-                opening_moves_lan,
-                played_moves_lan,
-                white_move_count,
-                black_move_count,
-                white_total_time_ns,
-                black_total_time_ns,
-            });
-        }
-
-        if repetition_count(&state) >= 3 {
+        let outcome = match state.status() {
+            GameStatus::Ongoing => None,
+            GameStatus::Checkmate {
+                winner: Color::Light,
+            } => Some(MatchOutcome::WhiteWinCheckmate),
+            GameStatus::Checkmate {
+                winner: Color::Dark,
+            } => Some(MatchOutcome::BlackWinCheckmate),
+            GameStatus::Stalemate => Some(MatchOutcome::DrawStalemate),
+            GameStatus::ThreefoldRepetition | GameStatus::FivefoldRepetition => {
+                Some(MatchOutcome::DrawRepetition)
+            }
+            GameStatus::FiftyMoveRule | GameStatus::SeventyFiveMoveRule => {
+                Some(MatchOutcome::DrawFiftyMoveRule)
+            }
+            GameStatus::InsufficientMaterial | GameStatus::DeadPosition => {
+                Some(MatchOutcome::DrawInsufficientMaterial)
+            }
+        };
+        if let Some(outcome) = outcome {
             return Ok(MatchResult {
-                outcome: MatchOutcome::DrawRepetition,
+                outcome,
                 final_state: state,
                 opening_moves_lan,
                 played_moves_lan,
@@ -215,32 +278,14 @@ fn play_engine_match_from_state_internal(
                 black_move_count,
                 white_total_time_ns,
                 black_total_time_ns,
+                white_search,
+                black_search,
             });
         }
 
         let mut probe = state.clone();
         let legal_moves = generate_legal_move_descriptions_in_place(&mut probe)
             .map_err(|e| format!("failed to generate legal moves: {e}"))?;
-        if legal_moves.is_empty() {
-            let outcome = if is_king_in_check(&state, state.side_to_move) {
-                match state.side_to_move {
-                    Color::Light => MatchOutcome::BlackWinCheckmate,
-                    Color::Dark => MatchOutcome::WhiteWinCheckmate,
-                }
-            } else {
-                MatchOutcome::DrawStalemate
-            };
-            return Ok(MatchResult {
-                outcome,
-                final_state: state,
-                opening_moves_lan,
-                played_moves_lan,
-                white_move_count,
-                black_move_count,
-                white_total_time_ns,
-                black_total_time_ns,
-            });
-        }
 
         let mover = state.side_to_move;
         let started = Instant::now();
@@ -255,14 +300,16 @@ fn play_engine_match_from_state_internal(
             Color::Light => {
                 white_move_count = white_move_count.saturating_add(1);
                 white_total_time_ns = white_total_time_ns.saturating_add(elapsed_ns);
+                white_search.record(&white_recorder);
             }
             Color::Dark => {
                 black_move_count = black_move_count.saturating_add(1);
                 black_total_time_ns = black_total_time_ns.saturating_add(elapsed_ns);
+                black_search.record(&black_recorder);
             }
         }
 
-        let chosen = out.best_move.unwrap_or(legal_moves[0]);
+        let chosen = out.best_move.map_or(legal_moves[0], Move::raw);
         if !legal_moves.contains(&chosen) {
             return Err("engine returned illegal move".to_owned());
         }
@@ -281,6 +328,8 @@ fn play_engine_match_from_state_internal(
         black_move_count,
         white_total_time_ns,
         black_total_time_ns,
+        white_search,
+        black_search,
     })
 }
 
@@ -346,6 +395,8 @@ where
             stats.player2_total_time_ns = stats
                 .player2_total_time_ns
                 .saturating_add(result.black_total_time_ns);
+            stats.player1_search.add(result.white_search);
+            stats.player2_search.add(result.black_search);
         } else {
             stats.player1_moves = stats.player1_moves.saturating_add(result.black_move_count);
             stats.player2_moves = stats.player2_moves.saturating_add(result.white_move_count);
@@ -355,6 +406,8 @@ where
             stats.player2_total_time_ns = stats
                 .player2_total_time_ns
                 .saturating_add(result.white_total_time_ns);
+            stats.player1_search.add(result.black_search);
+            stats.player2_search.add(result.white_search);
         }
 
         let mapped = match result.outcome {
@@ -400,6 +453,10 @@ where
                 stats.draws += 1;
                 SeriesOutcome::DrawFiftyMoveRule
             }
+            MatchOutcome::DrawInsufficientMaterial => {
+                stats.draws += 1;
+                SeriesOutcome::DrawInsufficientMaterial
+            }
             MatchOutcome::DrawMaxPlies => {
                 stats.draws += 1;
                 SeriesOutcome::DrawMaxPlies
@@ -477,6 +534,7 @@ fn apply_seeded_random_opening(
 
         let mut chosen = book
             .choose_weighted_move(&state, &mut rng)
+            .map(Move::raw)
             .filter(|m| legal_moves.contains(m));
         if chosen.is_none() {
             let idx = rng.random_range(0..legal_moves.len());
@@ -492,16 +550,6 @@ fn apply_seeded_random_opening(
     Ok((state, opening_moves_lan))
 }
 
-#[inline]
-fn repetition_count(state: &GameState) -> usize {
-    let current = state.zobrist_key;
-    state
-        .repetition_history
-        .iter()
-        .filter(|h| **h == current)
-        .count()
-}
-
 #[cfg(test)]
 mod tests {
     use super::{
@@ -511,15 +559,20 @@ mod tests {
     use crate::engines::engine_greedy::GreedyEngine;
     use crate::engines::engine_random::RandomEngine;
     use crate::engines::engine_trait::{Engine, EngineOutput, GoParams};
+    use crate::game_state::chess_types::Move;
     use crate::game_state::game_state::GameState;
     use crate::move_generation::legal_move_generator::FastLegalMoveGenerator;
     use crate::search::board_scoring::{AlphaZeroMetric, BoardScorer, MaterialScorer};
     use crate::search::iterative_deepening::{iterative_deepening_search, SearchConfig};
+    use crate::search::search_features::SearchFeatures;
+    use crate::search::search_observer::SearchObserver;
+    use std::sync::Arc;
 
     struct ConfigurableIterativeTestEngine<S: BoardScorer + Clone + Send + Sync + 'static> {
         scorer: S,
         depth: u8,
         generator: FastLegalMoveGenerator,
+        observer: Option<Arc<dyn SearchObserver>>,
     }
 
     impl<S: BoardScorer + Clone + Send + Sync + 'static> ConfigurableIterativeTestEngine<S> {
@@ -528,11 +581,16 @@ mod tests {
                 scorer,
                 depth,
                 generator: FastLegalMoveGenerator,
+                observer: None,
             }
         }
     }
 
     impl<S: BoardScorer + Clone + Send + Sync + 'static> Engine for ConfigurableIterativeTestEngine<S> {
+        fn set_search_observer(&mut self, observer: Option<Arc<dyn SearchObserver>>) {
+            self.observer = observer;
+        }
+
         fn choose_move(
             &mut self,
             game_state: &GameState,
@@ -546,14 +604,17 @@ mod tests {
                 SearchConfig {
                     max_depth: depth,
                     movetime_ms: params.movetime_ms,
+                    features: SearchFeatures::baseline(),
+                    observer: self.observer.clone(),
+                    ..SearchConfig::default()
                 },
             )
             .map_err(|e| e.to_string())?;
 
             Ok(EngineOutput {
-                best_move: result.best_move,
-                ponder_move: None,
+                best_move: result.best_move.map(Move::from_raw),
                 info_lines: vec![format!("info string test_engine depth {}", depth)],
+                ..EngineOutput::default()
             })
         }
     }
@@ -584,6 +645,7 @@ mod tests {
                 | MatchOutcome::DrawStalemate
                 | MatchOutcome::DrawRepetition
                 | MatchOutcome::DrawFiftyMoveRule
+                | MatchOutcome::DrawInsufficientMaterial
                 | MatchOutcome::DrawMaxPlies
         ));
     }
@@ -612,6 +674,10 @@ mod tests {
         assert!(stats.player1_avg_move_time_ms >= 0.0);
         assert!(stats.player2_avg_move_time_ms >= 0.0);
         assert!(stats.overall_avg_move_time_ms >= 0.0);
+        assert_eq!(stats.player1_search.searched_moves, stats.player1_moves);
+        assert_eq!(stats.player1_search.avg_depth(), 1.0);
+        assert_eq!(stats.player2_search.avg_depth(), 2.0);
+        assert!(stats.player2_search.nodes > stats.player1_search.nodes);
         assert!(stats.outcomes.iter().all(|o| {
             matches!(
                 o,
@@ -619,6 +685,7 @@ mod tests {
                     | SeriesOutcome::DrawStalemate
                     | SeriesOutcome::DrawRepetition
                     | SeriesOutcome::DrawFiftyMoveRule
+                    | SeriesOutcome::DrawInsufficientMaterial
                     | SeriesOutcome::DrawMaxPlies
             )
         }));
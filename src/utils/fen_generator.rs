@@ -6,13 +6,25 @@
 use crate::game_state::{chess_types::*, game_state::GameState};
 use crate::utils::algebraic::square_to_algebraic;
 
+/// Serialize to FEN; Chess960 castling rights use X-FEN notation.
+///
+/// X-FEN writes `KQkq` whenever the castling rook is the outermost rook on its
+/// side of the king, so standard positions produce ordinary FEN.
 pub fn generate_fen(game_state: &GameState) -> String {
+    generate_fen_with_castling(game_state, generate_castling_field(game_state, false))
+}
+
+/// Serialize to Shredder-FEN, naming castling rooks by file (`HAha`).
+pub fn generate_shredder_fen(game_state: &GameState) -> String {
+    generate_fen_with_castling(game_state, generate_castling_field(game_state, true))
+}
+
+fn generate_fen_with_castling(game_state: &GameState, castling: String) -> String {
     let board = generate_board_field(game_state);
     let side_to_move = match game_state.side_to_move {
         Color::Light => "w",
         Color::Dark => "b",
     };
-    let castling = generate_castling_field(game_state.castling_rights);
     let en_passant = generate_en_passant_field(game_state.en_passant_square);
 
     format!(
@@ -96,20 +108,37 @@ fn piece_to_fen_char(color: Color, piece: PieceKind) -> char {
     }
 }
 
-fn generate_castling_field(rights: CastlingRights) -> String {
+fn generate_castling_field(game_state: &GameState, shredder: bool) -> String {
     let mut out = String::new();
 
-    if (rights & CASTLE_LIGHT_KINGSIDE) != 0 {
-        out.push('K');
-    }
-    if (rights & CASTLE_LIGHT_QUEENSIDE) != 0 {
-        out.push('Q');
-    }
-    if (rights & CASTLE_DARK_KINGSIDE) != 0 {
-        out.push('k');
-    }
-    if (rights & CASTLE_DARK_QUEENSIDE) != 0 {
-        out.push('q');
+    for color in [Color::Light, Color::Dark] {
+        let base = if color == Color::Light { 0u8 } else { 56u8 };
+        let rooks = game_state.pieces[color.index()][PieceKind::Rook.index()] & (0xFFu64 << base);
+        for side in [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN] {
+            if (game_state.castling_rights & castling_right_flag(color, side)) == 0 {
+                continue;
+            }
+            let file = game_state.castling_rook_files[color.index()][side];
+            // Rooks further toward the edge than the castling rook.
+            let outer_files = if side == CASTLE_SIDE_KING {
+                (0xFFu64 << (file + 1)) & 0xFF
+            } else {
+                (1u64 << file) - 1
+            };
+            let ch = if !shredder && (rooks >> base) & outer_files == 0 {
+                if side == CASTLE_SIDE_KING {
+                    'k'
+                } else {
+                    'q'
+                }
+            } else {
+                char::from(b'a' + file)
+            };
+            out.push(match color {
+                Color::Light => ch.to_ascii_uppercase(),
+                Color::Dark => ch,
+            });
+        }
     }
 
     if out.is_empty() {
@@ -119,7 +148,7 @@ fn generate_castling_field(rights: CastlingRights) -> String {
     out
 }
 
-fn generate_en_passant_field(square: Option<Square>) -> String {
+fn generate_en_passant_field(square: Option<SquareIndex>) -> String {
     let Some(square) = square else {
         return "-".to_owned();
     };
@@ -129,7 +158,7 @@ fn generate_en_passant_field(square: Option<Square>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::generate_fen;
+    use super::{generate_fen, generate_shredder_fen};
     use crate::game_state::chess_rules::STARTING_POSITION_FEN;
     use crate::game_state::chess_types::{
         Color, CASTLE_DARK_KINGSIDE, CASTLE_DARK_QUEENSIDE, CASTLE_LIGHT_KINGSIDE,
@@ -174,4 +203,30 @@ mod tests {
         let light_castle = CASTLE_LIGHT_KINGSIDE | CASTLE_LIGHT_QUEENSIDE;
         assert_eq!(reparsed.castling_rights & light_castle, 0);
     }
+
+    #[test]
+    fn chess960_castling_fields_round_trip_as_xfen_and_shredder() {
+        // Inner h-side rook on f1 behind an outer g1 rook needs a file letter in X-FEN.
+        let fen = "rk2r2r/8/8/8/8/8/8/RK2R1R1 w Eq - 0 1";
+        let parsed = parse_fen(fen).expect("X-FEN should parse");
+        assert!(parsed.chess960);
+        assert_eq!(parsed.castling_rook_files[Color::Light.index()], [4, 0]);
+        assert_eq!(parsed.castling_rook_files[Color::Dark.index()], [7, 0]);
+        assert_eq!(generate_fen(&parsed), fen);
+        assert_eq!(
+            generate_shredder_fen(&parsed),
+            "rk2r2r/8/8/8/8/8/8/RK2R1R1 w Ea - 0 1"
+        );
+
+        let shredder = "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9";
+        let parsed = parse_fen(shredder).expect("Shredder-FEN should parse");
+        assert_eq!(generate_shredder_fen(&parsed), shredder);
+        assert_eq!(
+            generate_fen(&parsed),
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w KQkq - 2 9"
+        );
+        let reparsed = parse_fen(&generate_fen(&parsed)).expect("X-FEN should parse");
+        assert_eq!(reparsed.castling_rook_files, parsed.castling_rook_files);
+        assert_eq!(reparsed.zobrist_key, parsed.zobrist_key);
+    }
 }
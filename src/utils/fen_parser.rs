@@ -3,11 +3,27 @@
 //! Builds fully-populated incremental state from a Forsyth-Edwards Notation
 //! string, including piece bitboards, rights, clocks, and occupancies.
 
+use crate::game_state::position_validation::position_issues;
 use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::search::eval_accumulator::refresh_eval_accumulator;
 use crate::search::zobrist::refresh_game_state_hashes;
 use crate::utils::algebraic::algebraic_to_square;
 
+/// How `parse_fen_with_mode` treats syntactically valid but illegal positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FenParseMode {
+    /// Reject positions that fail `GameState::validate`.
+    Strict,
+    /// Accept any well-formed board (test fixtures, kingless studies).
+    Lenient,
+}
+
+/// Lenient parse: any well-formed FEN loads, legal or not.
 pub fn parse_fen(fen: &str) -> Result<GameState, String> {
+    parse_fen_with_mode(fen, FenParseMode::Lenient)
+}
+
+pub fn parse_fen_with_mode(fen: &str, mode: FenParseMode) -> Result<GameState, String> {
     let mut parts = fen.split_whitespace();
 
     let board_part = parts.next().ok_or("Missing board layout in FEN")?;
@@ -25,7 +41,10 @@ pub fn parse_fen(fen: &str) -> Result<GameState, String> {
 
     parse_board(board_part, &mut game_state)?;
     game_state.side_to_move = parse_side_to_move(side_part)?;
-    game_state.castling_rights = parse_castling_rights(castling_part)?;
+    let (castling_rights, castling_rook_files) = parse_castling_rights(castling_part, &game_state)?;
+    game_state.castling_rights = castling_rights;
+    game_state.castling_rook_files = castling_rook_files;
+    game_state.chess960 = is_chess960_castling_setup(&game_state);
     game_state.en_passant_square = parse_en_passant_square(en_passant_part)?;
     game_state.halfmove_clock = halfmove_part
         .parse::<u16>()
@@ -34,18 +53,22 @@ pub fn parse_fen(fen: &str) -> Result<GameState, String> {
         .parse::<u16>()
         .map_err(|_| format!("Invalid fullmove number: {fullmove_part}"))?;
 
-    game_state.occupancy_by_color[Color::Light.index()] = game_state.pieces[Color::Light.index()]
-        .iter()
-        .copied()
-        .fold(0u64, |acc, bb| acc | bb);
-    game_state.occupancy_by_color[Color::Dark.index()] = game_state.pieces[Color::Dark.index()]
-        .iter()
-        .copied()
-        .fold(0u64, |acc, bb| acc | bb);
-    game_state.occupancy_all = game_state.occupancy_by_color[Color::Light.index()]
-        | game_state.occupancy_by_color[Color::Dark.index()];
+    game_state.refresh_occupancy();
+
+    if mode == FenParseMode::Strict {
+        let issues = position_issues(&game_state);
+        if !issues.is_empty() {
+            let reasons = issues
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(format!("Illegal position in FEN: {reasons}"));
+        }
+    }
 
     refresh_game_state_hashes(&mut game_state);
+    refresh_eval_accumulator(&mut game_state);
     game_state.repetition_history.clear();
     game_state.repetition_history.push(game_state.zobrist_key);
 
@@ -100,27 +123,97 @@ fn parse_side_to_move(side_part: &str) -> Result<Color, String> {
     }
 }
 
-fn parse_castling_rights(castling_part: &str) -> Result<CastlingRights, String> {
+/// Parse standard, X-FEN (`KQkq` plus inner-rook file letters) and
+/// Shredder-FEN (`HAha`) castling fields.
+///
+/// `K`/`Q` select the outermost rook on that side of the king; a file letter
+/// names the rook directly. Rights without a matching rook fall back to the
+/// standard a/h files.
+fn parse_castling_rights(
+    castling_part: &str,
+    game_state: &GameState,
+) -> Result<(CastlingRights, CastlingRookFiles), String> {
+    let mut rook_files = STANDARD_CASTLING_ROOK_FILES;
     if castling_part == "-" {
-        return Ok(0);
+        return Ok((0, rook_files));
     }
 
     let mut rights: CastlingRights = 0;
 
     for ch in castling_part.chars() {
-        match ch {
-            'K' => rights |= CASTLE_LIGHT_KINGSIDE,
-            'Q' => rights |= CASTLE_LIGHT_QUEENSIDE,
-            'k' => rights |= CASTLE_DARK_KINGSIDE,
-            'q' => rights |= CASTLE_DARK_QUEENSIDE,
+        let color = if ch.is_ascii_uppercase() {
+            Color::Light
+        } else {
+            Color::Dark
+        };
+        let base = if color == Color::Light { 0u8 } else { 56u8 };
+        let back_rank = 0xFFu64 << base;
+        let king_on_back_rank =
+            game_state.pieces[color.index()][PieceKind::King.index()] & back_rank;
+        let king_file =
+            (king_on_back_rank != 0).then(|| king_on_back_rank.trailing_zeros() as u8 % 8);
+        let rooks = game_state.pieces[color.index()][PieceKind::Rook.index()] & back_rank;
+        let rook_on = |file: u8| (rooks & (1u64 << (base + file))) != 0;
+
+        let (side, file) = match ch.to_ascii_lowercase() {
+            'k' => {
+                let file = king_file
+                    .and_then(|kf| (kf + 1..8).rev().find(|f| rook_on(*f)))
+                    .unwrap_or(7);
+                (CASTLE_SIDE_KING, file)
+            }
+            'q' => {
+                let file = king_file
+                    .and_then(|kf| (0..kf).find(|f| rook_on(*f)))
+                    .unwrap_or(0);
+                (CASTLE_SIDE_QUEEN, file)
+            }
+            'a'..='h' => {
+                let file = ch.to_ascii_lowercase() as u8 - b'a';
+                let king_file = king_file.ok_or_else(|| {
+                    format!("Castling file '{ch}' requires a king on the back rank")
+                })?;
+                if file == king_file {
+                    return Err(format!("Castling file '{ch}' is the king file"));
+                }
+                let side = if file > king_file {
+                    CASTLE_SIDE_KING
+                } else {
+                    CASTLE_SIDE_QUEEN
+                };
+                (side, file)
+            }
             _ => return Err(format!("Invalid castling rights character: {ch}")),
-        }
+        };
+
+        rights |= castling_right_flag(color, side);
+        rook_files[color.index()][side] = file;
     }
 
-    Ok(rights)
+    Ok((rights, rook_files))
+}
+
+/// Whether any castling right implies a non-standard king or rook origin.
+fn is_chess960_castling_setup(game_state: &GameState) -> bool {
+    [Color::Light, Color::Dark].into_iter().any(|color| {
+        let base = if color == Color::Light { 0u8 } else { 56u8 };
+        let has_rights = [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN]
+            .into_iter()
+            .any(|side| (game_state.castling_rights & castling_right_flag(color, side)) != 0);
+        let king_moved =
+            (game_state.pieces[color.index()][PieceKind::King.index()] & (1u64 << (base + 4))) == 0;
+        let rook_moved = [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN]
+            .into_iter()
+            .any(|side| {
+                (game_state.castling_rights & castling_right_flag(color, side)) != 0
+                    && game_state.castling_rook_files[color.index()][side]
+                        != STANDARD_CASTLING_ROOK_FILES[color.index()][side]
+            });
+        has_rights && (king_moved || rook_moved)
+    })
 }
 
-fn parse_en_passant_square(en_passant_part: &str) -> Result<Option<Square>, String> {
+fn parse_en_passant_square(en_passant_part: &str) -> Result<Option<SquareIndex>, String> {
     if en_passant_part == "-" {
         return Ok(None);
     }
@@ -153,7 +246,7 @@ fn piece_from_fen_char(ch: char) -> Option<(Color, PieceKind)> {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_fen;
+    use super::{parse_fen, parse_fen_with_mode, FenParseMode};
     use crate::game_state::chess_rules::STARTING_POSITION_FEN;
     use crate::utils::render_game_state::render_game_state;
 
@@ -170,4 +263,17 @@ mod tests {
         assert_eq!(game_state.fullmove_number, 1);
         assert_eq!(game_state.halfmove_clock, 0);
     }
+
+    #[test]
+    fn strict_mode_rejects_illegal_positions_lenient_accepts() {
+        let kingless = "8/8/8/8/8/8/4P3/4K3 w - - 0 1";
+        assert!(parse_fen(kingless).is_ok());
+        let err = parse_fen_with_mode(kingless, FenParseMode::Strict).expect_err("no dark king");
+        assert!(err.contains("Dark has 0 kings"), "{err}");
+
+        assert!(parse_fen_with_mode(STARTING_POSITION_FEN, FenParseMode::Strict).is_ok());
+        assert!(
+            parse_fen_with_mode("4k3/8/8/8/8/8/8/4K2R w KQ - 0 1", FenParseMode::Strict).is_err()
+        );
+    }
 }
@@ -4,6 +4,7 @@
 //! aware validation of captures, promotions, castling, and en-passant.
 
 use crate::game_state::{chess_types::*, game_state::GameState};
+use crate::move_generation::legal_move_apply::castling_rook_squares;
 use crate::moves::move_descriptions::*;
 use crate::utils::algebraic::{algebraic_to_square, square_to_algebraic};
 
@@ -32,6 +33,13 @@ pub fn move_description_to_long_algebraic(
         return Err("From-square piece does not belong to side to move".to_owned());
     }
 
+    // Chess960 castles are written king-takes-rook.
+    let to = if game_state.chess960 && (move_description & FLAG_CASTLING) != 0 {
+        castling_rook_squares(game_state, color_on_from, to).0
+    } else {
+        to
+    };
+
     let mut out = String::new();
     out.push_str(&square_to_algebraic(from)?);
     out.push_str(&square_to_algebraic(to)?);
@@ -65,6 +73,13 @@ pub fn long_algebraic_to_move_description(
     }
 
     let target_piece = piece_on_square(game_state, to);
+
+    if game_state.chess960
+        && moved_piece == PieceKind::King
+        && target_piece == Some((moving_color, PieceKind::Rook))
+    {
+        return chess960_castle_to_move_description(game_state, moving_color, from, to);
+    }
     let mut captured_piece = target_piece.map(|(_, piece)| piece);
     let mut flags = 0u64;
 
@@ -76,7 +91,7 @@ pub fn long_algebraic_to_move_description(
         flags |= FLAG_DOUBLE_PAWN_PUSH;
     }
 
-    if moved_piece == PieceKind::King && from.abs_diff(to) == 2 {
+    if !game_state.chess960 && moved_piece == PieceKind::King && from.abs_diff(to) == 2 {
         flags |= FLAG_CASTLING;
     }
 
@@ -136,6 +151,37 @@ pub fn long_algebraic_to_move_description(
     ))
 }
 
+/// Resolve a king-takes-own-rook move into the internal castle encoding
+/// (king destination square plus `FLAG_CASTLING`).
+fn chess960_castle_to_move_description(
+    game_state: &GameState,
+    color: Color,
+    king_from: SquareIndex,
+    rook_square: SquareIndex,
+) -> Result<u64, String> {
+    let base = if color == Color::Light { 0 } else { 56 };
+    for side in [CASTLE_SIDE_KING, CASTLE_SIDE_QUEEN] {
+        if (game_state.castling_rights & castling_right_flag(color, side)) != 0
+            && base + game_state.castling_rook_files[color.index()][side] == rook_square
+        {
+            let king_to = if side == CASTLE_SIDE_KING {
+                base + 6
+            } else {
+                base + 2
+            };
+            return Ok(pack_move_description(
+                king_from,
+                king_to,
+                PieceKind::King,
+                None,
+                None,
+                FLAG_CASTLING,
+            ));
+        }
+    }
+    Err("King takes own rook without a matching castling right".to_owned())
+}
+
 fn promotion_to_char(piece_kind: PieceKind) -> Result<char, String> {
     match piece_kind {
         PieceKind::Knight => Ok('n'),
@@ -156,7 +202,7 @@ fn char_to_promotion(ch: char) -> Result<PieceKind, String> {
     }
 }
 
-fn piece_on_square(game_state: &GameState, square: Square) -> Option<(Color, PieceKind)> {
+fn piece_on_square(game_state: &GameState, square: SquareIndex) -> Option<(Color, PieceKind)> {
     let mask = 1u64 << square;
 
     for color in [Color::Light, Color::Dark] {
@@ -221,4 +267,28 @@ mod tests {
             .expect("en-passant should parse");
         assert_ne!(ep_move & FLAG_EN_PASSANT, 0);
     }
+
+    #[test]
+    fn chess960_castles_use_king_takes_rook_notation() {
+        // King f1 castles h-side onto g1 while the rook hops to f1.
+        let mut game_state =
+            parse_fen("4k3/8/8/8/8/8/8/R4K1R w HA - 0 1").expect("FEN should parse");
+        assert!(game_state.chess960);
+        let castle =
+            long_algebraic_to_move_description("f1h1", &game_state).expect("castle should parse");
+        assert_ne!(castle & FLAG_CASTLING, 0);
+        assert_eq!(
+            move_description_to_long_algebraic(castle, &game_state).expect("castle should convert"),
+            "f1h1"
+        );
+        let king_step = long_algebraic_to_move_description("f1g1", &game_state)
+            .expect("king move should parse");
+        assert_eq!(king_step & FLAG_CASTLING, 0);
+
+        game_state.chess960 = false;
+        assert_eq!(
+            move_description_to_long_algebraic(castle, &game_state).expect("castle should convert"),
+            "f1g1"
+        );
+    }
 }
@@ -6,6 +6,7 @@
 use std::collections::BTreeMap;
 
 use crate::game_state::chess_rules::STARTING_POSITION_FEN;
+use crate::game_state::chess_types::Move;
 use crate::game_state::game_state::GameState;
 use crate::move_generation::legal_move_apply::apply_move;
 use crate::utils::long_algebraic::{
@@ -16,14 +17,14 @@ use crate::utils::long_algebraic::{
 pub struct PgnGame {
     pub headers: BTreeMap<String, String>,
     pub initial_state: GameState,
-    pub move_history: Vec<u64>,
+    pub move_history: Vec<Move>,
     pub final_state: GameState,
     pub result: String,
 }
 
 pub fn write_pgn(
     initial_state: &GameState,
-    move_history: &[u64],
+    move_history: &[Move],
     result: &str,
 ) -> Result<String, String> {
     let mut headers = BTreeMap::<String, String>::new();
@@ -46,7 +47,7 @@ pub fn write_pgn(
 
 pub fn write_pgn_with_headers(
     initial_state: &GameState,
-    move_history: &[u64],
+    move_history: &[Move],
     headers: &BTreeMap<String, String>,
 ) -> Result<String, String> {
     let mut out = String::new();
@@ -59,13 +60,13 @@ pub fn write_pgn_with_headers(
     let mut state = initial_state.clone();
     let mut movetext_parts = Vec::<String>::with_capacity(move_history.len() + 1);
     for (ply, mv) in move_history.iter().enumerate() {
-        let lan = move_description_to_long_algebraic(*mv, &state)?;
+        let lan = move_description_to_long_algebraic(mv.raw(), &state)?;
         if ply % 2 == 0 {
             movetext_parts.push(format!("{}. {}", (ply / 2) + 1, lan));
         } else {
             movetext_parts.push(lan);
         }
-        state = apply_move(&state, *mv)?;
+        state = apply_move(&state, mv.raw())?;
     }
 
     let result = headers
@@ -106,7 +107,7 @@ pub fn read_pgn(pgn: &str) -> Result<PgnGame, String> {
     };
 
     let mut state = initial_state.clone();
-    let mut move_history = Vec::<u64>::new();
+    let mut move_history = Vec::<Move>::new();
     let mut result = "*".to_owned();
 
     let movetext = strip_pgn_comments_and_variations(&movetext_lines.join(" "));
@@ -123,7 +124,7 @@ pub fn read_pgn(pgn: &str) -> Result<PgnGame, String> {
 
         let mv = long_algebraic_to_move_description(cleaned, &state)?;
         state = apply_move(&state, mv)?;
-        move_history.push(mv);
+        move_history.push(Move::from_raw(mv));
     }
 
     if let Some(header_result) = headers.get("Result") {
@@ -219,6 +220,7 @@ mod tests {
     use super::{read_pgn, write_pgn, write_pgn_with_headers};
     use std::collections::BTreeMap;
 
+    use crate::game_state::chess_types::Move;
     use crate::game_state::game_state::GameState;
     use crate::move_generation::legal_move_apply::apply_move;
     use crate::utils::long_algebraic::long_algebraic_to_move_description;
@@ -226,12 +228,12 @@ mod tests {
     #[test]
     fn pgn_round_trip_start_position_history() {
         let mut game = GameState::new_game();
-        let mut history = Vec::<u64>::new();
+        let mut history = Vec::<Move>::new();
 
         for lan in ["e2e4", "e7e5", "g1f3", "b8c6"] {
             let mv = long_algebraic_to_move_description(lan, &game).expect("LAN should parse");
             game = apply_move(&game, mv).expect("move should apply");
-            history.push(mv);
+            history.push(Move::from_raw(mv));
         }
 
         let pgn = write_pgn(&GameState::new_game(), &history, "*").expect("PGN should write");
@@ -247,7 +249,7 @@ mod tests {
         let initial =
             GameState::from_fen("8/8/8/8/8/8/4P3/4K3 w - - 0 1").expect("FEN should parse");
         let mv = long_algebraic_to_move_description("e2e4", &initial).expect("LAN should parse");
-        let history = vec![mv];
+        let history = vec![Move::from_raw(mv)];
 
         let mut headers = BTreeMap::<String, String>::new();
         headers.insert("Event".to_owned(), "Custom".to_owned());